@@ -1,4 +1,34 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// The chain environment the contract, its events and its cross-contract calls are compiled for.
+///
+/// The types match `DefaultEnvironment`. To deploy on a chain with other account, balance, timestamp or block number
+/// types, such as 20-byte addresses, change the associated types here. Amounts in the contract are in base units
+/// of `Balance`, and the default amounts, such as `min_amount`, are derived from `DECIMALS`, so a chain with
+/// 6-decimal balances only needs `DECIMALS` changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RaiserEnvironment {}
+
+impl ink::env::Environment for RaiserEnvironment {
+    const MAX_EVENT_TOPICS: usize = <ink::env::DefaultEnvironment as ink::env::Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <ink::env::DefaultEnvironment as ink::env::Environment>::AccountId;
+    type Balance = <ink::env::DefaultEnvironment as ink::env::Environment>::Balance;
+    type Hash = <ink::env::DefaultEnvironment as ink::env::Environment>::Hash;
+    type Timestamp = <ink::env::DefaultEnvironment as ink::env::Environment>::Timestamp;
+    type BlockNumber = <ink::env::DefaultEnvironment as ink::env::Environment>::BlockNumber;
+    type ChainExtension = ink::env::NoChainExtension;
+}
+
+impl RaiserEnvironment {
+    /// The number of decimals of `Balance`: one token of the chain is `10^DECIMALS` base units.
+    pub const DECIMALS: u32 = 12;
+
+    /// One hundredth of a token, in base units. Default amounts are expressed in cents.
+    pub const CENT: <RaiserEnvironment as ink::env::Environment>::Balance = 10u128.pow(Self::DECIMALS - 2);
+}
+
 /// The `Raiser` contract is a blockchain-based funding system implemented in Rust.
 /// It allows users (contributors) to contribute funds to a pool and request payouts.
 ///
@@ -7,48 +37,265 @@
 /// and the maximum number of contributors allowed.
 ///
 /// The contract emits events when a token transfer occurs and when an approval occurs that a spender is allowed to withdraw.
+/// The events are defined in the `events` module with ink! 5 signature topics.
 ///
 /// The contract has several key functions:
 /// - `new` and `default`: Constructors for creating a new instance of the contract.
+/// - `new_with_owner_policy`: Constructor that sets whether the owner can join the pool and how their payout is approved.
+/// - `new_from_snapshot`: Constructor that restores a pool exported with `export_state`, for migrations.
+/// - `export_state`: Returns one SCALE-encoded chunk of the pool state. Only the owner can call this function.
 /// - `set_max_contributors`: Sets a new maximum number of contributors. Only the owner can call this function.
 /// - `get_max_contributors`: Returns the maximum number of contributors.
+/// - `set_treasurer` and `get_treasurer`: Manage the treasurer, who can approve requests on behalf of the owner.
 /// - `contribute`: Allows a user to contribute funds to the pool.
+/// - `contribute_with_weight`: Allows a user to join the pool with a 1x, 2x or 3x membership tier.
+/// - `weight_of`: Returns the membership tier of a specific account.
+/// - `contribute_round`: Allows a member to pay their installment for the current round.
+/// - `get_current_round`: Returns the current payout round.
+/// - `authorize_recurring` and `pull_contribution`: Let a keeper collect a member's installment each round from their PSP22 allowance.
+/// - `set_payment_token`: Sets the PSP22 token used for recurring installments. Only the owner can call this function.
+/// - `all_contributed_this_round`: Checks if every member has paid their installment for the current round.
 /// - `get_contributors`: Returns a list of contributors and their respective balances.
 /// - `request_token`: Allows a contributor to request a payout.
+/// - `request_token_with_memo`: Allows a contributor to request a payout with the hash of a note stating its purpose.
+/// - `can_contribute` and `can_request`: Dry-run the validation of `contribute` and `request_token` without mutating state.
 /// - `approve_request`: Allows the owner to approve a payout request.
+/// - `get_request_details`: Returns the requester, amount, status and timestamps of a payout request.
+/// - `remove_contributor`: Allows the owner to remove a contributor and refund their contribution.
+/// - `sponsor_pool`: Allows a third party to add matching funds to the pot without becoming a member.
+/// - `get_sponsored_amount` and `get_round_sponsorship`: Return the matching funds received.
+/// - `defer_turn`: Allows the next eligible requester to move to the back of the payout queue.
+/// - `skip_inactive`: Allows anyone to move an unresponsive requester to the back of the queue once the request timeout has passed.
+/// - `missed_turns_of`: Returns how many times an account was skipped for being unresponsive.
 /// - `get_next_requester`: Returns the AccountId of the next eligible requester.
 /// - `get_completed_payouts`: Returns the number of completed payouts.
 /// - `get_payout_history`: Returns the payout history.
 /// - `next_contribution_cycle`: Initiates the next contribution cycle.
 /// - `all_paid`: Checks if all contributors have been paid.
 /// - `get_total_supply`: Returns the total token supply.
+/// - `get_available_balance`: Returns the native balance the contract can pay out while keeping the existential deposit.
 /// - `total_contributors`: Returns the total number of contributors.
 /// - `balance_of`: Returns the balance of a specific account.
+/// - `set_private_mode` and `is_private_mode`: Manage whether individual contribution amounts are hidden from other accounts.
+/// - `verify_invariants`: Checks the internal consistency of the contract storage.
+/// - `get_config` and `update_config`: Read all pool settings at once, or update several of them in one call.
+/// - `get_receipts`: Returns a page of the contribution receipts of a specific account.
+/// - `get_payout_history_from` and `get_contribution_history`: Return payouts and receipts from a cursor, with the cursor to resume from.
+/// - `grant_role`, `revoke_role` and `has_role`: Manage the roles that guard every privileged message. Only admins can grant or revoke roles.
+/// - `get_next_requester_score`: Returns the next eligible requester together with their need score.
+/// - `get_untracked_balance` and `allocate_donations`: Report native funds sent to the contract outside of its messages, and let an admin add them to the payouts.
+/// - `get_pool_stats`: Returns the value locked, lifetime payouts and member count of the pool, for protocol-level dashboards.
+/// - `pools_of_member` and `global_stats`: Return the pools deployed by this contract an account is a member of, and the sum of their statistics.
+/// - `tick` and `get_contribution_deadline`: Let a keeper emit contribution reminders and overdue notices for the current round.
+/// - `claim` and `expire_escrow`: Let a recipient claim an escrowed payout, and return unclaimed payouts to the pool after the claim window.
+/// - `set_membership_gate` and `get_membership_gate`: Restrict membership to holders of a PSP22 token or a PSP34 collection. Only an admin can set the gate.
+/// - `get_pool_snapshot`: Returns the supply, phase, cycle, member count, next requester, pending request and settings of the pool in one call.
+/// - `get_prepaid`: Returns the overpayment a member has credited toward future installments.
+/// - `prune_history`: Deletes the receipts and closed requests of past cycles to reclaim storage deposits. Only an admin can call this function.
+/// - `commit_contribution` and `reveal_contribution`: Let a member commit to the hash of their installment first and pay it in a second transaction, so the amount is not visible before the commitment is included.
+///   With a `commit_period`, commitments close at `get_commit_deadline` and the rest of the round only accepts reveals, so no member can wait for the amounts of the others.
+/// - `contribute_with_referrer` and `get_referrals`: Let a new member name who invited them, and list the members an account referred.
+/// - `fund_fee_reserve` and `withdraw_referral_rewards`: Fund the referral bonuses, and let referrers withdraw the bonuses they earned.
+/// - `wind_down`, `refund_members` and `terminate`: End the pool: stop contributions, refund every member their net position after a timelock, then remove the contract. Only an admin can call these functions.
+/// - `is_payment_phase`: Checks if payouts have started, either with a full pool or with the quorum after the enrollment deadline.
+/// - `round_status` and `has_paid`: Show which members have paid the current round, and whether an account paid a given round.
+/// - `RaiserPool`: The trait other contracts use to call any pool through `contract_ref!`, implemented by the contract.
+/// - `get_balances_breakdown`: Splits the native funds the contract accounts for into member principal, prepayments, sponsorships, fees and escrow.
+/// - `cosign_request`: Lets a second approver co-sign a payout request of the owner, when the owner policy requires it.
+/// - `current_period` and `is_contribution_window_open`: Return the scheduled period and whether contributions are accepted right now.
+/// - `contribute_with_preference` and `claim_installment`: Let a member choose to receive their payout split across the remaining rounds, and claim each installment.
+/// - `fund_insurance` and `insurance_balance`: Fund and query the reserve that covers the installments of members who default mid-cycle.
+/// - `set_converter`, `set_accepted_asset` and `contribute_round_with_asset`: Let members pay their installment in an accepted alternative asset, swapped into the native token by a converter contract.
+/// - `set_status` and `get_status`: Move the pool through its lifecycle statuses. Archived pools reject every mutating message but stay readable.
+/// - `set_price_oracle` and `get_min_amount`: Peg the minimum amount to a fiat reference converted with a price oracle, falling back to the fixed minimum when the price is stale.
+/// - `check_in`, `set_join_constraints` and `get_join_constraints`: Keep sybil accounts out with a minimum account age and a proof-of-personhood attestation.
+/// - `dust_balance`: Returns the rounding remainders collected under the `Accumulate` dust policy.
+/// - `create_team`, `get_team` and `team_of`: Let a member share their payout slot with a team whose members can pay the installments and request the payout, which goes to the slot holder.
+/// - `get_pending_requests`: Returns a page of the payout requests awaiting approval, for an approval queue.
+/// - `enroll_next_cycle` and `get_next_cycle_enrollments`: Let accounts enroll in the next cycle while the payouts of the current one are underway.
+/// - `join`: Registers the caller as a member and fixes their queue position without a payment. They then pay each installment with `contribute_round`.
+/// - `propose_queue_swap` and `confirm_swap`: Let two members swap their payout positions once both confirm.
+/// - `clone_pool_with_members`: Deploys a successor pool with the same config and members, pre-registered for the next rotation.
+/// - `withdraw_payout` and `get_pending_withdrawal`: Let a recipient of a pull-delivery pool withdraw the payouts credited to them.
+/// - `get_existential_deposit`: Returns the existential deposit of the chain, below which contributions and refunds are rejected.
+/// - `force_approve`: Lets anyone approve or expire a request the approvers left pending past the approval timeout.
+/// - `set_attestation_registry` and `get_attestation_registry`: Require new members to be attested by a KYC registry contract.
+/// - `pending_config`: Returns the settings change queued until the current cycle ends.
+/// - `set_metadata_uri` and `get_metadata`: Point explorers to a rich pool description and the hash that verifies it.
+/// - `set_automation` and `get_automation`: Delegate operations such as approvals to an automation operator with permission flags.
+/// - `cover_for` and `get_cover_debts`: Let a member pay the missing installment of another member, recovered with interest from their next payout.
+/// - `estimated_payout_round` and `estimated_payout_timestamp`: Estimate when a member receives their next payout.
+/// - `propose_impeachment`, `vote_impeachment`, `execute_impeachment` and `get_impeachment`: Let a supermajority of the members replace the owner.
+/// - `get_lockup`: Returns the part of a payout held back for the installments the recipient still owes.
+/// - `set_alias` and `get_contributors_with_aliases`: Let members register a display name that is listed with the contributors.
+/// - `distribute_surplus` and `claim_surplus`: Split the untracked funds of the contract among the members, who claim their share.
+/// - `set_tvl_cap` and `get_tvl_cap`: Cap the total value the pool may hold.
+/// - `retry_payout` and `redirect_payout`: Deliver a payout whose transfer failed, to the recipient or, with the consent of the owner and the recipient, to another account.
+/// - `inactive_members` and `get_last_active_at`: Detect members who have not interacted with the pool for a while.
+/// - `create_pool` and `set_pool_creation_limits`: Let any account deploy a pool it owns, within the creation fee and per-account limit set by an admin.
+/// - `raise_dispute` and `resolve_dispute`: Escalate a disputed escrowed payout to an external arbitrator and enforce its ruling.
+/// - `leaderboard`: Ranks the members by the number of installments they paid on time.
+/// - `debug_dump`: Returns a SCALE-encoded storage section for support tooling.
+/// - `propose_extension` and `vote_extension`: Let a majority of the members extend the contribution deadline of the current round.
+/// - `set_remote_beneficiary` and `set_xcm_gateway`: Route the payouts of a member to an account on another parachain.
+/// - `get_member_root`, `get_member_proof` and `verify_member_proof`: Commit to the member set with a Merkle root and prove membership against it.
+/// - `is_stuck` and `rescue_withdraw`: Let members reclaim their net contribution from a pool that has stopped making progress.
+/// - `register_session_key` and `revoke_session_key`: Let members delegate paying their installments to a low-privilege key for a limited time.
+/// - `get_cycle_value_report`: Returns the fiat-equivalent total of a cycle's contributions, valued at the prices they were made at.
+/// - `code_info`: Returns the code hash, storage version and crate version of the running build, so members and auditors can verify it.
+/// - `get_enrollment_order`: Returns who joined the current cycle, and when, in join order, however the payout queue was rearranged.
+/// - `get_time_credit` and `get_total_time_credit`: Return the time-weighted contribution credit (amount × time locked) of a member and of the pool.
+/// - `simulate_cycle`: Previews the round-by-round pots, fees and payout schedule of a hypothetical pool before it is created.
 ///
 /// The contract also defines several error types for handling common error scenarios.
 ///
 /// The contract uses the ink! smart contract programming language, which is designed for writing secure and efficient blockchain contracts with Rust.
 
-#[ink::contract]
+#[ink::contract(env = crate::RaiserEnvironment)]
 /// The `Raiser` struct represents a blockchain-based funding system.
 ///
 /// It contains several fields:
-/// - `total_supply`: The total amount of funds in the system.
+/// - `total_supply`: The total amount contributed in the current cycle.
 /// - `address_to_amount_funded`: A mapping from account IDs to the amount they have funded and a boolean indicating if they have contributed.
 /// - `contributed`: A mapping from account IDs to a boolean indicating if they have contributed.
 /// - `balance`: A vector of tuples, each containing an account ID and the balance of that account.
-/// - `min_amount`: The minimum amount that can be contributed.
+/// - `config`: The pool settings, such as the minimum contribution amount and the maximum number of contributors.
 /// - `owner`: The account ID of the owner of the contract.
-/// - `contributors`: A vector of account IDs of the contributors.
+/// - `queue`, `queue_head` and `queue_tail`: The payout queue: the account IDs of the contributors in payout order, stored at the indices
+///   from `queue_head` up to `queue_tail`. Weighted members occupy one slot per share. Paying out the front slot only moves `queue_head`,
+///   so it costs the same whatever the size of the pool. Swaps, skips and deferrals reorder it, while `enrollment_order` keeps the order
+///   in which members joined.
+/// - `queued_slots`: The number of payout slots each account has left in the queue.
 /// - `contributors_count`: The total number of contributors.
-/// - `requests`: A vector of tuples, each containing an account ID and the amount they have requested.
+/// - `requests`: A vector of tuples, each containing an account ID, the amount they have requested and the request ID.
 /// - `completed_payouts`: The total number of completed payouts.
 /// - `payout_history`: A vector of tuples, each containing an account ID and the amount they have been paid.
-/// - `max_contributors`: The maximum number of contributors allowed.
 /// - `contribution_cycle`: The current contribution cycle.
+/// - `weights`: A mapping from account IDs to the membership tier they chose at join time.
+/// - `total_weight`: The sum of the weights of all contributors, i.e. the number of payout slots in a rotation.
+/// - `current_round`: The current payout round. A cycle is a full rotation made of one round per payout slot.
+/// - `round_paid`: A mapping from account IDs to the last round in which they paid their installment.
+/// - `sponsored_amount`: The total amount of matching funds received from sponsors.
+/// - `unallocated_sponsorship`: Matching funds not yet paid out that are split evenly across the remaining payouts.
+/// - `round_sponsorship`: A mapping from round numbers to matching funds added to that round's payout.
+/// - `treasurer`: The account ID of the optional treasurer, who can approve requests but cannot change the pool settings.
+/// - `next_request_id`: The ID assigned to the next payout request.
+/// - `request_details`: A mapping from request IDs to the details of every payout request.
+/// - `payment_token`: The account ID of the PSP22 token used to pull recurring installments.
+/// - `recurring`: A mapping from account IDs to their recurring contribution authorization.
+/// - `turn_started_at`: The timestamp at which the next requester's turn started, i.e. the last time the payout queue or the pool size changed.
+/// - `missed_turns`: A mapping from account IDs to the number of turns they were skipped for being unresponsive.
+/// - `receipt_count`: A mapping from account IDs to the number of contribution receipts they have, i.e. their next receipt nonce.
+/// - `receipts`: A mapping from an account ID and a receipt nonce to the contribution receipt.
+/// - `roles`: A mapping from a role and an account ID to whether the account holds the role.
+/// - `role_holders`: Every granted role and the account holding it, so that an impeachment can revoke them all.
+/// - `last_paid_round`: A mapping from account IDs to the round in which they last received a payout.
+/// - `payouts_received`: A mapping from account IDs to the number of payouts they have received.
+/// - `deferred_round`: A mapping from account IDs to the last round in which they deferred or were skipped.
+/// - `held_balance`: The native balance the contract should hold according to its own accounting: contributions and sponsorships minus payouts and refunds.
+/// - `donations`: The total amount of untracked native funds an admin has allocated to the payouts.
+/// - `total_payouts`: The number of payouts made over all contribution cycles.
+/// - `total_paid_out`: The total amount paid out over all contribution cycles.
+/// - `round_opened_at`: The timestamp at which the current round opened, from which the contribution deadline is computed.
+/// - `due_notified_round`: The last round for which `tick` emitted the contribution reminders.
+/// - `overdue_notified_round`: The last round for which `tick` emitted the overdue notices.
+/// - `escrow`: The approved payouts awaiting a claim, with the timestamp at which their claim window ends.
+/// - `escrowed_total`: The total amount of escrowed payouts and unclaimed payout installments, which is not available for new payouts.
+/// - `membership_gate`: The token an account must hold to join the pool, if any.
+/// - `prepaid`: The overpayments of each member credited toward their future installments.
+/// - `cycle_first_request`: The ID of the first payout request of each contribution cycle that has not been pruned.
+/// - `pruned_before_cycle`: The first contribution cycle whose history has not been pruned.
+/// - `first_receipt`: The nonce of the first receipt of each member that has not been pruned.
+/// - `commitments`: The installment commitment of each member and the round it was made in.
+/// - `referrers`: The referrer of each member whose referral bonus has not been paid yet.
+/// - `referrals`: The members each account has referred.
+/// - `referral_rewards`: The referral bonuses each referrer has earned and not withdrawn yet.
+/// - `fee_reserve`: The funds set aside to pay referral bonuses.
+/// - `received`: The total amount paid out to each member over all contribution cycles.
+/// - `wind_down_at`: The timestamp after which the members can be refunded, once an admin has started winding down the pool.
+/// - `wound_down`: Whether the members have been refunded and the contract can be terminated.
+/// - `paid_rounds`: The rounds each member has paid an installment for.
+/// - `principal`: The native contributions of the members that have not been paid out or refunded yet.
+/// - `prepaid_total`: The sum of the prepayments of every member.
+/// - `sponsorship_balance`: The sponsorships and donations that have not been paid out yet.
+/// - `rewards_owed`: The referral bonuses earned and not withdrawn yet.
+/// - `owner_policy`: Whether the owner can join the pool and how their payout is approved.
+/// - `cosigners`: The approver who co-signed each payout request of the owner.
+/// - `payout_preferences`: How each member wants to receive their payout.
+/// - `pending_payouts`: The unclaimed part of the split payout of each member.
+/// - `insurance`: The reserve that covers the installments of members who default mid-cycle.
+/// - `covered_defaults`: The installments the insurance reserve has covered for each member.
+/// - `converter`: The DEX router that swaps accepted alternative assets into the native token.
+/// - `accepted_assets`: The alternative PSP22 assets members can pay their installments with.
+/// - `status`: The lifecycle status of the pool.
+/// - `price_oracle`: The price oracle that converts a fiat minimum into native token units, if any.
+/// - `join_constraints`: The anti-sybil constraints new members have to meet.
+/// - `first_seen`: The block number at which each account first checked in.
+/// - `dust`: The rounding remainders collected under the `Accumulate` dust policy.
+/// - `teams`: The team members sharing the payout slot of each beneficiary.
+/// - `team_of`: The beneficiary whose payout slot each team member acts for.
+/// - `next_cycle_enrollments`: The accounts enrolled in the next cycle and their first installments.
+/// - `queue_swaps`: The proposed payout position swaps awaiting confirmation.
+/// - `next_swap_id`: The ID assigned to the next proposed swap.
+/// - `successors`: The successor pools deployed with `clone_pool_with_members`.
+/// - `withdrawals`: The approved payouts of pull-delivery pools that their recipients have not withdrawn yet.
+/// - `cycle_started_at`: The timestamp at which the current cycle started, from which the cycle duration is computed.
+/// - `cycle_defaults`: The number of installments the insurance reserve has covered in the current cycle.
+/// - `attestation_registry`: The KYC attestation registry new members must be attested by, if any.
+/// - `pending_config`: The settings change queued by `update_config` until the current cycle ends, if any.
+/// - `metadata_uri`: The IPFS or HTTP pointer to the rich description of the pool, such as its images and terms documents.
+/// - `metadata_hash`: The hash of the document behind `metadata_uri`, with which explorers verify its integrity.
+/// - `automation`: The permission flags delegated to each automation operator, such as `AUTOMATE_APPROVE`.
+/// - `automation_operators`: The accounts holding automation permissions, so that an impeachment can revoke them all.
+/// - `cover_debts`: The installments other members covered for each member with `cover_for`, as `(creditor, amount owed)` pairs, oldest first.
+/// - `impeachment`: The latest proposal of the members to replace the owner, if any.
+/// - `impeachment_votes`: The members who voted for each impeachment proposal.
+/// - `lockups`: The part of each payout held back for the installments the recipient still owes in the cycle.
+/// - `aliases`: The display names members registered for themselves.
+/// - `surplus_claims`: The distributed surplus each member can claim.
+/// - `unclaimed_surplus`: The total distributed surplus the members have not claimed yet.
+/// - `tvl_cap`: The most native funds the pool may hold, if capped.
+/// - `failed_payouts`: The approved payouts whose transfer failed, by request ID, awaiting a retry or a redirect.
+/// - `failed_payout_ids`: The request IDs of the open failed payouts, in the order they failed, so they can be listed without scanning every request ID.
+/// - `last_active_at`: The timestamp of the last interaction of each member, such as a payment, request, claim or vote.
+/// - `creation_fee`: The fee an account pays to create a pool with `create_pool`.
+/// - `max_pools_per_account`: The most pools one account may create with `create_pool`. 0 disables permissionless pool creation.
+/// - `pools_created`: The number of pools each account has created with `create_pool`.
+/// - `created_pools`: The pools created with `create_pool`, oldest first.
+/// - `registry`: The contract that deployed the pool and collects its reports, if it was deployed by another pool.
+/// - `member_pools`: The pools deployed by this contract each account is a member of, as reported by the pools.
+/// - `reported_stats`: The statistics each pool deployed by this contract reported last.
+/// - `global_stats`: The sum of the statistics reported by the pools deployed by this contract.
+/// - `arbitrator`: The external arbitration contract disputes on escrowed payouts are escalated to, if any.
+/// - `arbitration_fee`: The fee a claimant pays, forwarded to the arbitrator, to raise a dispute.
+/// - `disputes`: The disputes raised on escrowed payouts, by ID.
+/// - `next_dispute_id`: The ID assigned to the next dispute.
+/// - `disputed_escrow`: The open dispute freezing the escrowed payout of each recipient.
+/// - `on_time_scores`: The number of installments each member paid by the contribution deadline, across cycles.
+/// - `leaderboard`: The members with the highest on-time scores, highest first, updated with every on-time payment.
+/// - `extension`: The latest proposal to extend the contribution deadline of a round.
+/// - `extension_votes`: The members who voted for each deadline extension proposal.
+/// - `xcm_gateway`: The XCM gateway contract that routes payouts to remote beneficiaries, if any.
+/// - `remote_beneficiaries`: The remote-chain beneficiary each member registered for their payouts.
+/// - `member_tree`: The non-empty nodes of the Merkle tree committing to the member set, by level and index. Level 0 holds the leaves.
+/// - `member_leaf`: The leaf of each member in the member tree.
+/// - `next_member_leaf`: The next unused leaf of the member tree.
+/// - `free_member_leaves`: The leaves of removed members, reused before new leaves.
+/// - `session_keys`: The session keys members registered, mapped to the member and the expiry of the key.
+/// - `cycle_values`: The contributions of each contribution cycle, valued at the oracle price when they were made.
+/// - `enrollment_order`: The members of the current cycle in the order they joined, unaffected by changes to the payout queue and kept after removals.
+/// - `joined_at`: The time each member of the current cycle joined.
+/// - `time_credits`: The time-weighted contribution credit of each member.
+/// - `total_time_credit`: The time-weighted contribution credit of all members together.
+/// - `round_pot`: The installments collected in the current round, which the next payout pays out.
 ///
 /// The struct is used to manage the state of the contract, including the total supply of funds, the contributors, and the payouts.
 mod raiser {
+    use crate::events::*;
+    use crate::traits::{PoolRegistry, RaiserPool};
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
     #[ink(storage)]
     pub struct Raiser {
@@ -56,63 +303,1259 @@ mod raiser {
         address_to_amount_funded: Mapping<AccountId, (Balance, bool)>,
         contributed: Mapping<AccountId, bool>,
         balance: Vec<(AccountId, Balance)>,
-        min_amount:Balance,
+        config: Config,
         owner:AccountId,
-        contributors: Vec<AccountId>, 
+        queue: Mapping<u32, AccountId>,
+        queue_head: u32,
+        queue_tail: u32,
+        queued_slots: Mapping<AccountId, u32>,
         contributors_count: u128, 
-        requests: Vec<(AccountId, Balance)>,
+        requests: Vec<(AccountId, Balance, u128)>,
         completed_payouts: u128,
         payout_history: Vec<(AccountId, Balance)>,
-        max_contributors:u128,
-        contribution_cycle:u128
-
-    }
-
-    /// Event emitted when a token transfer occurs.
-    #[ink(event)]
-    pub struct Transfer {
-        #[ink(topic)]
-        from: Option<AccountId>,
-        #[ink(topic)]
-        to: Option<AccountId>,
-        value: Balance,
-    }
+        contribution_cycle:u128,
+        weights: Mapping<AccountId, u8>,
+        total_weight: u128,
+        current_round: u128,
+        round_paid: Mapping<AccountId, u128>,
+        sponsored_amount: Balance,
+        unallocated_sponsorship: Balance,
+        round_sponsorship: Mapping<u128, Balance>,
+        treasurer: Option<AccountId>,
+        next_request_id: u128,
+        request_details: Mapping<u128, RequestInfo>,
+        payment_token: Option<AccountId>,
+        recurring: Mapping<AccountId, RecurringIntent>,
+        turn_started_at: Timestamp,
+        missed_turns: Mapping<AccountId, u32>,
+        receipt_count: Mapping<AccountId, u32>,
+        receipts: Mapping<(AccountId, u32), Receipt>,
+        roles: Mapping<(Role, AccountId), bool>,
+        role_holders: Vec<(Role, AccountId)>,
+        last_paid_round: Mapping<AccountId, u128>,
+        payouts_received: Mapping<AccountId, u32>,
+        deferred_round: Mapping<AccountId, u128>,
+        held_balance: Balance,
+        donations: Balance,
+        total_payouts: u128,
+        total_paid_out: Balance,
+        round_opened_at: Timestamp,
+        due_notified_round: u128,
+        overdue_notified_round: u128,
+        escrow: Mapping<AccountId, (Balance, Timestamp)>,
+        escrowed_total: Balance,
+        membership_gate: Option<MembershipGate>,
+        prepaid: Mapping<AccountId, Balance>,
+        cycle_first_request: Mapping<u128, u128>,
+        pruned_before_cycle: u128,
+        first_receipt: Mapping<AccountId, u32>,
+        commitments: Mapping<AccountId, (Hash, u128)>,
+        referrers: Mapping<AccountId, AccountId>,
+        referrals: Mapping<AccountId, Vec<AccountId>>,
+        referral_rewards: Mapping<AccountId, Balance>,
+        fee_reserve: Balance,
+        received: Mapping<AccountId, Balance>,
+        wind_down_at: Option<Timestamp>,
+        wound_down: bool,
+        paid_rounds: Mapping<(AccountId, u128), bool>,
+        principal: Balance,
+        prepaid_total: Balance,
+        sponsorship_balance: Balance,
+        rewards_owed: Balance,
+        owner_policy: OwnerPolicy,
+        cosigners: Mapping<u128, AccountId>,
+        payout_preferences: Mapping<AccountId, PayoutPreference>,
+        pending_payouts: Mapping<AccountId, PendingPayout>,
+        insurance: Balance,
+        covered_defaults: Mapping<AccountId, Balance>,
+        converter: Option<AccountId>,
+        accepted_assets: Mapping<AccountId, bool>,
+        status: PoolStatus,
+        price_oracle: Option<PriceOracle>,
+        join_constraints: JoinConstraints,
+        first_seen: Mapping<AccountId, BlockNumber>,
+        dust: Balance,
+        teams: Mapping<AccountId, Vec<AccountId>>,
+        team_of: Mapping<AccountId, AccountId>,
+        next_cycle_enrollments: Vec<(AccountId, Balance)>,
+        queue_swaps: Mapping<u128, QueueSwap>,
+        next_swap_id: u128,
+        successors: Vec<AccountId>,
+        withdrawals: Mapping<AccountId, Balance>,
+        cycle_started_at: Timestamp,
+        cycle_defaults: u128,
+        attestation_registry: Option<AccountId>,
+        pending_config: Option<ConfigPatch>,
+        metadata_uri: String,
+        metadata_hash: Option<Hash>,
+        automation: Mapping<AccountId, u8>,
+        automation_operators: Vec<AccountId>,
+        cover_debts: Mapping<AccountId, CoverDebts>,
+        impeachment: Option<Impeachment>,
+        impeachment_votes: Mapping<(u128, AccountId), bool>,
+        lockups: Mapping<AccountId, Balance>,
+        aliases: Mapping<AccountId, Vec<u8>>,
+        surplus_claims: Mapping<AccountId, Balance>,
+        unclaimed_surplus: Balance,
+        tvl_cap: Option<Balance>,
+        failed_payouts: Mapping<u128, FailedPayout>,
+        failed_payout_ids: Vec<u128>,
+        last_active_at: Mapping<AccountId, Timestamp>,
+        creation_fee: Balance,
+        max_pools_per_account: u32,
+        pools_created: Mapping<AccountId, u32>,
+        created_pools: Vec<AccountId>,
+        registry: Option<AccountId>,
+        member_pools: Mapping<AccountId, Vec<AccountId>>,
+        reported_stats: Mapping<AccountId, PoolStats>,
+        global_stats: GlobalStats,
+        arbitrator: Option<AccountId>,
+        arbitration_fee: Balance,
+        disputes: Mapping<u128, Dispute>,
+        next_dispute_id: u128,
+        disputed_escrow: Mapping<AccountId, u128>,
+        on_time_scores: Mapping<AccountId, Score>,
+        leaderboard: Vec<(AccountId, Score)>,
+        extension: Option<DeadlineExtension>,
+        extension_votes: Mapping<(u128, AccountId), bool>,
+        xcm_gateway: Option<AccountId>,
+        remote_beneficiaries: Mapping<AccountId, RemoteBeneficiary>,
+        member_tree: Mapping<(u8, u32), Hash>,
+        member_leaf: Mapping<AccountId, u32>,
+        next_member_leaf: u32,
+        free_member_leaves: Vec<u32>,
+        session_keys: Mapping<AccountId, (AccountId, Timestamp)>,
+        cycle_values: Mapping<u128, CycleValueReport>,
+        enrollment_order: Vec<AccountId>,
+        joined_at: Mapping<AccountId, Timestamp>,
+        time_credits: Mapping<AccountId, TimeCredit>,
+        total_time_credit: TimeCredit,
+        round_pot: Balance,
 
-    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
-    #[ink(event)]
-    pub struct Approval {
-        #[ink(topic)]
-        owner: AccountId,
-        #[ink(topic)]
-        spender: AccountId,
-        value: Balance,
     }
 
-
     /// The ERC-20 error types.
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-   
     /// `Error` is an enumeration of all possible errors that can occur in our blockchain application.
     ///
     /// Variants:
     /// - `InsufficientBalance`: This error occurs when a user tries to make a transaction but their balance is too low.
     /// - `LowAmount`: This error occurs when the amount specified for a transaction is considered too low.
     /// - `NotContractOwner`: This error occurs when a user who is not the owner of the contract tries to perform an action that requires ownership.
-    /// - `AlreadyContributed`: This error occurs when a user tries to contribute to a contract but they have already made a contribution.
+    /// - `AlreadyMember`: This error occurs when a member tries to join again. `round` is the last round they paid and `amount` what they have funded in the cycle.
+    /// - `AlreadyPaidThisRound`: This error occurs when a member tries to pay the installment of `round` again. `amount` is what they paid for it.
     /// - `NotNextContributor`: This error occurs when a user tries to request for withdrawal but it's not their turn to withdraw.
     /// - `NotPaymentPhase`: This error occurs when a user tries to request a payment but is not in the payment phase.
     /// - `TransferError`: This error occurs when there's a problem transferring funds between accounts.
+    /// - `NotContributor`: This error occurs when an action targets an account that is not a contributor of the pool.
+    /// - `InvalidWeight`: This error occurs when a user chooses a membership tier of zero or above `MAX_WEIGHT`.
+    /// - `RoundIncomplete`: This error occurs when a payout is approved before every member has contributed to the current round.
+    /// - `InvalidRound`: This error occurs when an action targets a round that has already been paid out.
+    /// - `Unauthorized`: This error occurs when a user who is neither the owner nor the treasurer tries to perform a treasury action.
+    /// - `NoRecurringAuthorization`: This error occurs when an installment is pulled from a member without a remaining recurring authorization.
+    /// - `PaymentTokenNotSet`: This error occurs when an installment is pulled before the owner has configured the PSP22 payment token.
+    /// - `InvalidConfig`: This error occurs when the owner tries to apply pool settings that fail validation.
+    /// - `TimeoutNotReached`: This error occurs when a requester is skipped, or a request is force-approved, before the relevant timeout has passed, or when no timeout is configured.
+    /// - `RequestPending`: This error occurs when a requester who has already requested their payout is skipped.
+    /// - `InsufficientContractBalance`: This error occurs when the contract's available balance cannot cover a payout on top of the existential deposit.
+    /// - `InvalidSnapshot`: This error occurs when the data passed to `new_from_snapshot` cannot be decoded.
+    /// - `InvalidMaxContributors`: This error occurs when the maximum number of contributors is set below the current number of contributors or above `MAX_POOL_SIZE`.
+    /// - `PoolFull`: This error occurs when a user tries to join a pool that already has `max_contributors` contributors, or `MAX_POOL_SIZE` if no maximum is set, or enroll in a next cycle that is full.
+    /// - `NoUntrackedBalance`: This error occurs when an admin tries to allocate donations but the contract holds no native funds outside of its accounting.
+    /// - `RemindersDisabled`: This error occurs when `tick` is called while no contribution period is configured.
+    /// - `NothingToClaim`: This error occurs when an account without an escrowed payout tries to claim or expire one.
+    /// - `ClaimWindowClosed`: This error occurs when a recipient tries to claim an escrowed payout after its claim window has ended.
+    /// - `ClaimWindowOpen`: This error occurs when someone tries to return an escrowed payout to the pool before its claim window has ended.
+    /// - `NotTokenHolder`: This error occurs when an account that does not hold the token required by the membership gate tries to join the pool.
+    /// - `InvalidCycle`: This error occurs when an admin tries to prune the history of the current contribution cycle or a later one.
+    /// - `NoCommitment`: This error occurs when a member reveals an installment without a commitment for the current round.
+    /// - `CommitmentMismatch`: This error occurs when the revealed amount and salt do not match the commitment of the member.
+    /// - `InvalidReferrer`: This error occurs when a member names themselves as their referrer.
+    /// - `WindingDown`: This error occurs when someone tries to contribute to a pool that is winding down, or to start a wind-down twice.
+    /// - `NotWoundDown`: This error occurs when an admin tries to refund the members before the wind-down timelock has passed, or to terminate the contract before the members were refunded.
+    /// - `EnrollmentClosed`: This error occurs when a user tries to join a pool that started its payouts with a quorum after the enrollment deadline.
+    /// - `OwnerCannotJoin`: This error occurs when the owner tries to join a pool whose owner policy blocks them.
+    /// - `CoSignatureRequired`: This error occurs when a payout request of the owner is approved without the co-signature of another approver, or the owner tries to co-sign it.
+    /// - `OutsideContributionWindow`: This error occurs when a user tries to contribute outside of the contribution window of the current period.
+    /// - `ConverterNotSet`: No converter contract is configured.
+    /// - `AssetNotAccepted`: The asset is not accepted for contributions.
+    /// - `SlippageExceeded`: The converter returned less than the minimum amount the member accepts.
+    /// - `PoolNotActive`: The pool is not active, so it does not accept contributions, requests or payouts.
+    /// - `PoolArchived`: The pool is archived and only allows read access.
+    /// - `InvalidStatusTransition`: The pool cannot move from its current status to the requested one.
+    /// - `AccountTooNew`: The account checked in too recently, or not at all, to join the pool.
+    /// - `NotVerifiedPerson`: The proof-of-personhood contract does not attest the account.
+    /// - `InvalidTeamMember`: A team member is a pool member, already in a team, listed twice, or the team is too large.
+    /// - `ArithmeticOverflow`: A counter or balance would overflow.
+    /// - `SwapNotFound`: No pending swap exists with the given ID.
+    /// - `CloneFailed`: The successor pool, or a pool created with `create_pool`, could not be deployed.
+    /// - `BelowExistentialDeposit`: This error occurs when a contribution, or the excess refunded from it, is below the chain's existential deposit and could not be transferred out again.
+    /// - `NotAttested`: The attestation registry does not attest the account.
+    /// - `MetadataUriTooLong`: The metadata URI is longer than `MAX_METADATA_URI_LEN` bytes.
+    /// - `NoPendingRequest`: A payout is approved for a request that is not pending.
+    /// - `ImpeachmentActive`: An impeachment proposal is already open.
+    /// - `ImpeachmentNotFound`: No open impeachment proposal exists with the given ID.
+    /// - `AlreadyVoted`: The member has already voted for the proposal.
+    /// - `QuorumNotReached`: The votes for the impeachment proposal do not reach the supermajority yet.
+    /// - `AliasTooLong`: The display name is longer than `MAX_ALIAS_LEN` bytes.
+    /// - `NoMembers`: The pool has no members to distribute the surplus to.
+    /// - `CapExceeded`: The contribution would raise the funds held by the pool above its TVL cap.
+    /// - `FailedPayoutNotFound`: No failed payout is queued under the given request ID.
+    /// - `PoolLimitReached`: The caller has already created the most pools an account may create.
+    /// - `ArbitratorNotSet`: No arbitration contract is configured.
+    /// - `ArbitrationFailed`: The arbitration contract did not accept the dispute.
+    /// - `DisputeNotFound`: No open dispute exists with the given ID.
+    /// - `PayoutDisputed`: The escrowed payout is frozen by an open dispute.
+    /// - `InvalidRuling`: A split ruling gives the recipient more than 10,000 basis points.
+    /// - `InvalidExtension`: The proposed deadline extension is 0 or longer than `MAX_DEADLINE_EXTENSION`.
+    /// - `ExtensionActive`: The current round already has a deadline extension proposal.
+    /// - `ExtensionNotFound`: No open deadline extension proposal exists for the current round.
+    /// - `NotStuck`: The pool is not stuck, so members cannot rescue their funds yet.
+    /// - `InvalidSessionKey`: The session key is the member itself or belongs to another member, or its expiry is in the past or too far ahead.
+    /// - `ClaimsOutstanding`: The pool cannot be terminated while escrowed payouts, withdrawals, surplus, referral rewards or prepayments are still owed.
+    /// - `CannotCoverSelf`: This error occurs when a member tries to cover their own missed installment.
+    /// - `UnknownPool`: A pool report comes from an account that is not a pool deployed by this contract.
+    /// - `CommitPhaseClosed`: This error occurs when a member commits to an installment after the commit deadline of the round.
+    /// - `RevealNotOpen`: This error occurs when a member reveals an installment before the commit deadline of the round.
+    /// - `RevealOnly`: This error occurs when a member pays an installment without a commitment after the commit deadline of the round.
     pub enum Error {
         InsufficientBalance,
         LowAmount,
         NotContractOwner,
-        AlreadyContributed,
+        AlreadyMember { round: u128, amount: Balance },
+        AlreadyPaidThisRound { round: u128, amount: Balance },
         NotNextContributor,
         NotPaymentPhase,
         TransferError,
+        NotContributor,
+        InvalidWeight,
+        RoundIncomplete,
+        InvalidRound,
+        Unauthorized,
+        NoRecurringAuthorization,
+        PaymentTokenNotSet,
+        InvalidConfig,
+        TimeoutNotReached,
+        RequestPending,
+        InsufficientContractBalance,
+        InvalidSnapshot,
+        InvalidMaxContributors,
+        PoolFull,
+        NoUntrackedBalance,
+        RemindersDisabled,
+        NothingToClaim,
+        ClaimWindowClosed,
+        ClaimWindowOpen,
+        NotTokenHolder,
+        InvalidCycle,
+        NoCommitment,
+        CommitmentMismatch,
+        InvalidReferrer,
+        WindingDown,
+        NotWoundDown,
+        EnrollmentClosed,
+        OwnerCannotJoin,
+        CoSignatureRequired,
+        OutsideContributionWindow,
+        ConverterNotSet,
+        AssetNotAccepted,
+        SlippageExceeded,
+        PoolNotActive,
+        PoolArchived,
+        InvalidStatusTransition,
+        AccountTooNew,
+        NotVerifiedPerson,
+        InvalidTeamMember,
+        ArithmeticOverflow,
+        SwapNotFound,
+        CloneFailed,
+        BelowExistentialDeposit,
+        NotAttested,
+        MetadataUriTooLong,
+        NoPendingRequest,
+        ImpeachmentActive,
+        ImpeachmentNotFound,
+        AlreadyVoted,
+        QuorumNotReached,
+        AliasTooLong,
+        NoMembers,
+        CapExceeded,
+        FailedPayoutNotFound,
+        PoolLimitReached,
+        ArbitratorNotSet,
+        ArbitrationFailed,
+        DisputeNotFound,
+        PayoutDisputed,
+        InvalidRuling,
+        InvalidExtension,
+        ExtensionActive,
+        ExtensionNotFound,
+        NotStuck,
+        InvalidSessionKey,
+        ClaimsOutstanding,
+        CannotCoverSelf,
+        UnknownPool,
+        CommitPhaseClosed,
+        RevealNotOpen,
+        RevealOnly,
+    }
+
+    /// `InvariantViolation` describes an internal inconsistency found by `verify_invariants`.
+    ///
+    /// Variants:
+    /// - `TotalSupplyMismatch`: The sum of the `balance` vector does not match `total_supply`.
+    /// - `ContributorsCountMismatch`: The number of members in the `balance` vector does not match `contributors_count`.
+    /// - `DuplicateBalanceEntry`: An account appears more than once in the `balance` vector.
+    /// - `ContractUnderfunded`: The contract's available balance is less than the native balance it accounts for.
+    /// - `BreakdownMismatch`: The sub-balances returned by `get_balances_breakdown` do not add up to the accounted native balance.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum InvariantViolation {
+        TotalSupplyMismatch { balances_sum: Balance, total_supply: Balance },
+        ContributorsCountMismatch { members: u128, contributors_count: u128 },
+        DuplicateBalanceEntry(AccountId),
+        ContractUnderfunded { contract_balance: Balance, held_balance: Balance },
+        BreakdownMismatch { breakdown_total: Balance, held_balance: Balance },
+    }
+
+    /// `BalancesBreakdown` splits the native funds the contract accounts for, returned by `get_balances_breakdown`.
+    ///
+    /// Fields:
+    /// - `principal`: The contributions of the members that have not been paid out or refunded yet.
+    /// - `prepaid`: The overpayments credited toward future installments.
+    /// - `sponsorship`: The sponsorships and allocated donations that have not been paid out yet.
+    /// - `fees`: The fee reserve and the referral bonuses earned and not withdrawn yet.
+    /// - `escrow`: The approved payouts awaiting a claim.
+    /// - `insurance`: The reserve that covers defaulted installments.
+    /// - `dust`: The rounding remainders collected under the `Accumulate` dust policy.
+    /// - `surplus`: The distributed surplus the members have not claimed yet.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BalancesBreakdown {
+        pub principal: Balance,
+        pub prepaid: Balance,
+        pub sponsorship: Balance,
+        pub fees: Balance,
+        pub escrow: Balance,
+        pub insurance: Balance,
+        pub dust: Balance,
+        pub surplus: Balance,
+    }
+
+    impl BalancesBreakdown {
+        /// Returns the sum of every sub-balance.
+        pub fn total(&self) -> Balance {
+            [self.principal, self.prepaid, self.sponsorship, self.fees, self.escrow, self.insurance, self.dust, self.surplus]
+                .into_iter()
+                .fold(0, Balance::saturating_add)
+        }
+    }
+
+    /// `SponsorDistribution` describes how a sponsor's matching funds are paid out.
+    ///
+    /// Variants:
+    /// - `SplitEvenly`: The funds are split evenly across all remaining payouts of the cycle.
+    /// - `Round`: The funds are added to the payout of a specific round.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum SponsorDistribution {
+        SplitEvenly,
+        Round(u128),
+    }
+
+    /// `RequestStatus` is the lifecycle state of a payout request.
+    ///
+    /// Variants:
+    /// - `Pending`: The request is waiting for approval.
+    /// - `Approved`: The request was approved and paid.
+    /// - `Cancelled`: The request was dropped without being paid, e.g. because the requester was removed.
+    /// - `Expired`: The request was not approved within the approval timeout and was dropped with `force_approve`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum RequestStatus {
+        Pending,
+        Approved,
+        Cancelled,
+        Expired,
+    }
+
+    /// `RequestInfo` holds the details of a payout request returned by `get_request_details`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RequestInfo {
+        pub requester: AccountId,
+        pub amount: Balance,
+        pub status: RequestStatus,
+        pub created_at: Timestamp,
+        pub decided_at: Option<Timestamp>,
+        pub memo_hash: Option<Hash>,
+    }
+
+    /// `QueueSwap` is a proposed exchange of payout positions between two members, returned by `get_queue_swap`.
+    ///
+    /// The positions are swapped once both `a` and `b` have confirmed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct QueueSwap {
+        pub a: AccountId,
+        pub b: AccountId,
+        pub confirmed_a: bool,
+        pub confirmed_b: bool,
+    }
+
+    /// `Impeachment` is a member proposal to replace the owner, returned by `get_impeachment`.
+    ///
+    /// Fields:
+    /// - `id`: The ID of the proposal.
+    /// - `new_owner`: The account that becomes the owner if the proposal passes.
+    /// - `ends_at`: The timestamp at which voting closes.
+    /// - `votes`: The sum of the membership tiers of the members who voted for the proposal.
+    /// - `electorate`: The total membership weight when the proposal was opened.
+    /// - `executed`: Whether the owner was replaced.
+    /// - `cancelled`: Whether the proposal was cancelled because the contribution cycle ended before it passed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Impeachment {
+        pub id: u128,
+        pub new_owner: AccountId,
+        pub ends_at: Timestamp,
+        pub votes: u128,
+        pub electorate: u128,
+        pub executed: bool,
+        pub cancelled: bool,
+    }
+
+    /// `DeadlineExtension` is a member proposal to extend the contribution deadline of a round, returned by `get_extension`.
+    ///
+    /// Fields:
+    /// - `id`: The ID of the proposal.
+    /// - `round`: The round whose deadline is extended.
+    /// - `ms`: The extension in milliseconds.
+    /// - `votes`: The sum of the membership tiers of the members who voted for the proposal.
+    /// - `electorate`: The total membership weight when the proposal was opened.
+    /// - `executed`: Whether the deadline was extended.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct DeadlineExtension {
+        pub id: u128,
+        pub round: u128,
+        pub ms: Timestamp,
+        pub votes: u128,
+        pub electorate: u128,
+        pub executed: bool,
+    }
+
+    /// `Role` is a permission that guards privileged messages.
+    ///
+    /// Variants:
+    /// - `Admin`: Can change the pool settings, manage members and grant or revoke roles.
+    /// - `Approver`: Can approve payout requests.
+    /// - `Pauser`: Can pause and resume the pool.
+    /// - `Upgrader`: Can upgrade the contract code.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Role {
+        Admin,
+        Approver,
+        Pauser,
+        Upgrader,
+    }
+
+    /// `MembershipGate` restricts who can join the pool, checked with a cross-contract call when a new member contributes.
+    ///
+    /// Variants:
+    /// - `Psp22`: The account must hold at least `min_balance` of the PSP22 `token`.
+    /// - `Psp34`: The account must own at least one NFT of the PSP34 `collection`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum MembershipGate {
+        Psp22 { token: AccountId, min_balance: Balance },
+        Psp34 { collection: AccountId },
+    }
+
+    /// `PriceOracle` pegs the minimum amount to a fiat reference, such as 10 USD.
+    ///
+    /// Fields:
+    /// - `oracle`: The oracle contract, queried with `PriceOracle::get_price` for the native amount per fiat unit and the time it was last updated.
+    /// - `fiat_amount`: The minimum amount in the smallest fiat unit, such as cents.
+    /// - `max_age`: How old, in milliseconds, a price can be before it is considered stale.
+    ///
+    /// When the price is stale or the oracle cannot be queried, the fixed `min_amount` of the config applies.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PriceOracle {
+        pub oracle: AccountId,
+        pub fiat_amount: Balance,
+        pub max_age: Timestamp,
+    }
+
+    /// `JoinConstraints` are the optional anti-sybil checks a new member has to pass.
+    ///
+    /// Fields:
+    /// - `min_account_age`: How many blocks must have passed since the account first called `check_in`. 0 disables the check.
+    /// - `personhood`: The proof-of-personhood contract, queried with `Personhood::is_verified`, if any.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct JoinConstraints {
+        pub min_account_age: BlockNumber,
+        pub personhood: Option<AccountId>,
+    }
+
+    /// `PeriodUnit` is the clock a contribution schedule follows.
+    ///
+    /// Variants:
+    /// - `Milliseconds`: The block timestamp.
+    /// - `Blocks`: The block number.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PeriodUnit {
+        #[default]
+        Milliseconds,
+        Blocks,
+    }
+
+    /// `PayoutPreference` is how a member wants to receive their payout, chosen when they join.
+    ///
+    /// Variants:
+    /// - `LumpSum`: The whole payout is paid on approval.
+    /// - `Split`: The payout is split evenly across the remaining rounds of the cycle and claimed with `claim_installment`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PayoutPreference {
+        #[default]
+        LumpSum,
+        Split,
+    }
+
+    /// `PendingPayout` is the unclaimed part of a split payout, returned by `get_pending_payout`.
+    ///
+    /// Fields:
+    /// - `remaining`: The amount not claimed yet.
+    /// - `installments_left`: The number of installments not claimed yet.
+    /// - `claimed_until`: The last round whose installment was claimed, or the round before the payout.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PendingPayout {
+        pub remaining: Balance,
+        pub installments_left: u128,
+        pub claimed_until: u128,
+    }
+
+    /// `WinnerPayout` is the share of a round's pot paid to one winner, kept by `approve_next` until it is delivered.
+    struct WinnerPayout {
+        recipient: AccountId,
+        request_id: u128,
+        amount: Balance,
+        debt: Balance,
+        lockup: Balance,
+        split: bool,
+        pull: bool,
+        escrowed: bool,
+        failed: bool,
+    }
+
+    /// `FailedPayout` is a payout whose transfer failed at approval, returned by `get_failed_payout`.
+    ///
+    /// Fields:
+    /// - `recipient`: The requester the payout was approved for.
+    /// - `amount`: The amount held for the recipient.
+    /// - `redirect_to`: The destination proposed with `redirect_payout`, if any.
+    /// - `owner_consent`: Whether an admin agreed to send the payout to `redirect_to`.
+    /// - `recipient_consent`: Whether the recipient agreed to send the payout to `redirect_to`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct FailedPayout {
+        pub recipient: AccountId,
+        pub amount: Balance,
+        pub redirect_to: Option<AccountId>,
+        pub owner_consent: bool,
+        pub recipient_consent: bool,
+    }
+
+    /// `RemoteBeneficiary` is an account on another parachain that receives the payouts of a member through the XCM gateway.
+    ///
+    /// Fields:
+    /// - `para_id`: The ID of the parachain.
+    /// - `account`: The 32-byte account on that parachain.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RemoteBeneficiary {
+        pub para_id: u32,
+        pub account: [u8; 32],
+    }
+
+    /// `Ruling` is the decision of the arbitrator on a disputed payout, enforced by `resolve_dispute`.
+    ///
+    /// Variants:
+    /// - `Release`: The recipient can claim the whole payout.
+    /// - `Refund`: The payout returns to the pool and is split evenly among the remaining payouts.
+    /// - `Split`: The recipient can claim the given share of the payout, in basis points, and the rest returns to the pool.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Ruling {
+        Release,
+        Refund,
+        Split(u16),
+    }
+
+    /// `Dispute` is a disputed escrowed payout escalated to the arbitrator, returned by `get_dispute`.
+    ///
+    /// Fields:
+    /// - `claimant`: The member who raised the dispute.
+    /// - `recipient`: The member whose escrowed payout is disputed.
+    /// - `amount`: The escrowed amount at the time of the dispute.
+    /// - `ruling`: The ruling of the arbitrator, once resolved.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Dispute {
+        pub claimant: AccountId,
+        pub recipient: AccountId,
+        pub amount: Balance,
+        pub ruling: Option<Ruling>,
+    }
+
+    /// `StorageSection` selects the part of the storage `debug_dump` returns.
+    ///
+    /// Variants:
+    /// - `Members`: Every member with their balance, weight, last paid round and prepaid amount, as `Vec<(AccountId, Balance, u8, u128, Balance)>`.
+    /// - `Queue`: The payout slots in order, as `Vec<AccountId>`.
+    /// - `Requests`: The pending payout requests as `(requester, amount, request ID)`, as `Vec<(AccountId, Balance, u128)>`.
+    /// - `Config`: The settings and the queued settings change, as `(Config, Option<ConfigPatch>)`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum StorageSection {
+        Members,
+        Queue,
+        Requests,
+        Config,
+    }
+
+    /// `OwnerPolicy` resolves the conflict of interest when the owner is also a member, set at pool creation.
+    ///
+    /// Variants:
+    /// - `Unrestricted`: The owner can join and approve their own payout.
+    /// - `Blocked`: The owner cannot join the pool.
+    /// - `CoSigned`: The owner can join, but their payout needs the co-signature of another approver.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum OwnerPolicy {
+        #[default]
+        Unrestricted,
+        Blocked,
+        CoSigned,
+    }
+
+    /// `PayoutOrder` decides who the next eligible requester is.
+    ///
+    /// Variants:
+    /// - `JoinOrder`: The first payout slot in the queue is next.
+    /// - `NeedScore`: The member with the highest need score is next, ties going to the earliest slot in the queue.
+    ///   Members who deferred or were skipped in the current round are only picked if nobody else is left.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PayoutOrder {
+        #[default]
+        JoinOrder,
+        NeedScore,
+    }
+
+    /// `DustPolicy` decides where the remainder goes when an amount does not divide evenly.
+    ///
+    /// Variants:
+    /// - `FinalPayout`: The remainder is carried over and added to the last payout of the cycle, or to the last refund.
+    /// - `Accumulate`: The remainder is kept in the dust balance, so every payout receives the same share.
+    /// - `Fees`: The remainder is added to the fee reserve, so every payout receives the same share.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum DustPolicy {
+        #[default]
+        FinalPayout,
+        Accumulate,
+        Fees,
+    }
+
+    /// `Overpayment` decides what happens to the amount a member sends above their installment.
+    ///
+    /// Variants:
+    /// - `Absorb`: The whole amount is contributed to the pot.
+    /// - `Refund`: The excess is sent back to the member in the same call.
+    /// - `Prepay`: The excess is credited toward the member's future installments.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Overpayment {
+        #[default]
+        Absorb,
+        Refund,
+        Prepay,
+    }
+
+    /// `PayoutDelivery` decides how an approved payout reaches the recipient.
+    ///
+    /// Variants:
+    /// - `Push`: The payout is transferred to the recipient on approval.
+    /// - `Pull`: The payout is credited to the recipient's pending withdrawal, which they collect with `withdraw_payout`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PayoutDelivery {
+        #[default]
+        Push,
+        Pull,
+    }
+
+    /// `ApprovalTimeoutAction` decides what `force_approve` does with a request the approvers left pending past the approval timeout.
+    ///
+    /// Variants:
+    /// - `ForceApprove`: The request is approved and paid as if an approver had approved it.
+    /// - `Expire`: The request is dropped, and the requester may request again.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ApprovalTimeoutAction {
+        #[default]
+        ForceApprove,
+        Expire,
+    }
+
+    /// `WinnerSplit` decides how the pot of a round is split when a round pays out to several winners.
+    ///
+    /// Variants:
+    /// - `Even`: Every winner receives the same share.
+    /// - `ByWeight`: Every winner receives a share proportional to their membership tier.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum WinnerSplit {
+        #[default]
+        Even,
+        ByWeight,
+    }
+
+    /// The scale of the need score: a member who never received a payout scores this much per round waited.
+    pub const NEED_SCORE_SCALE: u128 = 1_000;
+
+    /// `Config` holds the pool settings returned by `get_config`.
+    ///
+    /// Fields:
+    /// - `min_amount`: The minimum amount that can be contributed per share.
+    /// - `max_contributors`: The maximum number of contributors allowed.
+    /// - `private_mode`: Whether individual balances are only visible to the account itself and the owner.
+    /// - `payout_order`: How the next eligible requester is chosen.
+    /// - `request_timeout`: How long, in milliseconds, the next requester has to request or defer their payout before anyone can skip them. 0 disables skipping.
+    /// - `contribution_period`: How long, in milliseconds, members have to pay their installment after a round opens. 0 disables reminders.
+    /// - `grace_period`: How long, in milliseconds, after the contribution deadline a missing installment is reported as overdue.
+    /// - `claim_window`: How long, in milliseconds, a recipient has to claim an approved payout. 0 transfers payouts directly on approval.
+    /// - `overpayment`: What happens to the amount a member sends above their installment.
+    /// - `referral_bonus`: The bonus paid from the fee reserve to the referrer of a member who completes a full cycle. 0 disables referral rewards.
+    /// - `min_contributors`: The quorum with which payouts can start after the enrollment deadline, even if the pool is not full. 0 requires a full pool.
+    /// - `enrollment_deadline`: The timestamp after which the quorum is enough to start payouts. 0 disables the quorum.
+    /// - `period_unit`: Whether `period_length` and `contribution_window` count milliseconds or blocks.
+    /// - `period_length`: The length of a scheduled period, aligned to timestamp or block 0. 0 disables the schedule.
+    /// - `contribution_window`: How long contributions are accepted at the start of each period.
+    /// - `dust_policy`: Where the remainder goes when sponsorships or refunds do not divide evenly.
+    /// - `payout_delivery`: Whether approved payouts are pushed to the recipient or held until they withdraw them.
+    /// - `approval_timeout`: How long, in milliseconds, a request can wait for approval before anyone can resolve it with `force_approve`. 0 disables the escape hatch.
+    /// - `approval_timeout_action`: Whether `force_approve` approves or expires a timed-out request.
+    /// - `payout_lockup`: Whether the installments a recipient still owes in the cycle are held back from their payout and paid automatically each round.
+    /// - `cover_interest_bps`: The interest, in basis points, a member owes on an installment another member covered with `cover_for`. At most 10 000.
+    /// - `winners_per_round`: How many members each round pays out to, at least 1. It is set at creation and cannot be changed with `update_config`.
+    /// - `winner_split`: How the pot of a round is split between its winners.
+    /// - `time_weighted_credit`: Whether surplus distributions follow the time-weighted credit of the members instead of their weight,
+    ///   and ties in the need score order go to the member with the most credit.
+    /// - `commit_period`: How long, in milliseconds, after a round opens members can commit to their installment with `commit_contribution`.
+    ///   After it, the round is reveal-only: installments are only accepted from `reveal_contribution`. 0 disables the commit deadline.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Config {
+        pub min_amount: Balance,
+        pub max_contributors: u128,
+        pub private_mode: bool,
+        pub request_timeout: Timestamp,
+        pub payout_order: PayoutOrder,
+        pub contribution_period: Timestamp,
+        pub grace_period: Timestamp,
+        pub claim_window: Timestamp,
+        pub overpayment: Overpayment,
+        pub referral_bonus: Balance,
+        pub min_contributors: u128,
+        pub enrollment_deadline: Timestamp,
+        pub period_unit: PeriodUnit,
+        pub period_length: u64,
+        pub contribution_window: u64,
+        pub dust_policy: DustPolicy,
+        pub payout_delivery: PayoutDelivery,
+        pub approval_timeout: Timestamp,
+        pub approval_timeout_action: ApprovalTimeoutAction,
+        pub cover_interest_bps: u32,
+        pub payout_lockup: bool,
+        pub winners_per_round: u32,
+        pub winner_split: WinnerSplit,
+        pub time_weighted_credit: bool,
+        pub commit_period: Timestamp,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                min_amount: 50 * crate::RaiserEnvironment::CENT,
+                max_contributors: 0,
+                private_mode: false,
+                request_timeout: 0,
+                payout_order: PayoutOrder::JoinOrder,
+                contribution_period: 0,
+                grace_period: 0,
+                claim_window: 0,
+                overpayment: Overpayment::Absorb,
+                referral_bonus: 0,
+                min_contributors: 0,
+                enrollment_deadline: 0,
+                period_unit: PeriodUnit::Milliseconds,
+                period_length: 0,
+                contribution_window: 0,
+                dust_policy: DustPolicy::FinalPayout,
+                payout_delivery: PayoutDelivery::Push,
+                approval_timeout: 0,
+                approval_timeout_action: ApprovalTimeoutAction::ForceApprove,
+                cover_interest_bps: 0,
+                payout_lockup: false,
+                winners_per_round: 1,
+                winner_split: WinnerSplit::Even,
+                time_weighted_credit: false,
+                commit_period: 0,
+            }
+        }
+    }
+
+    /// `ConfigPatch` lists the pool settings to change in `update_config`. Fields left as `None` are kept.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ConfigPatch {
+        pub min_amount: Option<Balance>,
+        pub max_contributors: Option<u128>,
+        pub private_mode: Option<bool>,
+        pub request_timeout: Option<Timestamp>,
+        pub payout_order: Option<PayoutOrder>,
+        pub contribution_period: Option<Timestamp>,
+        pub grace_period: Option<Timestamp>,
+        pub claim_window: Option<Timestamp>,
+        pub overpayment: Option<Overpayment>,
+        pub referral_bonus: Option<Balance>,
+        pub min_contributors: Option<u128>,
+        pub enrollment_deadline: Option<Timestamp>,
+        pub period_unit: Option<PeriodUnit>,
+        pub period_length: Option<u64>,
+        pub contribution_window: Option<u64>,
+        pub dust_policy: Option<DustPolicy>,
+        pub payout_delivery: Option<PayoutDelivery>,
+        pub approval_timeout: Option<Timestamp>,
+        pub approval_timeout_action: Option<ApprovalTimeoutAction>,
+        pub cover_interest_bps: Option<u32>,
+        pub payout_lockup: Option<bool>,
+        pub time_weighted_credit: Option<bool>,
+        pub commit_period: Option<Timestamp>,
+    }
+
+    /// `PoolSnapshot` is the pool state exported by `export_state` and restored by `new_from_snapshot`.
+    ///
+    /// The fields are laid out in chunk order, so concatenating every chunk returned by `export_state`
+    /// yields the SCALE encoding of a `PoolSnapshot`:
+    /// - Chunk 0: the settings and counters, from `config` to `next_request_id`.
+    /// - Chunk 1: `contributors`, the payout queue.
+    /// - Chunk 2: `balance`, the member ledger.
+    /// - Chunk 3: `members`, the state and the funds held for every member.
+    /// - Chunk 4: `payout_history`.
+    /// - Chunk 5: `enrollment_order`, with the time each account joined.
+    /// - Chunk 6: `requests` and `failed_payouts`, the pending requests and the payouts awaiting a retry.
+    /// - Chunk 7: `next_cycle_enrollments`.
+    /// - Chunk 8: `funds`, the sub-balances of the pool.
+    ///
+    /// Claims of accounts that are no longer members, such as escrowed payouts of an earlier cycle or referral rewards, cannot be listed,
+    /// so `new_from_snapshot` rejects a snapshot taken before they are paid out. Receipts, roles, recurring authorizations, sponsorships
+    /// of later cycles, governance proposals and open disputes are not exported.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PoolSnapshot {
+        pub config: Config,
+        pub total_supply: Balance,
+        pub contributors_count: u128,
+        pub total_weight: u128,
+        pub completed_payouts: u128,
+        pub contribution_cycle: u128,
+        pub current_round: u128,
+        pub round_pot: Balance,
+        pub next_request_id: u128,
+        pub contributors: Vec<AccountId>,
+        pub balance: Vec<(AccountId, Balance)>,
+        pub members: Vec<MemberSnapshot>,
+        pub payout_history: Vec<(AccountId, Balance)>,
+        pub enrollment_order: Vec<(AccountId, Timestamp)>,
+        pub requests: Vec<(u128, RequestInfo)>,
+        pub failed_payouts: Vec<(u128, FailedPayout)>,
+        pub next_cycle_enrollments: Vec<(AccountId, Balance)>,
+        pub funds: FundsSnapshot,
+    }
+
+    /// `MemberSnapshot` is the state of a member exported within a `PoolSnapshot`.
+    ///
+    /// Fields:
+    /// - `account`, `weight` and `round_paid`: The member, their tier and the last round they paid.
+    /// - `paid_rounds`: The rounds of the current cycle the member paid.
+    /// - `received`, `last_paid_round` and `payouts_received`: The payouts the member received.
+    /// - `prepaid`: The prepayment of the member.
+    /// - `time_credit`: The time-weighted credit of the member.
+    /// - `referrer`: The account to reward once the member completes the cycle, if any.
+    /// - `covered_defaults` and `cover_debts`: The installments the reserve and other members covered for the member.
+    /// - `escrow`, `lockup`, `withdrawal`, `pending_payout`, `surplus_claim` and `referral_rewards`: The funds held for the member.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct MemberSnapshot {
+        pub account: AccountId,
+        pub weight: u8,
+        pub round_paid: u128,
+        pub paid_rounds: Vec<u128>,
+        pub received: Balance,
+        pub last_paid_round: Option<u128>,
+        pub payouts_received: u32,
+        pub prepaid: Balance,
+        pub time_credit: TimeCredit,
+        pub referrer: Option<AccountId>,
+        pub covered_defaults: Balance,
+        pub cover_debts: CoverDebts,
+        pub escrow: Option<(Balance, Timestamp)>,
+        pub lockup: Balance,
+        pub withdrawal: Balance,
+        pub pending_payout: Option<PendingPayout>,
+        pub surplus_claim: Balance,
+        pub referral_rewards: Balance,
+    }
+
+    /// `FundsSnapshot` lists the sub-balances of a pool exported within a `PoolSnapshot`.
+    ///
+    /// The totals that `new_from_snapshot` rebuilds from the members and requests, `held_balance`, `prepaid_total`, `sponsorship_balance`,
+    /// `rewards_owed`, `escrowed_total` and `unclaimed_surplus`, are exported to check the rebuilt values against.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct FundsSnapshot {
+        pub held_balance: Balance,
+        pub principal: Balance,
+        pub prepaid_total: Balance,
+        pub sponsorship_balance: Balance,
+        pub unallocated_sponsorship: Balance,
+        pub round_sponsorships: Vec<(u128, Balance)>,
+        pub fee_reserve: Balance,
+        pub rewards_owed: Balance,
+        pub escrowed_total: Balance,
+        pub insurance: Balance,
+        pub dust: Balance,
+        pub unclaimed_surplus: Balance,
+    }
+
+    /// The number of chunks returned by `export_state`.
+    pub const SNAPSHOT_CHUNKS: u32 = 9;
+
+    /// `Receipt` is the record of a single contribution, returned by `get_receipts`.
+    ///
+    /// Fields:
+    /// - `nonce`: The index of the receipt among the receipts of the member.
+    /// - `cycle` and `round`: The contribution cycle and payout round the contribution was made in.
+    /// - `amount`: The amount contributed.
+    /// - `block_number`: The block in which the contribution was made.
+    /// - `fiat_value`: The value of the contribution in the smallest fiat unit, at the price the price oracle reported when it was made.
+    ///   `None` if no price oracle is set or its price was stale.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Receipt {
+        pub nonce: u32,
+        pub cycle: u128,
+        pub round: u128,
+        pub amount: Balance,
+        pub block_number: BlockNumber,
+        pub fiat_value: Option<Balance>,
+    }
+
+    /// `TimeCredit` is the time-weighted contribution credit of a member, or of the whole pool.
+    ///
+    /// Fields:
+    /// - `locked`: The amount contributed and not withdrawn.
+    /// - `credit`: The credit accrued until `updated_at`, in amount × milliseconds locked.
+    /// - `updated_at`: When `credit` was last brought up to date.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TimeCredit {
+        pub locked: Balance,
+        pub credit: u128,
+        pub updated_at: Timestamp,
+    }
+
+    impl TimeCredit {
+        /// Returns the credit accrued until `now`.
+        pub fn accrued(&self, now: Timestamp) -> u128 {
+            let elapsed = now.saturating_sub(self.updated_at) as u128;
+            self.credit.saturating_add(self.locked.saturating_mul(elapsed))
+        }
+    }
+
+    /// `SimulatedPayout` is a payout of a simulated round, returned within `SimulatedRound`.
+    ///
+    /// Fields:
+    /// - `position`: The join position of the recipient, starting at 0.
+    /// - `amount`: The amount the recipient receives.
+    /// - `held_back`: The part of the share held back to pay the recipient's remaining installments, if payout lock-ups are enabled.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SimulatedPayout {
+        pub position: u32,
+        pub amount: Balance,
+        pub held_back: Balance,
+    }
+
+    /// `SimulatedRound` is a round of a simulated cycle, returned within `SimulationResult`.
+    ///
+    /// Fields:
+    /// - `round`: The number of the round in the cycle, starting at 1.
+    /// - `opens_after`: How long after the start of the cycle the round opens, in `period_unit`s. 0 for every round if no schedule is configured.
+    /// - `pot`: The installments collected in the round.
+    /// - `fees`: The referral bonuses due when the round completes the cycle, assuming every member was referred.
+    ///   They are paid from the fee reserve, not from the pot.
+    /// - `payouts`: The payouts of the round, in queue order.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SimulatedRound {
+        pub round: u32,
+        pub opens_after: u64,
+        pub pot: Balance,
+        pub fees: Balance,
+        pub payouts: Vec<SimulatedPayout>,
+    }
+
+    /// `SimulationResult` previews the economics of a cycle, returned by `simulate_cycle`.
+    ///
+    /// Fields:
+    /// - `installment`: The installment every member pays per round.
+    /// - `rounds`: The rounds of the cycle, in order.
+    /// - `total_contributed`: The sum of the pots of every round.
+    /// - `total_fees`: The sum of the fees of every round.
+    /// - `creation_fee`: The fee to create the pool with `create_pool` on this pool.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SimulationResult {
+        pub installment: Balance,
+        pub rounds: Vec<SimulatedRound>,
+        pub total_contributed: Balance,
+        pub total_fees: Balance,
+        pub creation_fee: Balance,
+    }
+
+    /// `CycleValueReport` sums the contributions of a contribution cycle at the prices they were made at, returned by `get_cycle_value_report`.
+    ///
+    /// Fields:
+    /// - `contributed`: The total amount contributed in the cycle, in native token units.
+    /// - `fiat_value`: The fiat value of the priced contributions, in the smallest fiat unit.
+    /// - `unpriced`: The part of `contributed` made while no fresh oracle price was available, which `fiat_value` does not cover.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct CycleValueReport {
+        pub contributed: Balance,
+        pub fiat_value: Balance,
+        pub unpriced: Balance,
+    }
+
+    /// `PoolStats` summarizes a pool for protocol-level dashboards, returned by `get_pool_stats`.
+    ///
+    /// The counters are maintained as the pool mutates, so an explorer or a pool directory can aggregate many pools
+    /// with one call each, without walking their members or history.
+    ///
+    /// Fields:
+    /// - `total_value_locked`: The native funds the pool holds from contributions and sponsorships.
+    /// - `total_payouts` and `total_paid_out`: The number and total amount of payouts over all contribution cycles.
+    /// - `members`: The number of members in the current contribution cycle.
+    /// - `active`: Whether the current contribution cycle has any members.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PoolStats {
+        pub total_value_locked: Balance,
+        pub total_payouts: u128,
+        pub total_paid_out: Balance,
+        pub members: u128,
+        pub active: bool,
+    }
+
+    /// `GlobalStats` sums the statistics of the pools a contract deployed, returned by `global_stats`.
+    ///
+    /// Fields:
+    /// - `total_value_locked`, `total_payouts`, `total_paid_out` and `members`: The sums of the `PoolStats` the pools reported last.
+    /// - `active_pools`: The number of pools whose current contribution cycle has members.
+    /// - `pools`: The number of pools deployed with `create_pool` or `clone_pool_with_members`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct GlobalStats {
+        pub total_value_locked: Balance,
+        pub total_payouts: u128,
+        pub total_paid_out: Balance,
+        pub members: u128,
+        pub active_pools: u32,
+        pub pools: u32,
+    }
+
+    /// `PoolStatus` is the lifecycle status of a pool.
+    ///
+    /// Variants:
+    /// - `Draft`: The pool is being set up and does not accept members yet.
+    /// - `Active`: The pool accepts contributions, requests and payouts.
+    /// - `Suspended`: The pool is paused. Members can still claim and withdraw what they are owed.
+    /// - `Completed`: The pool was wound down and the members were refunded.
+    /// - `Archived`: The pool only allows read access.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PoolStatus {
+        Draft,
+        Active,
+        Suspended,
+        Completed,
+        Archived,
+    }
+
+    /// `CycleAdvanceOutcome` is the result of `next_contribution_cycle`.
+    ///
+    /// Variants:
+    /// - `Advanced`: A new cycle started.
+    /// - `NotAllPaid`: Not every member has paid into the current cycle, so nothing changed.
+    /// - `HistoryMismatch`: Not every payout slot has been paid out yet, so nothing changed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum CycleAdvanceOutcome {
+        Advanced,
+        NotAllPaid,
+        HistoryMismatch,
+    }
+
+    /// `PoolPhase` is the phase of the current contribution cycle.
+    ///
+    /// Variants:
+    /// - `Contribution`: The pool is waiting for members to join.
+    /// - `Payment`: The pool is full and members can request their payouts.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PoolPhase {
+        Contribution,
+        Payment,
+    }
+
+    /// `PoolOverview` is the state a dapp needs to render a pool, returned by `get_pool_snapshot`.
+    ///
+    /// It is not the `PoolSnapshot` used for migrations, which holds the full member state.
+    ///
+    /// Fields:
+    /// - `total_supply`: The total amount contributed in the current cycle.
+    /// - `phase`: Whether the pool is still collecting members or paying out.
+    /// - `cycle` and `round`: The current contribution cycle and payout round.
+    /// - `members`: The number of members in the current cycle.
+    /// - `next_requester`: The next eligible requester, if any.
+    /// - `pending_request`: The oldest pending payout request, if any.
+    /// - `config`: The pool settings.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PoolOverview {
+        pub total_supply: Balance,
+        pub phase: PoolPhase,
+        pub cycle: u128,
+        pub round: u128,
+        pub members: u128,
+        pub next_requester: Option<AccountId>,
+        pub pending_request: Option<RequestInfo>,
+        pub config: Config,
+    }
+
+    /// `RecurringIntent` is a member's authorization for a keeper to pull their installment each round.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RecurringIntent {
+        pub amount: Balance,
+        pub remaining_rounds: u128,
+    }
+
+    /// The error type returned by PSP22 token contracts.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
     }
 
+    /// The hard cap on the number of contributors of a pool, which bounds the growth of the storage vectors.
+    ///
+    /// The member vectors (`balance`, `requests`, `payout_history`, `next_cycle_enrollments`, `leaderboard`, `enrollment_order`
+    /// and `free_member_leaves`) take up to 280 bytes per member in the root storage cell, which must fit the 16 KiB buffer of the contract.
+    pub const MAX_POOL_SIZE: u128 = 48;
+
+    /// How long, in milliseconds, members have between the start of a wind-down and the refunds: 7 days.
+    pub const WIND_DOWN_DELAY: Timestamp = 7 * 24 * 60 * 60 * 1000;
+
+    /// The highest membership tier a contributor can choose.
+    pub const MAX_WEIGHT: u8 = 3;
+
+    /// The largest number of team members that can share a payout slot.
+    pub const MAX_TEAM_SIZE: usize = 10;
+
+    /// The automation permission that lets an operator approve payout requests.
+    pub const AUTOMATE_APPROVE: u8 = 1 << 0;
+
+    /// Every automation permission an operator can be granted.
+    pub const AUTOMATION_PERMISSIONS: u8 = AUTOMATE_APPROVE;
+
+    /// How long, in milliseconds, members can vote on an impeachment proposal.
+    pub const IMPEACHMENT_WINDOW: Timestamp = 7 * 24 * 60 * 60 * 1_000;
+
+    /// The share of the total membership weight, in basis points, that must vote to replace the owner: two thirds.
+    pub const IMPEACHMENT_THRESHOLD_BPS: u128 = 6_666;
+
+    /// The longest metadata URI, in bytes, a pool can store.
+    pub const MAX_METADATA_URI_LEN: usize = 256;
+
+    /// The longest extension, in milliseconds, members can vote onto the contribution deadline of a round.
+    pub const MAX_DEADLINE_EXTENSION: Timestamp = 3 * 24 * 60 * 60 * 1_000;
+
+    /// The depth of the Merkle tree committing to the member set. Its 1,024 leaves cover `MAX_POOL_SIZE`, since leaves of removed members are reused.
+    pub const MEMBER_TREE_DEPTH: u8 = 10;
+
+    /// How long, in milliseconds, past the contribution deadline and the grace period the pool must make no progress before members can rescue their funds.
+    pub const RESCUE_TIMEOUT: Timestamp = 30 * 24 * 60 * 60 * 1_000;
+
+    /// The longest display name, in bytes, a member can register.
+    pub const MAX_ALIAS_LEN: usize = 32;
+
+    /// The version of the storage layout, reported by `code_info`. Increment it with every change to the `Raiser` storage struct.
+    ///
+    /// Versions:
+    /// - `1`: The layout `code_info` was introduced with.
+    /// - `2`: Adds `round_pot`, the pot of the current round.
+    /// - `3`: Extends `PoolSnapshot` with the members, requests, failed payouts, enrollments and funds of the pool,
+    ///   adds `role_holders` and `automation_operators`, and adds the registry fields `registry`, `member_pools`,
+    ///   `reported_stats` and `global_stats`.
+    /// - `4`: Adds the `electorate` of impeachment and deadline extension proposals, and the `cancelled` flag of impeachments.
+    /// - `5`: Replaces the `contributors` vector with the `queue` mapping, `queue_head`, `queue_tail` and `queued_slots`.
+    /// - `6`: Removes `existential_deposit`, which is read from the chain instead.
+    /// - `7`: Adds `commit_period` to `Config`.
+    /// - `8`: Adds `failed_payout_ids`, the index of the open failed payouts.
+    pub const STORAGE_VERSION: u32 = 8;
+
+    /// The version of the crate the contract was built from, reported by `code_info`.
+    pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+    /// The longest a session key can stay valid, in milliseconds after its registration.
+    pub const MAX_SESSION_KEY_DURATION: Timestamp = 30 * 24 * 60 * 60 * 1_000;
+
+    /// The number of members the leaderboard keeps.
+    pub const LEADERBOARD_SIZE: usize = 100;
+
+    /// The leaderboard score of a member: the number of installments they paid by the contribution deadline.
+    pub type Score = u32;
+
+    /// The installments a member owes to the members who covered them with `cover_for`, as `(creditor, owed)` pairs, oldest first.
+    pub type CoverDebts = Vec<(AccountId, Balance)>;
+
     /// The ERC-20 result type.
     pub type Result<T> = core::result::Result<T, Error>;
     /// Constructs a new instance of the contract.
@@ -121,556 +1564,11479 @@ mod raiser {
     /// - `owner`: The account ID of the caller who deploys the contract.
     /// - `address_to_amount_funded`: An empty mapping of account IDs to the amount they have funded.
     /// - `contributed`: An empty mapping of contributors.
-    /// - `total_supply`: The total supply of tokens, initially set to 0.
-    /// - `contributors`: An empty vector of contributors.
+    /// - `total_supply`: The total amount contributed in the current cycle, initially set to 0.
+    /// - `queue`, `queue_head`, `queue_tail` and `queued_slots`: An empty payout queue.
     /// - `contributors_count`: The count of contributors, initially set to 0.
     /// - `requests`: An empty vector of requests.
     /// - `completed_payouts`: The count of completed payouts, initially set to 0.
     /// - `payout_history`: An empty vector of payout history.
-    /// - `max_contributors`: The maximum number of contributors, initially set to 0.
     /// - `contribution_cycle`: The contribution cycle, initially set to 1.
-    /// - `min_amount`: The minimum contribution amount, initially set to 50.
+    /// - `config`: The default pool settings, see `Config::default`.
     /// - `balance`: An empty vector of balances.
+    /// - `weights`: An empty mapping of membership tiers.
+    /// - `total_weight`: The sum of all membership tiers, initially set to 0.
+    /// - `current_round`: The current payout round, initially set to 1.
+    /// - `round_paid`: An empty mapping of round installments.
+    /// - `sponsored_amount` and `unallocated_sponsorship`: The matching funds, initially set to 0.
+    /// - `round_sponsorship`: An empty mapping of round sponsorships.
+    /// - `treasurer`: No treasurer, initially set to `None`.
+    /// - `next_request_id`: The first request ID, initially set to 1.
+    /// - `request_details`: An empty mapping of request details.
+    /// - `payment_token`: No PSP22 payment token, initially set to `None`.
+    /// - `recurring`: An empty mapping of recurring authorizations.
+    /// - `turn_started_at`: The deployment timestamp.
+    /// - `missed_turns`: An empty mapping of missed turns.
+    /// - `receipt_count`: An empty mapping of receipt counts.
+    /// - `receipts`: An empty mapping of receipts.
+    /// - `roles`: The deployer holds every role.
+    /// - `last_paid_round`: An empty mapping of payout rounds.
+    /// - `payouts_received`: An empty mapping of payout counts.
+    /// - `deferred_round`: An empty mapping of deferred rounds.
+    /// - `held_balance`: The accounted native balance, initially set to 0.
+    /// - `donations`: The allocated donations, initially set to 0.
+    /// - `total_payouts`: The lifetime payout count, initially set to 0.
+    /// - `total_paid_out`: The lifetime payout amount, initially set to 0.
+    /// - `round_opened_at`: The deployment timestamp.
+    /// - `due_notified_round`: The last reminded round, initially set to 0.
+    /// - `overdue_notified_round`: The last round with overdue notices, initially set to 0.
+    /// - `escrow`: An empty mapping of escrowed payouts.
+    /// - `escrowed_total`: The escrowed amount, initially set to 0.
+    /// - `membership_gate`: No membership gate.
+    /// - `prepaid`: An empty mapping of prepayments.
+    /// - `cycle_first_request`: The first request ID of cycle 1.
+    /// - `pruned_before_cycle`: The first unpruned cycle, initially set to 1.
+    /// - `first_receipt`: An empty mapping of first receipts.
+    /// - `commitments`: An empty mapping of commitments.
+    /// - `referrers`: An empty mapping of referrers.
+    /// - `referrals`: An empty mapping of referrals.
+    /// - `referral_rewards`: An empty mapping of referral rewards.
+    /// - `fee_reserve`: The fee reserve, initially set to 0.
+    /// - `received`: An empty mapping of received amounts.
+    /// - `wind_down_at`: No wind-down.
+    /// - `wound_down`: Initially set to `false`.
+    /// - `paid_rounds`: An empty mapping of paid rounds.
+    /// - `principal`: The principal, initially set to 0.
+    /// - `prepaid_total`: The total prepayment, initially set to 0.
+    /// - `sponsorship_balance`: The sponsorship balance, initially set to 0.
+    /// - `rewards_owed`: The owed rewards, initially set to 0.
+    /// - `owner_policy`: `OwnerPolicy::Unrestricted`.
+    /// - `cosigners`: An empty mapping of co-signers.
+    /// - `payout_preferences`: An empty mapping of payout preferences.
+    /// - `pending_payouts`: An empty mapping of pending payouts.
+    /// - `insurance`: The insurance reserve, initially set to 0.
+    /// - `covered_defaults`: An empty mapping of covered defaults.
+    /// - `converter`: The converter, initially set to `None`.
+    /// - `accepted_assets`: An empty mapping of accepted assets.
+    /// - `status`: The status, initially set to `PoolStatus::Active`.
+    /// - `price_oracle`: The price oracle, initially set to `None`.
+    /// - `join_constraints`: The join constraints, initially all disabled.
+    /// - `first_seen`: An empty mapping of check-ins.
+    /// - `dust`: The dust balance, initially set to 0.
+    /// - `teams`: An empty mapping of teams.
+    /// - `team_of`: An empty mapping of team memberships.
+    /// - `next_cycle_enrollments`: An empty list of enrollments.
+    /// - `queue_swaps`: An empty mapping of swaps.
+    /// - `next_swap_id`: The first swap ID, initially set to 1.
+    /// - `successors`: An empty list of successors.
+    /// - `withdrawals`: An empty mapping for pending withdrawals.
+    /// - `cycle_started_at`: The deployment timestamp.
+    /// - `cycle_defaults`: No covered defaults.
+    /// - `attestation_registry`: No attestation registry.
+    /// - `pending_config`: No queued settings change.
+    /// - `metadata_uri`: An empty metadata URI.
+    /// - `metadata_hash`: No metadata hash.
+    /// - `automation`: An empty mapping of automation operators.
+    /// - `cover_debts`: An empty mapping of cover debts.
+    /// - `impeachment`: No impeachment proposal.
+    /// - `impeachment_votes`: An empty mapping of impeachment votes.
+    /// - `lockups`: An empty mapping of payout lock-ups.
+    /// - `aliases`: An empty mapping of display names.
+    /// - `surplus_claims`: An empty mapping of surplus claims.
+    /// - `unclaimed_surplus`: The unclaimed surplus, initially set to 0.
+    /// - `tvl_cap`: No TVL cap.
+    /// - `failed_payouts`: An empty failed-payout queue.
+    /// - `failed_payout_ids`: No open failed payouts.
+    /// - `last_active_at`: An empty mapping of member activity.
+    /// - `creation_fee`: No pool creation fee.
+    /// - `max_pools_per_account`: One pool per account.
+    /// - `pools_created`: An empty mapping of created pools per account.
+    /// - `created_pools`: An empty list of created pools.
+    /// - `registry`: No registry.
+    /// - `member_pools`: An empty mapping of member pools.
+    /// - `reported_stats`: An empty mapping of pool reports.
+    /// - `global_stats`: Empty global statistics.
+    /// - `arbitrator`: No arbitrator.
+    /// - `arbitration_fee`: No arbitration fee.
+    /// - `disputes`: An empty mapping of disputes.
+    /// - `next_dispute_id`: The first dispute ID, initially set to 1.
+    /// - `disputed_escrow`: An empty mapping of frozen escrows.
+    /// - `on_time_scores`: An empty mapping of on-time scores.
+    /// - `leaderboard`: An empty leaderboard.
+    /// - `extension`: No deadline extension proposal.
+    /// - `extension_votes`: An empty mapping of deadline extension votes.
+    /// - `xcm_gateway`: No XCM gateway.
+    /// - `remote_beneficiaries`: An empty address book of remote beneficiaries.
+    /// - `member_tree`: An empty member tree.
+    /// - `member_leaf`: An empty mapping of member leaves.
+    /// - `next_member_leaf`: The first leaf, 0.
+    /// - `free_member_leaves`: An empty list of free leaves.
+    /// - `session_keys`: An empty mapping of session keys.
+    /// - `cycle_values`: An empty mapping of cycle value reports.
+    /// - `enrollment_order`: An empty enrollment order.
+    /// - `joined_at`: An empty mapping of join times.
+    /// - `time_credits`: An empty mapping of time-weighted credits.
+    /// - `total_time_credit`: No time-weighted credit.
+    /// - `round_pot`: The pot of the first round, initially set to 0.
     ///
     /// Returns the newly created contract instance.
     impl Raiser {
         #[ink(constructor)]
+        #[allow(clippy::new_without_default)]
         pub fn new() -> Self {
             let caller: ink::primitives::AccountId = Self::env().caller();
-            Self{
+            let mut contract = Self{
                 owner:caller, 
                 address_to_amount_funded:Mapping::default(), 
                 contributed:Mapping::default(), 
                 total_supply:0,
-                contributors:Vec::default(), 
+                queue:Mapping::default(),
+                queue_head:0,
+                queue_tail:0,
+                queued_slots:Mapping::default(),
                 contributors_count:0, 
                 requests:Vec::default(),
                 completed_payouts: 0,
                 payout_history:Vec::default(),
-                max_contributors:0,
                 contribution_cycle:1,
-                min_amount:50,
+                config:Config::default(),
                 balance:Vec::default(),
+                weights:Mapping::default(),
+                total_weight:0,
+                current_round:1,
+                round_paid:Mapping::default(),
+                sponsored_amount:0,
+                unallocated_sponsorship:0,
+                round_sponsorship:Mapping::default(),
+                treasurer:None,
+                next_request_id:1,
+                request_details:Mapping::default(),
+                payment_token:None,
+                recurring:Mapping::default(),
+                turn_started_at:Self::env().block_timestamp(),
+                missed_turns:Mapping::default(),
+                receipt_count:Mapping::default(),
+                receipts:Mapping::default(),
+                roles:Mapping::default(),
+                role_holders:Vec::new(),
+                last_paid_round:Mapping::default(),
+                payouts_received:Mapping::default(),
+                deferred_round:Mapping::default(),
+                held_balance:0,
+                donations:0,
+                total_payouts:0,
+                total_paid_out:0,
+                round_opened_at:Self::env().block_timestamp(),
+                due_notified_round:0,
+                overdue_notified_round:0,
+                escrow:Mapping::default(),
+                escrowed_total:0,
+                membership_gate:None,
+                prepaid:Mapping::default(),
+                cycle_first_request:Mapping::default(),
+                pruned_before_cycle:1,
+                first_receipt:Mapping::default(),
+                commitments:Mapping::default(),
+                referrers:Mapping::default(),
+                referrals:Mapping::default(),
+                referral_rewards:Mapping::default(),
+                fee_reserve:0,
+                received:Mapping::default(),
+                wind_down_at:None,
+                wound_down:false,
+                paid_rounds:Mapping::default(),
+                principal:0,
+                prepaid_total:0,
+                sponsorship_balance:0,
+                rewards_owed:0,
+                owner_policy:OwnerPolicy::Unrestricted,
+                cosigners:Mapping::default(),
+                payout_preferences:Mapping::default(),
+                pending_payouts:Mapping::default(),
+                insurance:0,
+                covered_defaults:Mapping::default(),
+                converter:None,
+                accepted_assets:Mapping::default(),
+                status:PoolStatus::Active,
+                price_oracle:None,
+                join_constraints:JoinConstraints::default(),
+                first_seen:Mapping::default(),
+                dust:0,
+                teams:Mapping::default(),
+                team_of:Mapping::default(),
+                next_cycle_enrollments:Vec::new(),
+                queue_swaps:Mapping::default(),
+                next_swap_id:1,
+                successors:Vec::new(),
+                withdrawals:Mapping::default(),
+                cycle_started_at:Self::env().block_timestamp(),
+                cycle_defaults:0,
+                attestation_registry:None,
+                pending_config:None,
+                metadata_uri:String::new(),
+                metadata_hash:None,
+                automation:Mapping::default(),
+                automation_operators:Vec::new(),
+                cover_debts:Mapping::default(),
+                impeachment:None,
+                impeachment_votes:Mapping::default(),
+                lockups:Mapping::default(),
+                aliases:Mapping::default(),
+                surplus_claims:Mapping::default(),
+                unclaimed_surplus:0,
+                tvl_cap:None,
+                failed_payouts:Mapping::default(),
+                failed_payout_ids:Vec::new(),
+                last_active_at:Mapping::default(),
+                creation_fee:0,
+                max_pools_per_account:1,
+                pools_created:Mapping::default(),
+                created_pools:Vec::default(),
+                registry:None,
+                member_pools:Mapping::default(),
+                reported_stats:Mapping::default(),
+                global_stats:GlobalStats::default(),
+                arbitrator:None,
+                arbitration_fee:0,
+                disputes:Mapping::default(),
+                next_dispute_id:1,
+                disputed_escrow:Mapping::default(),
+                on_time_scores:Mapping::default(),
+                leaderboard:Vec::default(),
+                extension:None,
+                extension_votes:Mapping::default(),
+                xcm_gateway:None,
+                remote_beneficiaries:Mapping::default(),
+                member_tree:Mapping::default(),
+                member_leaf:Mapping::default(),
+                next_member_leaf:0,
+                free_member_leaves:Vec::default(),
+                session_keys:Mapping::default(),
+                cycle_values:Mapping::default(),
+                enrollment_order:Vec::default(),
+                joined_at:Mapping::default(),
+                time_credits:Mapping::default(),
+                total_time_credit:TimeCredit::default(),
+                round_pot:0,
 
+            };
+            for role in [Role::Admin, Role::Approver, Role::Pauser, Role::Upgrader] {
+                contract.roles.insert((role, caller), &true);
+                contract.role_holders.push((role, caller));
             }
+            contract.cycle_first_request.insert(1, &contract.next_request_id);
+            contract
 
         }
 
-        /// Constructors can delegate to other constructors.
+        /// Creates a new pool with the given owner policy.
+        ///
+        /// The owner policy cannot be changed afterwards, so members know the rules before they join.
         #[ink(constructor)]
-        pub fn default() -> Self {
-            Self::new()
+        pub fn new_with_owner_policy(owner_policy: OwnerPolicy) -> Self {
+            let mut contract = Self::new();
+            contract.owner_policy = owner_policy;
+            contract
         }
 
-        /// Sets a new maximum number of contributors.
-        ///
-        /// This function updates the `max_contributors` field of the contract. 
-        /// It can only be called by the owner of the contract. If a non-owner 
-        /// attempts to call this function, it will return an `NotContractOwner` error.
-        ///
-        /// # Arguments
-        ///
-        /// * `new_max` - The new maximum number of contributors.
-        ///
-        /// # Returns
+        /// Creates a new pool with all of its settings in one transaction, instead of deploying with the defaults and calling `update_config`.
         ///
-        /// * `Ok(())` if the `max_contributors` was successfully updated.
-        /// * `Err(Error::InsufficientAllowance)` if the caller is not the owner of the contract.
-        
-        #[ink(message)]
-        pub fn set_max_contributors(&mut self, new_max: u128) -> Result<()> {
-            let caller = self.env().caller();
-            if caller != self.owner {
-                return Err(Error::NotContractOwner);
-            }
-            self.max_contributors = new_max;
-            Ok(())
+        /// Returns an `InvalidConfig` or `InvalidMaxContributors` error if `config` fails the validation of `update_config`.
+        #[ink(constructor)]
+        pub fn new_with_config(config: Config) -> Result<Self> {
+            let mut contract = Self::new();
+            contract.validate_config(&config)?;
+            contract.config = config;
+            Ok(contract)
         }
 
-        /// Returns the maximum number of contributors.
-        ///
-        /// This function returns the `max_contributors` field of the contract. 
-        /// It can be called by any user to know the maximum number of contributors allowed.
+        /// Creates a new pool in the `Draft` status, so the owner can configure it before members join.
+        #[ink(constructor)]
+        pub fn new_draft() -> Self {
+            let mut contract = Self::new();
+            contract.status = PoolStatus::Draft;
+            contract
+        }
+
+        /// Returns the build the pool is running, as `(code hash, storage version, crate version)`.
         ///
-        /// # Returns
+        /// The code hash identifies the uploaded Wasm blob, which auditors can compare with a verified build of the published source.
+        /// The storage version is `STORAGE_VERSION` and the crate version is `CRATE_VERSION`, as UTF-8 bytes. After an upgrade,
+        /// all three change together, so members can confirm which build now holds their funds.
+        #[ink(message)]
+        pub fn code_info(&self) -> (Hash, u32, Vec<u8>) {
+            let code_hash = self.env().own_code_hash().unwrap_or_default();
+            (code_hash, STORAGE_VERSION, CRATE_VERSION.as_bytes().to_vec())
+        }
+
+        /// Returns the members of the current cycle in the order they joined, with the time each joined.
         ///
-        /// * `u128` - The maximum number of contributors.
-        
+        /// Unlike the payout queue, the enrollment order is not changed by swaps, skips, deferrals or payouts,
+        /// and removed members stay listed, so the history of who joined when is preserved for the whole cycle.
         #[ink(message)]
-        pub fn get_max_contributors(&self) -> u128 {
-            self.max_contributors
+        pub fn get_enrollment_order(&self) -> Vec<(AccountId, Timestamp)> {
+            self.enrollment_order
+                .iter()
+                .map(|account| (*account, self.joined_at.get(account).unwrap_or(0)))
+                .collect()
+        }
+
+        /// Returns the owner policy of the pool.
+        #[ink(message)]
+        pub fn get_owner_policy(&self) -> OwnerPolicy {
+            self.owner_policy
         }
 
-        /// Allows a user to contribute to the contract.
+        /// Creates a successor pool with `config` and `members` pre-registered in that order, owned by `owner`.
         ///
-        /// The `contribute` function is called when a user wants to contribute to the contract. It performs the following operations:
-        /// - Checks if the caller has already contributed. If so, it returns an `AlreadyContributed` error.
-        /// - Checks if the transferred value is less than the minimum amount. If so, it returns a `LowAmount` error.
-        /// - Retrieves the amount the caller has already funded.
-        /// - Increments the contributors count, adds the caller to the contributors list, and marks the caller as having contributed.
-        /// - Updates the amount the caller has funded and their balance.
-        /// - Increases the total supply by the transferred value.
-        /// - Emits a `Transfer` event with the new total supply.
+        /// This is the constructor `clone_pool_with_members` instantiates. Each member joins with their membership tier
+        /// and pays their installments with `contribute_round`, as if they had called `join`.
+        /// If a contract deploys the pool, it becomes the registry the pool reports its members and statistics to.
         ///
-        /// Returns `Ok(())` if the contribution is successful, or an `Error` if not.
+        /// The settings are validated like in `new_with_config`. Returns an `InvalidMaxContributors` error if there are more than
+        /// `MAX_POOL_SIZE` members, or more than a maximum number of contributors the settings set, an `InvalidWeight` error if a membership tier is zero or above `MAX_WEIGHT`, an `AlreadyMember`
+        /// error if a member is listed twice, and an `ArithmeticOverflow` error if the member count or the total weight would overflow.
+        #[ink(constructor)]
+        pub fn new_successor(owner: AccountId, config: Config, members: Vec<(AccountId, u8)>) -> Result<Self> {
+            Self::validate_settings(&config)?;
+            let count = members.len() as u128;
+            if count > MAX_POOL_SIZE || (config.max_contributors > 0 && count > config.max_contributors) {
+                return Err(Error::InvalidMaxContributors);
+            }
+            let mut contract = Self::new();
+            let caller = contract.env().caller();
+            for role in [Role::Admin, Role::Approver, Role::Pauser, Role::Upgrader] {
+                contract.roles.remove((role, caller));
+                contract.roles.insert((role, owner), &true);
+            }
+            contract.role_holders = [Role::Admin, Role::Approver, Role::Pauser, Role::Upgrader]
+                .into_iter()
+                .map(|role| (role, owner))
+                .collect();
+            contract.owner = owner;
+            contract.config = config;
+            if contract.env().is_contract(&caller) {
+                contract.registry = Some(caller);
+            }
+            for (member, weight) in members {
+                if weight == 0 || weight > MAX_WEIGHT {
+                    return Err(Error::InvalidWeight);
+                }
+                if contract.contributed.contains(member) {
+                    return Err(contract.already_member(member));
+                }
+                contract.contributors_count = contract.contributors_count.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+                for _ in 0..weight {
+                    contract.queue_push(member);
+                }
+                contract.record_enrollment(member);
+                contract.contributed.insert(member, &true);
+                contract.weights.insert(member, &weight);
+                contract.total_weight = contract.total_weight.checked_add(weight as u128).ok_or(Error::ArithmeticOverflow)?;
+                contract.balance.push((member, 0));
+                contract.commit_member(member);
+            }
+            Ok(contract)
+        }
 
-        #[ink(message, payable)]
-        pub fn contribute(&mut self) -> Result<()> {
-            let caller: ink::primitives::AccountId = self.env().caller();
+        /// Constructors can delegate to other constructors.
+        #[ink(constructor)]
+        #[allow(clippy::should_implement_trait)]
+        pub fn default() -> Self {
+            Self::new()
+        }
 
-             if self.contributed.get(&caller).is_some() {
-                return Err(Error::AlreadyContributed);
-            }
+        /// Restores a pool from the concatenated chunks returned by `export_state`.
+        ///
+        /// The caller becomes the owner of the new pool. Funds are not moved by the migration,
+        /// so the new deployment must be funded with the exported `held_balance` separately. Until then, `verify_invariants`
+        /// reports the pool as underfunded, and none of the funds sent for the migration count as untracked.
+        ///
+        /// The settings are validated, and the member count, the total weight, the total supply and every sub-balance
+        /// are rebuilt from the members, requests and enrollments. Returns an `InvalidSnapshot` error if `data` is not a valid `PoolSnapshot`,
+        /// or if a rebuilt value does not match the exported one.
+        #[ink(constructor)]
+        pub fn new_from_snapshot(data: Vec<u8>) -> Result<Self> {
+            let snapshot = <PoolSnapshot as scale::Decode>::decode(&mut &data[..])
+                .map_err(|_| Error::InvalidSnapshot)?;
+            Self::validate_settings(&snapshot.config).map_err(|_| Error::InvalidSnapshot)?;
 
-            let value: u128 = self.env().transferred_value();
-            
-            if value < self.min_amount {
-                return Err(Error::LowAmount);
+            let accounts: Vec<AccountId> = snapshot.members.iter().map(|member| member.account).collect();
+            let is_member = |account: &AccountId| accounts.contains(account);
+            let distinct = |list: &[AccountId]| list.iter().enumerate().all(|(index, account)| !list[..index].contains(account));
+
+            // Every member appears once in the member list and the ledger, and in the enrollment order, which also keeps removed members
+            if !distinct(&accounts)
+                || snapshot.balance.len() != accounts.len()
+                || !snapshot.balance.iter().all(|(account, _)| is_member(account))
+                || !accounts.iter().all(|account| snapshot.enrollment_order.iter().any(|(enrolled, _)| enrolled == account))
+            {
+                return Err(Error::InvalidSnapshot);
+            }
+            if snapshot.members.iter().any(|member| member.weight == 0 || member.weight > MAX_WEIGHT)
+                || snapshot.contributors.iter().any(|account| !is_member(account))
+                || snapshot.members.iter().any(|member| {
+                    snapshot.contributors.iter().filter(|account| *account == &member.account).count() > member.weight as usize
+                })
+            {
+                return Err(Error::InvalidSnapshot);
+            }
+            if snapshot.requests.iter().enumerate().any(|(index, (request_id, info))| {
+                *request_id >= snapshot.next_request_id
+                    || info.status != RequestStatus::Pending
+                    || !is_member(&info.requester)
+                    || snapshot.requests[..index].iter().any(|(other, _)| other == request_id)
+            }) || snapshot.failed_payouts.iter().any(|(request_id, _)| *request_id >= snapshot.next_request_id)
+            {
+                return Err(Error::InvalidSnapshot);
             }
 
-            let funded_amount: u128 = self.balance_of(caller);
+            let funds = &snapshot.funds;
+            let total_weight = snapshot.members.iter().map(|member| member.weight as u128).sum::<u128>();
+            let total_supply = Self::checked_sum(snapshot.balance.iter().map(|(_, balance)| *balance)).ok_or(Error::InvalidSnapshot)?;
+            let prepaid_total = Self::checked_sum(
+                snapshot
+                    .members
+                    .iter()
+                    .map(|member| member.prepaid)
+                    .chain(snapshot.next_cycle_enrollments.iter().map(|(_, amount)| *amount)),
+            )
+            .ok_or(Error::InvalidSnapshot)?;
+            let escrowed_total = Self::checked_sum(
+                snapshot
+                    .members
+                    .iter()
+                    .flat_map(|member| {
+                        let escrow = member.escrow.map(|(amount, _)| amount).unwrap_or(0);
+                        let pending = member.pending_payout.map(|pending| pending.remaining).unwrap_or(0);
+                        [escrow, member.lockup, member.withdrawal, pending]
+                    })
+                    .chain(snapshot.failed_payouts.iter().map(|(_, failed)| failed.amount)),
+            )
+            .ok_or(Error::InvalidSnapshot)?;
+            let rewards_owed = Self::checked_sum(snapshot.members.iter().map(|member| member.referral_rewards)).ok_or(Error::InvalidSnapshot)?;
+            let unclaimed_surplus = Self::checked_sum(snapshot.members.iter().map(|member| member.surplus_claim)).ok_or(Error::InvalidSnapshot)?;
+            let sponsorship_balance = Self::checked_sum(
+                core::iter::once(funds.unallocated_sponsorship).chain(funds.round_sponsorships.iter().map(|(_, amount)| *amount)),
+            )
+            .ok_or(Error::InvalidSnapshot)?;
+            let held_balance = Self::checked_sum([
+                funds.principal,
+                prepaid_total,
+                sponsorship_balance,
+                funds.fee_reserve,
+                rewards_owed,
+                escrowed_total,
+                funds.insurance,
+                funds.dust,
+                unclaimed_surplus,
+            ])
+            .ok_or(Error::InvalidSnapshot)?;
+            if snapshot.contributors_count != accounts.len() as u128
+                || snapshot.total_weight != total_weight
+                || snapshot.total_supply != total_supply
+                || snapshot.round_pot > funds.principal
+                || funds.prepaid_total != prepaid_total
+                || funds.escrowed_total != escrowed_total
+                || funds.rewards_owed != rewards_owed
+                || funds.unclaimed_surplus != unclaimed_surplus
+                || funds.sponsorship_balance != sponsorship_balance
+                || funds.held_balance != held_balance
+            {
+                return Err(Error::InvalidSnapshot);
+            }
 
-            self.contributors_count += 1;
-            self.contributors.push(caller);
-            self.contributed.insert(caller, &true);
-        
-            self.address_to_amount_funded.insert(caller, &(funded_amount + value, true));
-            self.balance.push((caller, funded_amount + value));
+            let mut contract = Self::new();
+            let now = contract.env().block_timestamp();
+            contract.config = snapshot.config;
+            contract.total_supply = total_supply;
+            contract.contributors_count = accounts.len() as u128;
+            contract.total_weight = total_weight;
+            contract.completed_payouts = snapshot.completed_payouts;
+            contract.contribution_cycle = snapshot.contribution_cycle;
+            contract.current_round = snapshot.current_round;
+            contract.round_pot = snapshot.round_pot;
+            contract.next_request_id = snapshot.next_request_id;
+            for account in snapshot.contributors {
+                contract.queue_push(account);
+            }
+            contract.payout_history = snapshot.payout_history;
+            for (account, joined_at) in snapshot.enrollment_order {
+                contract.enrollment_order.push(account);
+                contract.joined_at.insert(account, &joined_at);
+            }
+            contract.next_cycle_enrollments = snapshot.next_cycle_enrollments;
 
-            self.total_supply += value;
+            let mut total_time_credit = TimeCredit { locked: 0, credit: 0, updated_at: now };
+            for member in snapshot.members {
+                let account = member.account;
+                contract.commit_member(account);
+                contract.contributed.insert(account, &true);
+                contract.weights.insert(account, &member.weight);
+                contract.round_paid.insert(account, &member.round_paid);
+                for round in member.paid_rounds {
+                    contract.paid_rounds.insert((account, round), &true);
+                }
+                contract.received.insert(account, &member.received);
+                if let Some(round) = member.last_paid_round {
+                    contract.last_paid_round.insert(account, &round);
+                }
+                contract.payouts_received.insert(account, &member.payouts_received);
+                if member.prepaid > 0 {
+                    contract.prepaid.insert(account, &member.prepaid);
+                }
+                contract.time_credits.insert(account, &member.time_credit);
+                total_time_credit.locked = total_time_credit.locked.saturating_add(member.time_credit.locked);
+                total_time_credit.credit = total_time_credit.credit.saturating_add(member.time_credit.accrued(now));
+                if let Some(referrer) = member.referrer {
+                    contract.referrers.insert(account, &referrer);
+                }
+                if member.covered_defaults > 0 {
+                    contract.covered_defaults.insert(account, &member.covered_defaults);
+                }
+                if !member.cover_debts.is_empty() {
+                    contract.cover_debts.insert(account, &member.cover_debts);
+                }
+                if let Some(escrow) = member.escrow {
+                    contract.escrow.insert(account, &escrow);
+                }
+                if member.lockup > 0 {
+                    contract.lockups.insert(account, &member.lockup);
+                }
+                if member.withdrawal > 0 {
+                    contract.withdrawals.insert(account, &member.withdrawal);
+                }
+                if let Some(pending) = member.pending_payout {
+                    contract.pending_payouts.insert(account, &pending);
+                }
+                if member.surplus_claim > 0 {
+                    contract.surplus_claims.insert(account, &member.surplus_claim);
+                }
+                if member.referral_rewards > 0 {
+                    contract.referral_rewards.insert(account, &member.referral_rewards);
+                }
+            }
+            contract.total_time_credit = total_time_credit;
+            for (account_id, balance) in &snapshot.balance {
+                contract.address_to_amount_funded.insert(account_id, &(*balance, true));
+            }
+            contract.balance = snapshot.balance;
 
-            Self::env().emit_event(
-                Transfer {
-                from: None,
-                to: Some(caller),
-                value: self.total_supply,
-            });
-            Ok(())
+            for (request_id, info) in snapshot.requests {
+                contract.requests.push((info.requester, info.amount, request_id));
+                contract.request_details.insert(request_id, &info);
+            }
+            for (request_id, failed) in snapshot.failed_payouts {
+                contract.failed_payouts.insert(request_id, &failed);
+                contract.failed_payout_ids.push(request_id);
+            }
+            for (round, amount) in &funds.round_sponsorships {
+                contract.round_sponsorship.insert(round, amount);
+            }
+            contract.unallocated_sponsorship = funds.unallocated_sponsorship;
+            contract.sponsorship_balance = sponsorship_balance;
+            contract.principal = funds.principal;
+            contract.prepaid_total = prepaid_total;
+            contract.fee_reserve = funds.fee_reserve;
+            contract.rewards_owed = rewards_owed;
+            contract.escrowed_total = escrowed_total;
+            contract.insurance = funds.insurance;
+            contract.dust = funds.dust;
+            contract.unclaimed_surplus = unclaimed_surplus;
+            contract.held_balance = held_balance;
+            Ok(contract)
         }
 
-        /// Retrieves the list of contributors and their balances.
+        /// Deploys a successor pool with the same config and the current members, pre-registered for the next rotation.
         ///
-        /// The `get_contributors` function iterates over the list of contributors, retrieves the balance for each contributor using the `balance_of` function, and adds a tuple of the account ID and balance to the `contributors` vector.
+        /// The `clone_pool_with_members` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Instantiates this contract's code with `new_successor`, passing the owner, the config and every member with their tier.
+        ///   If the deployment fails, it returns a `CloneFailed` error.
+        /// - Records the successor and emits a `PoolCloned` event.
         ///
-        /// Returns a vector of tuples, where each tuple contains an account ID and the corresponding balance.
+        /// Returns the account of the successor pool, or an `Error`.
 
         #[ink(message)]
-        pub fn get_contributors(&self) -> Vec<(AccountId, Balance)> {
-            let mut contributors = Vec::new();
-            for account_id in &self.contributors {
-                let balance = self.balance_of(account_id.clone());
-                contributors.push((account_id.clone(), balance));
+        pub fn clone_pool_with_members(&mut self) -> Result<AccountId> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            let members: Vec<(AccountId, u8)> = self
+                .balance
+                .iter()
+                .map(|(member, _)| (*member, self.weight_of(*member)))
+                .collect();
+            let member_count = members.len() as u32;
+            let accounts: Vec<AccountId> = members.iter().map(|(member, _)| *member).collect();
+            let code_hash = self.env().own_code_hash().map_err(|_| Error::CloneFailed)?;
+            let salt = scale::Encode::encode(&(self.env().account_id(), self.successors.len() as u32));
+
+            let successor = match RaiserRef::new_successor(self.owner, self.config.clone(), members)
+                .code_hash(code_hash)
+                .endowment(0)
+                .salt_bytes(salt)
+                .try_instantiate()
+            {
+                Ok(Ok(Ok(successor))) => ink::ToAccountId::to_account_id(&successor),
+                _ => return Err(Error::CloneFailed),
+            };
+
+            self.successors.push(successor);
+            // The successor cannot call back into this pool while it is being deployed, so its members are recorded here
+            self.global_stats.pools = self.global_stats.pools.saturating_add(1);
+            for member in accounts {
+                self.record_membership(successor, member, true);
             }
-            contributors
+            self.record_pool_stats(successor, PoolStats {
+                members: member_count as u128,
+                active: member_count > 0,
+                ..Default::default()
+            })?;
+            self.env().emit_event(PoolCloned {
+                successor,
+                members: member_count,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(successor)
         }
 
-        /// Allows a contributor to request tokens.
+        /// Returns the successor pools deployed with `clone_pool_with_members`, oldest first.
+        #[ink(message)]
+        pub fn get_successors(&self) -> Vec<AccountId> {
+            self.successors.clone()
+        }
+
+        /// Deploys a new, empty pool with `config`, owned by the caller.
         ///
-        /// The `request_token` function is called when a contributor wants to request tokens. It performs the following operations:
-        /// - Checks if the number of contributors has reached the maximum limit. If not, it returns a `NotPaymentPhase` error.
-        /// - Checks if the caller is the first contributor in the list. If not, it returns a `NotNextContributor` error.
-        /// - If the caller is the first contributor, it adds a request for the total supply of tokens to the `requests` vector.
+        /// The `create_pool` function performs the following operations:
+        /// - Checks if the caller has created fewer than `max_pools_per_account` pools. If not, it returns a `PoolLimitReached` error.
+        /// - Checks if the transferred value covers the creation fee. If not, it returns a `LowAmount` error.
+        /// - Validates `config` like `update_config`. If it is invalid, it returns an `InvalidConfig` or `InvalidMaxContributors` error.
+        /// - Instantiates this contract's code with `new_successor`, with the caller as owner and no members.
+        ///   If the deployment fails, it returns a `CloneFailed` error.
+        /// - Adds the transferred value to the fee reserve, records the pool and emits a `PoolCreated` event.
         ///
-        /// Returns `Ok(())` if the token request is successful, or an `Error` if not.
+        /// Returns the account of the new pool, or an `Error`.
 
-        #[ink(message)]
-        pub fn request_token(&mut self) -> Result<()> {
+        #[ink(message, payable)]
+        pub fn create_pool(&mut self, config: Config) -> Result<AccountId> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let fee = self.env().transferred_value();
+
+            let created = self.pools_created.get(caller).unwrap_or(0);
+            if created >= self.max_pools_per_account {
+                return Err(Error::PoolLimitReached);
+            }
+            if fee < self.creation_fee {
+                return Err(Error::LowAmount);
+            }
+            Self::validate_settings(&config)?;
 
-            if self.contributors_count == self.max_contributors as u128 {
-                let caller = self.env().caller();
+            let code_hash = self.env().own_code_hash().map_err(|_| Error::CloneFailed)?;
+            let salt = scale::Encode::encode(&(self.env().account_id(), caller, created));
+            let pool = match RaiserRef::new_successor(caller, config, Vec::new())
+                .code_hash(code_hash)
+                .endowment(0)
+                .salt_bytes(salt)
+                .try_instantiate()
+            {
+                Ok(Ok(Ok(pool))) => ink::ToAccountId::to_account_id(&pool),
+                _ => return Err(Error::CloneFailed),
+            };
 
-                if Some(&caller) == self.contributors.first() {
-                    let amount = self.total_supply; 
-                     self.requests.push((caller, amount));
+            self.fee_reserve = self.fee_reserve.checked_add(fee).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.checked_add(fee).ok_or(Error::ArithmeticOverflow)?;
+            self.pools_created.insert(caller, &(created + 1));
+            self.created_pools.push(pool);
+            self.global_stats.pools = self.global_stats.pools.saturating_add(1);
+            self.env().emit_event(PoolCreated {
+                creator: caller,
+                pool,
+                fee,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(pool)
+        }
+
+        /// Simulates a full cycle of a hypothetical pool with `config` and `members` members, without touching any state.
+        ///
+        /// The `simulate_cycle` function performs the following operations:
+        /// - Checks if `config` passes the validation of `create_pool`. If not, it returns an `InvalidConfig` or `InvalidMaxContributors` error.
+        /// - Checks if `members` is between 1 and `MAX_POOL_SIZE`. If not, it returns an `InvalidMaxContributors` error.
+        /// - Assumes every member joins with a 1x membership tier and pays `min_amount` each round, on time.
+        /// - Pays each round's pot to the next `winners_per_round` members in join order. The rounding remainder goes to the first of them,
+        ///   and with payout lock-ups their remaining installments are held back.
+        ///
+        /// Organizers can preview the economics of a pool before creating it with `create_pool`. The price oracle of this pool is not consulted,
+        /// so the fixed `min_amount` of `config` applies. Returns the `SimulationResult`, or an `Error` if the inputs are invalid.
+        #[ink(message)]
+        pub fn simulate_cycle(&self, config: Config, members: u32) -> Result<SimulationResult> {
+            Self::validate_settings(&config)?;
+            if members == 0 || members as u128 > MAX_POOL_SIZE {
+                return Err(Error::InvalidMaxContributors);
+            }
+
+            let installment = config.min_amount;
+            let pot = installment.saturating_mul(members as u128);
+            let per_round = config.winners_per_round.min(members);
+            let round_count = members.div_ceil(per_round);
+            let mut rounds = Vec::with_capacity(round_count as usize);
+            let mut total_fees: Balance = 0;
+            for index in 0..round_count {
+                let first = index * per_round;
+                let winners = per_round.min(members - first);
+                let share = pot / winners as u128;
+                let remainder = pot - share * winners as u128;
+                let remaining_rounds = (round_count - index - 1) as u128;
+                let payouts = (first..first + winners)
+                    .map(|position| {
+                        let gross = if position == first { share + remainder } else { share };
+                        let held_back = if config.payout_lockup {
+                            installment.saturating_mul(remaining_rounds).min(gross)
+                        } else {
+                            0
+                        };
+                        SimulatedPayout { position, amount: gross - held_back, held_back }
+                    })
+                    .collect();
+                let fees = if index + 1 == round_count {
+                    config.referral_bonus.saturating_mul(members as u128)
                 } else {
-                    return Err(Error::NotNextContributor)
-                }
-               
-            }else{
-                return Err(Error::NotPaymentPhase)
+                    0
+                };
+                total_fees += fees;
+                rounds.push(SimulatedRound {
+                    round: index + 1,
+                    opens_after: index as u64 * config.period_length,
+                    pot,
+                    fees,
+                    payouts,
+                });
             }
+
+            Ok(SimulationResult {
+                installment,
+                rounds,
+                total_contributed: pot.saturating_mul(round_count as u128),
+                total_fees,
+                creation_fee: self.creation_fee,
+            })
+        }
+
+        /// Sets the fee and the per-account limit of `create_pool`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// A `max_pools_per_account` of 0 disables permissionless pool creation.
+
+        #[ink(message)]
+        pub fn set_pool_creation_limits(&mut self, creation_fee: Balance, max_pools_per_account: u32) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.creation_fee = creation_fee;
+            self.max_pools_per_account = max_pools_per_account;
             Ok(())
         }
 
-        /// Allows the contract owner to approve a token request.
+        /// Returns the fee and the per-account limit of `create_pool`.
+        #[ink(message)]
+        pub fn get_pool_creation_limits(&self) -> (Balance, u32) {
+            (self.creation_fee, self.max_pools_per_account)
+        }
+
+        /// Returns the pools created with `create_pool`, oldest first.
+        #[ink(message)]
+        pub fn get_created_pools(&self) -> Vec<AccountId> {
+            self.created_pools.clone()
+        }
+
+        /// Records the statistics a pool deployed by this contract reports about itself.
         ///
-        /// The `approve_request` function is called when the contract owner wants to approve a token request. It performs the following operations:
-        /// - Checks if the caller is the contract owner. If not, it returns a `NotContractOwner` error.
-        /// - Attempts to transfer the requested amount of tokens to the requester. If the transfer fails, it returns a `TransferError`.
-        /// - If the transfer is successful, it resets the `requests` vector, removes the first contributor, increments the `completed_payouts` count, and logs the number of completed payouts.
-        /// - Adds the payout to the `payout_history`, resets the `contributed` mapping, and starts the next contribution cycle.
-        /// - Emits a `Transfer` event with the amount of tokens transferred.
+        /// The `report_pool_stats` function performs the following operations:
+        /// - Checks if the caller is a pool created with `create_pool` or `clone_pool_with_members`. If not, it returns an `UnknownPool` error.
+        /// - Replaces the figures the pool reported last with `stats` in the global statistics.
         ///
-        /// Returns `Ok(())` if the approval and transfer are successful, or an `Error` if not.
+        /// Pools report when members join, pay, leave or are paid out, so the global statistics are as of the last report of each pool.
+        /// Returns `Ok(())` if the report was recorded, or an `Error` if not.
 
         #[ink(message)]
-        pub fn approve_request(&mut self, caller:AccountId) -> Result<()> {
-           //  let caller: ink::primitives::AccountId = self.env().caller();
-            if caller != self.owner {
-                return Err(Error::NotContractOwner);
-            }
+        pub fn report_pool_stats(&mut self, stats: PoolStats) -> Result<()> {
+            self.ensure_not_archived()?;
+            let pool = self.env().caller();
+            self.ensure_deployed_pool(pool)?;
+            self.record_pool_stats(pool, stats)
+        }
 
-            // Transfer token
-            let (requester, amount) = self.requests[0];
-            match Self::env().transfer(requester, amount) {
-                Ok(_value) => {
-                    self.requests = Vec::default();
-                    self.contributors.remove(0); 
-                    self.completed_payouts += 1;
-                    self.payout_history.push((requester, amount));
-                    self.contributed = Mapping::default();
-                   
-                    self.next_contribution_cycle();
-        
-                    self.env().emit_event(Transfer {
-                        from: Some(self.owner),
-                        to: Some(requester.clone()),
-                        value:amount,
-                    });        
-                },
-                Err(_e) => {
-                    return Err(Error::TransferError);
-                }
-            }
+        /// Records that `member` joined or left a pool deployed by this contract. The caller must be that pool,
+        /// otherwise it returns an `UnknownPool` error.
 
-           
+        #[ink(message)]
+        pub fn report_membership(&mut self, member: AccountId, joined: bool) -> Result<()> {
+            self.ensure_not_archived()?;
+            let pool = self.env().caller();
+            self.ensure_deployed_pool(pool)?;
+            self.record_membership(pool, member, joined);
             Ok(())
         }
-        
-        /// This function returns the AccountId of the next eligible requester.
-        /// It does this by checking the first contributor in the queue (the next eligible requester).
-        /// If there are no contributors in the queue, it returns `None`.
-        
+
+        /// Returns the pools deployed by this contract `account` is a member of, in the order they reported the membership.
         #[ink(message)]
-        pub fn get_next_requester(&self) -> Option<AccountId> {
-            // Use the `first` method to get a reference to the first contributor in the queue.
-            // This returns an `Option<&AccountId>`, so we use the `map` method to transform the data.
-            self.contributors.first().map(|account_id| {
-                // Return the AccountId.
-                // We dereference `account_id` to get the AccountId from the reference.
-                *account_id
-            })
+        pub fn pools_of_member(&self, account: AccountId) -> Vec<AccountId> {
+            self.member_pools.get(account).unwrap_or_default()
         }
-        
-        /// Retrieves the number of completed payouts.
+
+        /// Returns the sum of the statistics reported by the pools deployed by this contract.
+        #[ink(message)]
+        pub fn global_stats(&self) -> GlobalStats {
+            self.global_stats
+        }
+
+        /// Points explorers to the rich description of the pool.
         ///
-        /// The `get_completed_payouts` function is called to get the count of completed payouts from the contract.
+        /// The `set_metadata_uri` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if `uri` is longer than `MAX_METADATA_URI_LEN` bytes. If so, it returns a `MetadataUriTooLong` error.
+        /// - Stores `uri` and `hash`, the hash of the document it points to, and emits a `MetadataUpdated` event.
         ///
-        /// Returns the number of completed payouts as a `u128`.
+        /// Returns `Ok(())` if the metadata was updated, or an `Error` if not.
 
-       #[ink(message)]
-        pub fn get_completed_payouts(&self) -> u128 {
-            self.completed_payouts
+        #[ink(message)]
+        pub fn set_metadata_uri(&mut self, uri: String, hash: Hash) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            if uri.len() > MAX_METADATA_URI_LEN {
+                return Err(Error::MetadataUriTooLong);
+            }
+
+            self.metadata_uri = uri.clone();
+            self.metadata_hash = Some(hash);
+            self.env().emit_event(MetadataUpdated {
+                uri,
+                hash,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
         }
-        
-        /// Retrieves the payout history.
+
+        /// Registers a display name for the caller.
         ///
-        /// The `get_payout_history` function is called to get the history of payouts from the contract. Each payout is represented as a tuple containing the account ID of the recipient and the amount of the payout.
+        /// The `set_alias` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if `name` is longer than `MAX_ALIAS_LEN` bytes. If so, it returns an `AliasTooLong` error.
+        /// - Stores `name` as the display name of the caller, or removes it if `name` is empty, and emits an `AliasSet` event.
         ///
-        /// Returns a vector of tuples, where each tuple contains an account ID and the corresponding payout amount.
+        /// Returns `Ok(())` if the display name was updated, or an `Error` if not.
 
         #[ink(message)]
-        pub fn get_payout_history(&self) -> Vec<(AccountId, Balance)> {
-            self.payout_history.clone()
+        pub fn set_alias(&mut self, name: Vec<u8>) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            if name.len() > MAX_ALIAS_LEN {
+                return Err(Error::AliasTooLong);
+            }
+
+            if name.is_empty() {
+                self.aliases.remove(caller);
+            } else {
+                self.aliases.insert(caller, &name);
+            }
+            self.env().emit_event(AliasSet {
+                account: caller,
+                name,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.record_activity(caller);
+            Ok(())
         }
-    
-        
-        /// Starts the next contribution cycle.
+
+        /// Registers a session key that can pay the caller's installments until `expires_at`.
         ///
-        /// The `next_contribution_cycle` function is called to start a new contribution cycle. It performs the following operations:
-        /// - Checks if all contributors have been paid. If not, it does nothing.
-        /// - If all contributors have been paid and the length of the payout history is equal to the number of contributors, it resets the `address_to_amount_funded` mapping, the `payout_history` vector, and the `contributors_count`, increments the `contribution_cycle`, and resets the `completed_payouts` count.
+        /// The `register_session_key` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if `key` is not the caller, is not the session key of another member, and `expires_at` is in the future
+        ///   but at most `MAX_SESSION_KEY_DURATION` ahead. If not, it returns an `InvalidSessionKey` error.
+        /// - Stores the key, replacing its earlier expiry, and emits a `SessionKeyRegistered` event.
+        ///
+        /// A session key can only call `contribute` and `contribute_round`, which it does on behalf of the member.
+        /// The funds it sends are credited to the member, and it cannot request, withdraw or manage anything.
+        ///
+        /// Returns `Ok(())` if the key was registered, or an `Error` if not.
 
         #[ink(message)]
-        pub fn next_contribution_cycle(&mut self){
-            let all_paid =  self.all_paid();
-            if all_paid {
-                if self.payout_history.len() as u128 == self.contributors_count {
-                    self.address_to_amount_funded = Mapping::default();
-                    self.payout_history = Vec::default();
-                    self.contributors_count = 0;
-                    self.contribution_cycle+= 1;
-                    self.completed_payouts = 0;
-                 }
+        pub fn register_session_key(&mut self, key: AccountId, expires_at: Timestamp) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
             }
-           
+            let now = self.env().block_timestamp();
+            let taken = self.session_keys.get(key).is_some_and(|(member, _)| member != caller);
+            if key == caller || taken || expires_at <= now || expires_at > now.saturating_add(MAX_SESSION_KEY_DURATION) {
+                return Err(Error::InvalidSessionKey);
+            }
+
+            self.session_keys.insert(key, &(caller, expires_at));
+            self.env().emit_event(SessionKeyRegistered {
+                member: caller,
+                key,
+                expires_at,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.record_activity(caller);
+            Ok(())
         }
-        
-        /// Checks if all contributors have been paid.
-        ///
-        /// The `all_paid` function is called to check if all contributors have been paid. It iterates over the list of contributors and checks the `paid` status for each contributor in the `address_to_amount_funded` mapping.
+
+        /// Revokes a session key of the caller before it expires.
         ///
-        /// Returns `true` if all contributors have been paid, or `false` if at least one contributor has not been paid.
+        /// Returns an `InvalidSessionKey` error if `key` is not a session key of the caller.
 
         #[ink(message)]
-        pub fn all_paid(&self) -> bool {
-            for key in &self.contributors {
-                let (_, paid) = self.address_to_amount_funded.get(&key).unwrap_or((0, false));
-                if !paid {
-                    return false;
+        pub fn revoke_session_key(&mut self, key: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            match self.session_keys.get(key) {
+                Some((member, _)) if member == caller => {
+                    self.session_keys.remove(key);
+                    Ok(())
                 }
+                _ => Err(Error::InvalidSessionKey),
             }
-            true
         }
 
-        /// Returns the total token supply.
+        /// Returns the member `key` acts for and its expiry, if `key` is a registered session key.
         #[ink(message)]
-        pub fn get_total_supply(&self) -> Balance {
-          //  let balance: u128 =  self.env().balance();
-            let balance: u128 =  self.total_supply;
-            balance
+        pub fn get_session_key(&self, key: AccountId) -> Option<(AccountId, Timestamp)> {
+            self.session_keys.get(key)
         }
 
-        /// Retrieves the total number of contributors.
-        ///
-        /// The `total_contributors` function is called to get the total count of contributors from the contract.
-        ///
-        /// Returns the total number of contributors as a `u128`.
-         #[ink(message)]
-         pub fn total_contributors(&self) -> u128 {
-             self.contributors_count
-         }
+        /// Returns the display name `account` registered, if any.
+        #[ink(message)]
+        pub fn get_alias(&self, account: AccountId) -> Option<Vec<u8>> {
+            self.aliases.get(account)
+        }
 
-        /// Retrieves the balance of a specific account.
+        /// Returns the metadata URI of the pool and the hash of the document it points to, if set.
+        #[ink(message)]
+        pub fn get_metadata(&self) -> (String, Option<Hash>) {
+            (self.metadata_uri.clone(), self.metadata_hash)
+        }
+
+        /// Returns one SCALE-encoded chunk of the pool state.
         ///
-        /// The `balance_of` function is called to get the balance of a specific account from the contract. It iterates over the `balance` vector and returns the balance for the given account ID.
+        /// The `export_state` function lets the owner migrate the pool to a fresh deployment when upgrades are not available.
+        /// See `PoolSnapshot` for the content of each chunk. Chunks from `SNAPSHOT_CHUNKS` onwards are empty.
         ///
-        /// Returns the balance of the given account as a `Balance`. If the account does not exist in the `balance` vector, it returns 0.
+        /// Returns a `NotContractOwner` error if the caller is not the owner.
         #[ink(message)]
-        pub fn balance_of(&self, owner: AccountId) -> Balance {
-            for (account_id, balance) in &self.balance {
-                if account_id == &owner {
-                    return *balance;
-                }
+        pub fn export_state(&self, chunk: u32) -> Result<Vec<u8>> {
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            if chunk >= SNAPSHOT_CHUNKS {
+                return Ok(Vec::new());
             }
-            0
-        }
-    }
 
+            let data = match chunk {
+                0 => scale::Encode::encode(&(
+                    &self.config,
+                    self.total_supply,
+                    self.contributors_count,
+                    self.total_weight,
+                    self.completed_payouts,
+                    self.contribution_cycle,
+                    self.current_round,
+                    self.round_pot,
+                    self.next_request_id,
+                )),
+                1 => scale::Encode::encode(&self.queue_accounts()),
+                2 => scale::Encode::encode(&self.balance),
+                3 => {
+                    let members: Vec<MemberSnapshot> = self
+                        .balance
+                        .iter()
+                        .map(|(account_id, _)| self.member_snapshot(*account_id))
+                        .collect();
+                    scale::Encode::encode(&members)
+                }
+                4 => scale::Encode::encode(&self.payout_history),
+                5 => scale::Encode::encode(&self.get_enrollment_order()),
+                6 => {
+                    let requests: Vec<(u128, RequestInfo)> = self
+                        .requests
+                        .iter()
+                        .filter_map(|(_, _, request_id)| self.request_details.get(request_id).map(|info| (*request_id, info)))
+                        .collect();
+                    let failed_payouts: Vec<(u128, FailedPayout)> = self
+                        .failed_payout_ids
+                        .iter()
+                        .filter_map(|request_id| self.failed_payouts.get(request_id).map(|failed| (*request_id, failed)))
+                        .collect();
+                    scale::Encode::encode(&(requests, failed_payouts))
+                }
+                7 => scale::Encode::encode(&self.next_cycle_enrollments),
+                8 => {
+                    // A cycle has at most one round per payout slot, so later rounds belong to later cycles
+                    let last_round = self.current_round.saturating_add(self.total_weight);
+                    let round_sponsorships: Vec<(u128, Balance)> = (self.current_round..=last_round)
+                        .filter_map(|round| self.round_sponsorship.get(round).map(|amount| (round, amount)))
+                        .collect();
+                    scale::Encode::encode(&FundsSnapshot {
+                        held_balance: self.held_balance,
+                        principal: self.principal,
+                        prepaid_total: self.prepaid_total,
+                        sponsorship_balance: self.sponsorship_balance,
+                        unallocated_sponsorship: self.unallocated_sponsorship,
+                        round_sponsorships,
+                        fee_reserve: self.fee_reserve,
+                        rewards_owed: self.rewards_owed,
+                        escrowed_total: self.escrowed_total,
+                        insurance: self.insurance,
+                        dust: self.dust,
+                        unclaimed_surplus: self.unclaimed_surplus,
+                    })
+                }
+                _ => Vec::new(),
+            };
+            Ok(data)
+        }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        /// Test case for the initialization of the `Raiser` contract.
+        /// Returns one SCALE-encoded section of the storage, for support tooling diagnosing a stuck pool.
         ///
-        /// This test creates a new instance of the `Raiser` contract and checks if it is initialized with the correct default values.
-        /// It asserts that the total supply of tokens, the total number of contributors, the number of completed payouts, 
-        /// the maximum number of contributors, and the length of the payout history are all zero.
-        #[ink::test]
-        fn it_works() {
-            let  contract = Raiser::default();
-            assert_eq!(contract.get_total_supply(), 0);
-            assert_eq!(contract.total_contributors(), 0);
-            assert_eq!(contract.get_completed_payouts(), 0);
-            assert_eq!(contract.get_max_contributors(), 0);
-            assert_eq!(contract.get_payout_history().len(), 0);
+        /// See `StorageSection` for the encoded type of each section. Unlike `export_state`, the sections are meant for reading,
+        /// not for restoring a pool. Returns a `NotContractOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn debug_dump(&self, section: StorageSection) -> Result<Vec<u8>> {
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            let data = match section {
+                StorageSection::Members => {
+                    let members: Vec<(AccountId, Balance, u8, u128, Balance)> = self
+                        .balance
+                        .iter()
+                        .map(|(account_id, balance)| {
+                            (
+                                *account_id,
+                                *balance,
+                                self.weight_of(*account_id),
+                                self.round_paid.get(account_id).unwrap_or(0),
+                                self.get_prepaid(*account_id),
+                            )
+                        })
+                        .collect();
+                    scale::Encode::encode(&members)
+                }
+                StorageSection::Queue => scale::Encode::encode(&self.queue_accounts()),
+                StorageSection::Requests => scale::Encode::encode(&self.requests),
+                StorageSection::Config => scale::Encode::encode(&(&self.config, &self.pending_config)),
+            };
+            Ok(data)
         }
 
-        /// Test case for the `set_max_contributors` function of the `Raiser` contract.
+        /// Sets a new maximum number of contributors.
         ///
-        /// This test simulates the owner's call to `set_max_contributors` function.
-        /// It first sets up the testing environment by getting the default accounts and setting the callee and caller.
-        /// The callee is set to the contract's account ID and the caller is set to Alice's account (the owner).
+        /// This function updates the `max_contributors` field of the contract. 
+        /// It can only be called by the owner of the contract. If a non-owner 
+        /// attempts to call this function, it will return an `NotContractOwner` error.
+        /// Setting the maximum to the current number of contributors starts the payment phase immediately.
+        /// Once the maximum is reached, new members are turned away with a `PoolFull` error, so the pool stays in the payment phase.
+        /// A maximum of 0 means no maximum is set yet: admission is then bounded by `MAX_POOL_SIZE` only.
         ///
-        /// Then it creates a new instance of the `Raiser` contract and asserts that the initial maximum number of contributors is zero.
+        /// # Arguments
         ///
-        /// It then calls `set_max_contributors` to set the maximum number of contributors to 10 and asserts that the function returns `Ok(())`.
+        /// * `new_max` - The new maximum number of contributors.
         ///
-        /// Finally, it asserts that the maximum number of contributors is now 10.
-        #[ink::test]
-        fn set_max_contributors_works() {
-            let accounts =
-            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        /// # Returns
+        ///
+        /// * `Ok(())` if the `max_contributors` was successfully updated.
+        /// * `Err(Error::NotContractOwner)` if the caller is not the owner of the contract.
+        /// * `Err(Error::InvalidMaxContributors)` if `new_max` is below the current number of contributors or above `MAX_POOL_SIZE`.
+        
+        #[ink(message)]
+        pub fn set_max_contributors(&mut self, new_max: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.validate_max_contributors(new_max)?;
+            self.config.max_contributors = new_max;
+            self.start_turn();
+            Ok(())
+        }
 
-            let mut contract: Raiser = Raiser::new();
-            assert_eq!(contract.get_max_contributors(), 0);
-            assert_eq!(contract.set_max_contributors(10), Ok(()));
-            assert_eq!(contract.get_max_contributors(), 10);
+        /// Sets or clears the treasurer of the pool.
+        ///
+        /// The treasurer holds the `Approver` role, so they can approve payout requests on behalf of the owner but cannot change the pool settings.
+        /// The previous treasurer loses the role. Only admins can call this function, otherwise it returns a `NotContractOwner` error.
+
+        #[ink(message)]
+        pub fn set_treasurer(&mut self, treasurer: Option<AccountId>) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            self.ensure_role(caller, Role::Admin)?;
+            if let Some(previous) = self.treasurer {
+                self.set_role(Role::Approver, previous, false, caller);
+            }
+            if let Some(account) = treasurer {
+                self.set_role(Role::Approver, account, true, caller);
+            }
+            self.treasurer = treasurer;
+            Ok(())
         }
 
-        /// This test function verifies the functionality of the `contribute` function in the `Raiser` contract.
-        /// It first creates a new instance of the `Raiser` contract and asserts that the total supply of tokens is initially zero.
-        /// Then, it simulates a user (Alice) contributing to the contract by setting the transferred value to 100.
-        /// Finally, it asserts that the total supply of tokens has increased by the contributed amount (100 in this case).
-        #[ink::test]
-        fn contribute_works() {
-            let mut contract = Raiser::new();
-            assert_eq!(contract.get_total_supply(), 0);
-            let accounts =
-            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.contribute(), Ok(()));
-            assert_eq!(contract.get_total_supply(), 100);
+        /// Proposes to replace the owner with `new_owner`, e.g. because the operator disappeared or misbehaves.
+        ///
+        /// The `propose_impeachment` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if another proposal is still open. If so, it returns an `ImpeachmentActive` error.
+        /// - Opens a proposal for `IMPEACHMENT_WINDOW` milliseconds, records the total membership weight as its electorate,
+        ///   counts the caller's vote and emits an `ImpeachmentProposed` event.
+        ///
+        /// The proposal is cancelled if the contribution cycle ends before it passes, since the members who could vote on it leave the pool.
+        /// Returns the ID of the proposal, or an `Error` if it could not be opened.
 
-             // Try to contribute again
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(200);
-            // Check if the contribution fails as expected
-            assert_eq!(contract.contribute(), Err(Error::AlreadyContributed));
-            // Check if the total supply is still the same
-            assert_eq!(contract.get_total_supply(), 100);
+        #[ink(message)]
+        pub fn propose_impeachment(&mut self, new_owner: AccountId) -> Result<u128> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            let now = self.env().block_timestamp();
+            if matches!(self.impeachment, Some(open) if Self::is_impeachment_open(&open, now)) {
+                return Err(Error::ImpeachmentActive);
+            }
 
-            // Check if Alice is in the list of contributors
-            let contributors = contract.get_contributors();
-            assert_eq!(contributors.len(), 1);
-            assert_eq!(contributors[0].0, accounts.alice);
-            assert_eq!(contributors[0].1, 100);
-         }
+            let id = self.impeachment.map_or(Some(1), |previous| previous.id.checked_add(1)).ok_or(Error::ArithmeticOverflow)?;
+            let ends_at = now.saturating_add(IMPEACHMENT_WINDOW);
+            self.impeachment = Some(Impeachment {
+                id,
+                new_owner,
+                ends_at,
+                votes: 0,
+                electorate: self.total_weight,
+                executed: false,
+                cancelled: false,
+            });
+            self.env().emit_event(ImpeachmentProposed {
+                id,
+                proposer: caller,
+                new_owner,
+                ends_at,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.vote_impeachment(id)?;
+            Ok(id)
+        }
 
-        /// This test function verifies the functionality of the `request_token` function in the `Raiser` contract.
-        /// It first creates a new instance of the `Raiser` contract and sets the maximum number of contributors to 1.
-        /// Then, it simulates a user (Alice) contributing to the contract.
-        /// After the contribution, Alice requests a token.
-        /// Finally, it asserts that Alice's request has been added to the requests list.
-        #[ink::test]
-        fn request_token_works() {
-            let mut contract = Raiser::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.set_max_contributors(1), Ok(()));
-            assert_eq!(contract.contribute(), Ok(()));
-            assert_eq!(contract.request_token(), Ok(()));
-            let requests = contract.requests;
-            assert_eq!(requests.len(), 1);
-            assert_eq!(requests[0].0, accounts.alice);
+        /// Votes for the open impeachment proposal with the caller's membership tier.
+        ///
+        /// The `vote_impeachment` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if `id` is the open proposal and voting has not closed. If not, it returns an `ImpeachmentNotFound` error.
+        /// - Checks if the caller has already voted. If so, it returns an `AlreadyVoted` error.
+        /// - Adds the caller's membership tier to the votes of the proposal.
+        ///
+        /// Returns `Ok(())` if the vote was counted, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn vote_impeachment(&mut self, id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            let mut proposal = match self.impeachment {
+                Some(open) if open.id == id && Self::is_impeachment_open(&open, self.env().block_timestamp()) => open,
+                _ => return Err(Error::ImpeachmentNotFound),
+            };
+            if self.impeachment_votes.contains((id, caller)) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            self.impeachment_votes.insert((id, caller), &true);
+            proposal.votes = proposal.votes.checked_add(self.weight_of(caller) as u128).ok_or(Error::ArithmeticOverflow)?;
+            self.impeachment = Some(proposal);
+            self.record_activity(caller);
+            Ok(())
         }
 
+        /// Replaces the owner once a supermajority of the members voted for the open impeachment proposal. Anyone can call this function.
+        ///
+        /// The `execute_impeachment` function performs the following operations:
+        /// - Checks if `id` is the open proposal and voting has not closed. If not, it returns an `ImpeachmentNotFound` error.
+        /// - Checks if the votes reach `IMPEACHMENT_THRESHOLD_BPS` of the electorate, the larger of the membership weight when the proposal
+        ///   was opened and the current one. If not, or if the electorate is 0, it returns a `QuorumNotReached` error. Members who leave
+        ///   after voting therefore cannot lower the quorum, and members who join later raise it.
+        /// - Checks if the weighted votes overflow. If so, it returns an `ArithmeticOverflow` error.
+        /// - Revokes every role and every automation grant of any account other than the new owner, removes the treasurer,
+        ///   grants every role to the new owner and makes the new owner the owner. Admins appointed by the impeached owner
+        ///   would otherwise keep control of the pool.
+        /// - Emits an `OwnerImpeached` event.
+        ///
+        /// Returns `Ok(())` if the owner was replaced, or an `Error` if not.
 
-        #[ink::test]
-        fn approve_request_works() {
-            let mut contract = Raiser::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            // Simulate a contribution from Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.set_max_contributors(1), Ok(()));
-            assert_eq!(contract.contribute(), Ok(()));
+        #[ink(message)]
+        pub fn execute_impeachment(&mut self, id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let mut proposal = match self.impeachment {
+                Some(open) if open.id == id && Self::is_impeachment_open(&open, self.env().block_timestamp()) => open,
+                _ => return Err(Error::ImpeachmentNotFound),
+            };
+            let electorate = proposal.electorate.max(self.total_weight);
+            let votes = proposal.votes.checked_mul(10_000).ok_or(Error::ArithmeticOverflow)?;
+            let quorum = electorate.checked_mul(IMPEACHMENT_THRESHOLD_BPS).ok_or(Error::ArithmeticOverflow)?;
+            if electorate == 0 || votes < quorum {
+                return Err(Error::QuorumNotReached);
+            }
 
-            contract.request_token().unwrap();
-             
-            // Try to approve the request as the owner
-            assert_eq!(contract.approve_request(contract.owner), Ok(()));
-          
+            let previous = self.owner;
+            let pool = self.env().account_id();
+            for (role, account) in self.role_holders.clone() {
+                if account != proposal.new_owner {
+                    self.set_role(role, account, false, pool);
+                }
+            }
+            for role in [Role::Admin, Role::Approver, Role::Pauser, Role::Upgrader] {
+                self.set_role(role, proposal.new_owner, true, pool);
+            }
+            for operator in self.automation_operators.clone() {
+                if operator != proposal.new_owner {
+                    self.set_automation_permissions(operator, 0);
+                }
+            }
+            self.treasurer = None;
+            self.owner = proposal.new_owner;
+            proposal.executed = true;
+            self.impeachment = Some(proposal);
+
+            self.env().emit_event(OwnerImpeached {
+                id,
+                previous_owner: previous,
+                new_owner: proposal.new_owner,
+                pool_id: pool,
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
         }
 
-        // This test checks the functionality of the `get_next_requester` function.
-        // It simulates contributions and token requests from two accounts, Alice and Bob.
-        // The test verifies that `get_next_requester` correctly returns the account that should be the next to receive tokens.
-        // Even after Bob makes a request, Alice is returned as the next requester because her request came first.
-        #[ink::test]
-        fn get_next_requester_works() {
-            let mut contract = Raiser::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        /// Returns the latest impeachment proposal, if any.
+        #[ink(message)]
+        pub fn get_impeachment(&self) -> Option<Impeachment> {
+            self.impeachment
+        }
 
-            // Initially, there should be no next requester
-            assert_eq!(contract.get_next_requester(), None);
+        /// Grants `role` to `account`.
+        ///
+        /// Only admins can call this function, otherwise it returns a `NotContractOwner` error. Emits a `RoleGranted` event.
 
-            // Simulate a contribution and token request from Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.set_max_contributors(2), Ok(()));
-            assert_eq!(contract.contribute(), Ok(()));
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            self.ensure_role(caller, Role::Admin)?;
+            self.set_role(role, account, true, caller);
+            Ok(())
+        }
 
-            // Now, Alice should be the next requester
-            assert_eq!(contract.get_next_requester(), Some(accounts.alice));
+        /// Revokes `role` from `account`.
+        ///
+        /// Only admins can call this function, otherwise it returns a `NotContractOwner` error. Emits a `RoleRevoked` event.
 
-            // Simulate a contribution and token request from Bob
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.contribute(), Ok(()));
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            self.ensure_role(caller, Role::Admin)?;
+            self.set_role(role, account, false, caller);
+            Ok(())
+        }
 
-            // Alice should still be the next requester, because Bob's request comes after Alice's
-            assert_eq!(contract.get_next_requester(), Some(accounts.alice));
+        /// Returns whether `account` holds `role`.
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.contains((role, account))
         }
 
-        // This test checks the functionality of the `get_completed_payouts` function.
-        // It simulates a contribution and token request from Alice, and then approves the request.
-        // The test verifies that `get_completed_payouts` correctly returns the number of completed payouts.
-        // After the approval of Alice's request, the number of completed payouts should increase by one.
+        /// Delegates operations to an automation operator, such as a keeper bot contract, without sharing the owner key.
+        ///
+        /// The `set_automation` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if `permissions` only contains flags from `AUTOMATION_PERMISSIONS`. If not, it returns an `InvalidConfig` error.
+        /// - Replaces the permissions of `operator`, or revokes them all if `permissions` is 0, and emits an `AutomationSet` event.
+        ///
+        /// Returns `Ok(())` if the permissions were updated, or an `Error` if not.
 
-        #[ink::test]
-        fn get_completed_payouts_works() {
-            let mut contract = Raiser::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        #[ink(message)]
+        pub fn set_automation(&mut self, operator: AccountId, permissions: u8) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            if permissions & !AUTOMATION_PERMISSIONS != 0 {
+                return Err(Error::InvalidConfig);
+            }
 
-            // Initially, there should be no completed payouts
-            assert_eq!(contract.get_completed_payouts(), 0);
+            self.set_automation_permissions(operator, permissions);
+            Ok(())
+        }
 
-            // Simulate a contribution and token request from Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.set_max_contributors(2), Ok(()));
-            assert_eq!(contract.contribute(), Ok(()));
+        /// Returns the permission flags delegated to `operator`, or 0 if it is not an automation operator.
+        #[ink(message)]
+        pub fn get_automation(&self, operator: AccountId) -> u8 {
+            self.automation.get(operator).unwrap_or(0)
+        }
 
-            // Simulate a contribution from bob
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.contribute(), Ok(()));
-           
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            assert_eq!(contract.request_token(), Ok(()));
+        /// Returns the treasurer of the pool, if any.
+        #[ink(message)]
+        pub fn get_treasurer(&self) -> Option<AccountId> {
+            self.treasurer
+        }
 
-            // Approve the request
-            assert_eq!(contract.approve_request(contract.owner), Ok(()));
+        /// Returns the maximum number of contributors.
+        ///
+        /// This function returns the `max_contributors` field of the contract. 
+        /// It can be called by any user to know the maximum number of contributors allowed.
+        ///
+        /// # Returns
+        ///
+        /// * `u128` - The maximum number of contributors.
+        
+        #[ink(message)]
+        pub fn get_max_contributors(&self) -> u128 {
+            self.config.max_contributors
+        }
 
-            // Now, there should be one completed payout
-             assert_eq!(contract.get_completed_payouts(), 1);
-            
-            
-             assert_eq!(contract.get_payout_history().len(),1);
+        /// Returns all pool settings at once.
+        #[ink(message)]
+        pub fn get_config(&self) -> Config {
+            self.config.clone()
+        }
 
+        /// Returns the existential deposit of the chain, which contributions, refunds and payout installments are checked against.
+        #[ink(message)]
+        pub fn get_existential_deposit(&self) -> Balance {
+            self.env().minimum_balance()
         }
 
-        // This test checks the functionality of the `next_contribution_cycle` function.
-        // It calls `next_contribution_cycle` twice and checks that the cycle number increases each time.
-        // The test verifies that `next_contribution_cycle` correctly increments the cycle counter.
-        #[ink::test]
-        fn next_contribution_cycle_works() {
-            let mut contract = Raiser::new();
+        /// Updates several pool settings in one call.
+        ///
+        /// The `update_config` function performs the following operations:
+        /// - Checks if the caller is the contract owner. If not, it returns a `NotContractOwner` error.
+        /// - Applies every field of `patch` that is set.
+        /// - Validates the resulting settings. If the minimum amount is zero, the quorum exceeds the maximum number of contributors,
+        ///   the contribution window is longer than the period or the cover interest exceeds 10 000 basis points, it returns an `InvalidConfig` error,
+        ///   and if the maximum number of contributors is out of bounds, it returns an `InvalidMaxContributors` error.
+        /// - If the patch sets the minimum amount, the payout order or the referral bonus while a cycle is underway, queues these fields until
+        ///   the cycle ends, merged into any queued patch, and emits a `ConfigQueued` event with the whole queued patch.
+        ///   Members who already paid keep the terms they joined on.
+        /// - Applies the other fields right away and emits a `ConfigUpdated` event with the old and new settings.
+        ///
+        /// Returns `Ok(())` if the settings were updated or queued, or an `Error` if not.
 
-            // Initially, we should be in the first contribution cycle
-            assert_eq!(contract.contribution_cycle, 1);
+        #[ink(message)]
+        pub fn update_config(&mut self, patch: ConfigPatch) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
 
-            // Move to the next contribution cycle
-            contract.next_contribution_cycle();
+            let new = self.merge_config(patch.clone())?;
+            let gated = patch.min_amount.is_some() || patch.payout_order.is_some() || patch.referral_bonus.is_some();
+            if !gated || self.contributors_count == 0 {
+                self.set_config(new);
+                return Ok(());
+            }
 
-            // Now, we should be in the second contribution cycle
-            assert_eq!(contract.contribution_cycle, 2);
-            
+            let mut queued = self.pending_config.clone().unwrap_or_default();
+            queued.min_amount = patch.min_amount.or(queued.min_amount);
+            queued.payout_order = patch.payout_order.or(queued.payout_order);
+            queued.referral_bonus = patch.referral_bonus.or(queued.referral_bonus);
+            // The queued fields must still be valid on top of the fields applied now
+            self.merge_config(ConfigPatch {
+                min_amount: queued.min_amount,
+                payout_order: queued.payout_order,
+                referral_bonus: queued.referral_bonus,
+                ..patch.clone()
+            })?;
+
+            let immediate = ConfigPatch {
+                min_amount: None,
+                payout_order: None,
+                referral_bonus: None,
+                ..patch
+            };
+            if immediate != ConfigPatch::default() {
+                let new = self.merge_config(immediate)?;
+                self.set_config(new);
+            }
+            self.pending_config = Some(queued.clone());
+            self.env().emit_event(ConfigQueued {
+                patch: queued,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
         }
-        #[ink::test]
-        fn total_contributors_works() {
-            let mut contract = Raiser::new();
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-            // Initially, there should be no contributors
-            assert_eq!(contract.total_contributors(), 0);
+        /// Returns the settings change queued until the current cycle ends, if any.
+        #[ink(message)]
+        pub fn pending_config(&self) -> Option<ConfigPatch> {
+            self.pending_config.clone()
+        }
 
-            // Simulate a contribution from Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.set_max_contributors(1), Ok(()));
-            assert_eq!(contract.contribute(), Ok(()));
+        /// Allows a user to contribute to the contract with a 1x membership tier.
+        ///
+        /// This is a shorthand for `contribute_with_weight(1)`.
 
-            // Now, there should be one contributor
-            assert_eq!(contract.total_contributors(), 1);
+        #[ink(message, payable)]
+        pub fn contribute(&mut self) -> Result<()> {
+            self.contribute_with_weight(1)
+        }
 
-            // Simulate a contribution from Bob
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
-            assert_eq!(contract.contribute(), Ok(()));
+        /// Registers the caller as a member with a chosen membership tier, without paying an installment.
+        ///
+        /// The `join` function runs the same checks as `contribute_with_weight`, except the minimum amount, and assigns the caller's
+        /// queue position at enrollment. The caller then pays each installment, including the current round's, with `contribute_round`.
+        /// Payouts wait until every member has paid the round.
+        ///
+        /// Returns `Ok(())` if the caller joined, or an `Error` if not.
 
-            // Now, there should be two contributors
-            assert_eq!(contract.total_contributors(), 2);
+        #[ink(message)]
+        pub fn join(&mut self, weight: u8) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+
+            self.validate_contribution(caller, None, weight)?;
+            self.admit(caller, weight)?;
+
+            self.env().emit_event(MemberJoined {
+                member: caller,
+                weight,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
         }
 
-    }
-   
-}
+        /// Allows a user to contribute to the contract with a chosen membership tier.
+        ///
+        /// The `contribute_with_weight` function is called when a user wants to contribute to the contract. It performs the following operations:
+        /// - Checks if `weight` is between 1 and `MAX_WEIGHT`. If not, it returns an `InvalidWeight` error.
+        /// - Acts for the member if the caller is an unexpired session key, as registered with `register_session_key`.
+        /// - Checks if the caller is already a member. If so, it returns an `AlreadyMember` error.
+        /// - Checks if the transferred value is less than the minimum amount multiplied by `weight`. If so, it returns a `LowAmount` error.
+        /// - Checks if the transferred value is below the existential deposit. If so, it returns a `BelowExistentialDeposit` error.
+        /// - Retrieves the amount the caller has already funded.
+        /// - Increments the contributors count, adds `weight` payout slots for the caller to the contributors list, and marks the caller as having contributed.
+        /// - Updates the amount the caller has funded and their balance, and counts the contribution as their installment for the current round.
+        /// - Increases the total supply by the transferred value.
+        /// - Emits a `Transfer` event with the new total supply.
+        ///
+        /// Returns `Ok(())` if the contribution is successful, or an `Error` if not. A rejected contribution reverts, so it emits no event:
+        /// the returned error, which is part of the contract metadata, is what wallets decode and show. `can_contribute` runs the same checks
+        /// without a transaction.
+
+        #[ink(message, payable)]
+        pub fn contribute_with_weight(&mut self, weight: u8) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller: ink::primitives::AccountId = self.on_behalf_of(self.env().caller());
+            let value: u128 = self.env().transferred_value();
+
+            self.validate_contribution(caller, Some(value), weight)?;
+            let credited = self.settle_installment(caller, value, self.installment_for(weight)?)?;
+
+            self.admit(caller, weight)?;
+            self.credit(caller, credited)?;
+
+            Self::env().emit_event(
+                Transfer {
+                from: None,
+                to: Some(caller),
+                value: self.total_supply,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Allows a user to join the pool with a 1x membership tier, recording `referrer` as the account that invited them.
+        ///
+        /// The `contribute_with_referrer` function performs the following operations:
+        /// - Checks if `referrer` is the caller. If so, it returns an `InvalidReferrer` error.
+        /// - Joins the pool like `contribute`.
+        /// - Records the referral. Once the caller completes a full cycle, `referrer` earns the referral bonus, if the fee reserve covers it.
+        ///
+        /// Returns `Ok(())` if the contribution is successful, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn contribute_with_referrer(&mut self, referrer: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.on_behalf_of(self.env().caller());
+            if referrer == caller {
+                return Err(Error::InvalidReferrer);
+            }
 
+            self.contribute_with_weight(1)?;
 
+            self.referrers.insert(caller, &referrer);
+            let mut referrals = self.get_referrals(referrer);
+            referrals.push(caller);
+            self.referrals.insert(referrer, &referrals);
+            Ok(())
+        }
+
+        /// Returns the members `account` has referred.
+        #[ink(message)]
+        pub fn get_referrals(&self, account: AccountId) -> Vec<AccountId> {
+            self.referrals.get(account).unwrap_or_default()
+        }
+
+        /// Adds the transferred value to the fee reserve that pays referral bonuses. Anyone can fund the reserve.
+        #[ink(message, payable)]
+        pub fn fund_fee_reserve(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let value = self.env().transferred_value();
+            if value == 0 {
+                return Err(Error::LowAmount);
+            }
+
+            self.fee_reserve = self.fee_reserve.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Returns the referral bonuses `account` has earned and not withdrawn yet.
+        #[ink(message)]
+        pub fn get_referral_rewards(&self, account: AccountId) -> Balance {
+            self.referral_rewards.get(account).unwrap_or(0)
+        }
+
+        /// Transfers the referral bonuses the caller has earned.
+        ///
+        /// Returns `Ok(())` if the bonuses were transferred, a `LowAmount` error if there is nothing to withdraw,
+        /// or a `TransferError` if the transfer fails.
+
+        #[ink(message)]
+        pub fn withdraw_referral_rewards(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let amount = self.get_referral_rewards(caller);
+            if amount == 0 {
+                return Err(Error::LowAmount);
+            }
+
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::TransferError);
+            }
+            self.referral_rewards.remove(caller);
+            self.rewards_owed = self.rewards_owed.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.saturating_sub(amount);
+            Ok(())
+        }
+
+        /// Allows a user to join the pool with a 1x membership tier and choose how they receive their payout.
+        ///
+        /// Joins the pool like `contribute`, then stores `preference`. The preference cannot be changed afterwards.
+        ///
+        /// Returns `Ok(())` if the contribution is successful, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn contribute_with_preference(&mut self, preference: PayoutPreference) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.on_behalf_of(self.env().caller());
+            self.contribute_with_weight(1)?;
+            self.payout_preferences.insert(caller, &preference);
+            Ok(())
+        }
+
+        /// Returns how `account` receives their payout.
+        #[ink(message)]
+        pub fn get_payout_preference(&self, account: AccountId) -> PayoutPreference {
+            self.payout_preferences.get(account).unwrap_or_default()
+        }
+
+        /// Returns the unclaimed part of the split payout of `account`, if any.
+        #[ink(message)]
+        pub fn get_pending_payout(&self, account: AccountId) -> Option<PendingPayout> {
+            self.pending_payouts.get(account)
+        }
+
+        /// Claims the installments of the caller's split payout for the rounds since their last claim.
+        ///
+        /// The `claim_installment` function performs the following operations:
+        /// - Checks if the caller has a split payout with an installment due. If not, it returns a `NothingToClaim` error.
+        /// - Transfers one installment per round since the last claim, and the remainder with the last installment. If the transfer fails, it returns a `TransferError`.
+        /// - Emits a `Transfer` event.
+        ///
+        /// Returns `Ok(())` if the installments were claimed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn claim_installment(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let mut pending = self.pending_payouts.get(caller).ok_or(Error::NothingToClaim)?;
+
+            let due = pending.installments_left.min(self.current_round.saturating_sub(pending.claimed_until));
+            if due == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            let amount = if due == pending.installments_left {
+                pending.remaining
+            } else {
+                pending.remaining.checked_mul(due).ok_or(Error::ArithmeticOverflow)? / pending.installments_left
+            };
+
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::TransferError);
+            }
+            pending.remaining -= amount;
+            pending.installments_left -= due;
+            pending.claimed_until += due;
+            if pending.installments_left == 0 {
+                self.pending_payouts.remove(caller);
+            } else {
+                self.pending_payouts.insert(caller, &pending);
+            }
+            self.escrowed_total = self.escrowed_total.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.saturating_sub(amount);
+
+            self.env().emit_event(Transfer {
+                from: Some(self.owner),
+                to: Some(caller),
+                value: amount,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Allows a member to pay their installment for the current round.
+        ///
+        /// The `contribute_round` function is called by an existing member once per round. It performs the following operations:
+        /// - Checks if the caller is a member, a session key of a member, or in the team of a member, whose slot is then paid. If not, it returns a `NotContributor` error.
+        /// - Checks if the commit deadline of the round has passed. If so, installments are only accepted from `reveal_contribution` and it returns a `RevealOnly` error.
+        /// - Checks if the caller has already paid this round. If so, it returns an `AlreadyPaidThisRound` error.
+        /// - Checks if the transferred value plus the caller's prepayment is less than the minimum amount multiplied by the caller's weight. If so, it returns a `LowAmount` error.
+        /// - Checks if the transferred value, or an excess to refund, is below the existential deposit. If so, it returns a `BelowExistentialDeposit` error.
+        /// - Refunds or prepays any excess, depending on the overpayment setting.
+        /// - Updates the amount the caller has funded, their balance and the total supply.
+        /// - Emits a `Transfer` event with the new total supply.
+        ///
+        /// Returns `Ok(())` if the installment is accepted, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn contribute_round(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            if self.is_reveal_only() {
+                return Err(Error::RevealOnly);
+            }
+            let caller = self.slot_of(self.on_behalf_of(self.env().caller()));
+            let value = self.env().transferred_value();
+            self.pay_installment(caller, value)?;
+            self.record_activity(self.on_behalf_of(self.env().caller()));
+            Ok(())
+        }
+
+        /// Allows a member to pay the missing installment of another member for the current round.
+        ///
+        /// The `cover_for` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if `member` is the caller. If so, it returns a `CannotCoverSelf` error.
+        /// - Checks if the round is overdue, i.e. a contribution period is configured and its deadline plus the grace period has passed.
+        ///   If not, it returns a `RoundIncomplete` error.
+        /// - Pays the installment of `member` with the transferred value, with the same checks as `contribute_round`.
+        /// - Records that `member` owes the caller the credited amount plus the cover interest. The debt is deducted from the next payout
+        ///   of `member` and credited to the caller's pending withdrawal.
+        /// - Emits a `MemberCovered` event.
+        ///
+        /// Returns `Ok(())` if the installment was covered, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn cover_for(&mut self, member: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            if member == caller {
+                return Err(Error::CannotCoverSelf);
+            }
+            if !self.is_round_overdue() {
+                return Err(Error::RoundIncomplete);
+            }
+
+            let funded = self.ledger_balance(member);
+            self.pay_installment(member, self.env().transferred_value())?;
+            let credited = self.ledger_balance(member) - funded;
+            let interest = credited.checked_mul(self.config.cover_interest_bps as u128).ok_or(Error::ArithmeticOverflow)? / 10_000;
+            let debt = credited.checked_add(interest).ok_or(Error::ArithmeticOverflow)?;
+
+            let mut debts = self.cover_debts.get(member).unwrap_or_default();
+            debts.push((caller, debt));
+            self.cover_debts.insert(member, &debts);
+            self.env().emit_event(MemberCovered {
+                member,
+                coverer: caller,
+                amount: credited,
+                debt,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the part of the payouts of `account` held back for the installments they still owe in the cycle.
+        #[ink(message)]
+        pub fn get_lockup(&self, account: AccountId) -> Balance {
+            self.lockups.get(account).unwrap_or(0)
+        }
+
+        /// Returns the installments other members covered for `member` and not yet recovered from their payout, as `(creditor, amount owed)` pairs.
+        #[ink(message)]
+        pub fn get_cover_debts(&self, member: AccountId) -> CoverDebts {
+            self.cover_debts.get(member).unwrap_or_default()
+        }
+
+        /// Allows a member to pay their installment for the current round with an accepted alternative asset.
+        ///
+        /// The caller must first grant this contract a PSP22 allowance of `amount` on `asset`. The `contribute_round_with_asset` function performs the following operations:
+        /// - Checks if a converter is configured and `asset` is accepted. If not, it returns a `ConverterNotSet` or `AssetNotAccepted` error.
+        /// - Checks if the commit deadline of the round has passed. If so, it returns a `RevealOnly` error.
+        /// - Calls `PSP22::transfer_from` on `asset`, approves the converter and calls `AssetConverter::swap_to_native` on it. If a call fails, it returns a `TransferError`.
+        /// - Checks if the native amount received is less than `min_out`. If so, it returns a `SlippageExceeded` error.
+        /// - Pays the installment with the received amount like `contribute_round`, and emits an `AssetConverted` event.
+        ///
+        /// Returns `Ok(())` if the installment was paid, or an `Error` if not. Any error reverts the transfer and the swap.
+
+        #[ink(message)]
+        pub fn contribute_round_with_asset(&mut self, asset: AccountId, amount: Balance, min_out: Balance) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let converter = self.converter.ok_or(Error::ConverterNotSet)?;
+            if !self.is_accepted_asset(asset) {
+                return Err(Error::AssetNotAccepted);
+            }
+            if self.is_reveal_only() {
+                return Err(Error::RevealOnly);
+            }
+
+            let received = self.convert_to_native(caller, converter, asset, amount)?;
+            if received < min_out {
+                return Err(Error::SlippageExceeded);
+            }
+
+            self.pay_installment(self.slot_of(caller), received)?;
+            self.env().emit_event(AssetConverted {
+                member: caller,
+                asset,
+                amount_in: amount,
+                amount_out: received,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Lets the caller share their payout slot with a team.
+        ///
+        /// The `create_team` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if every team member is neither a pool member nor in another team, and listed once, and the team has at most `MAX_TEAM_SIZE` members.
+        ///   If not, it returns an `InvalidTeamMember` error.
+        /// - Replaces the caller's previous team, if any, and emits a `TeamCreated` event.
+        ///
+        /// Team members can pay the slot's installments and request its payout. The slot and the payout stay with the caller, the team's beneficiary.
+        /// Returns `Ok(())` if the team was created, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn create_team(&mut self, members: Vec<AccountId>) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+
+            if members.len() > MAX_TEAM_SIZE {
+                return Err(Error::InvalidTeamMember);
+            }
+            for (i, member) in members.iter().enumerate() {
+                let taken = self.team_of.get(member).is_some_and(|beneficiary| beneficiary != caller);
+                if *member == caller || self.contributed.contains(member) || taken || members[..i].contains(member) {
+                    return Err(Error::InvalidTeamMember);
+                }
+            }
+
+            for member in self.get_team(caller) {
+                self.team_of.remove(member);
+            }
+            for member in &members {
+                self.team_of.insert(member, &caller);
+            }
+            self.teams.insert(caller, &members);
+
+            self.env().emit_event(TeamCreated {
+                beneficiary: caller,
+                members,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the team members sharing the payout slot of `beneficiary`.
+        #[ink(message)]
+        pub fn get_team(&self, beneficiary: AccountId) -> Vec<AccountId> {
+            self.teams.get(beneficiary).unwrap_or_default()
+        }
+
+        /// Returns the beneficiary whose payout slot `account` acts for, if `account` is in a team.
+        #[ink(message)]
+        pub fn team_of(&self, account: AccountId) -> Option<AccountId> {
+            self.team_of.get(account)
+        }
+
+        /// Proposes that members `a` and `b` swap their payout positions.
+        ///
+        /// The `propose_queue_swap` function performs the following operations:
+        /// - Checks if the caller is `a` or `b`. If not, it returns an `Unauthorized` error.
+        /// - Checks if both members are still waiting for their payout and are different accounts. If not, it returns a `NotContributor` error.
+        /// - Stores the proposal and emits a `SwapProposed` event.
+        ///
+        /// Both members, including the proposer, then confirm with `confirm_swap`. Returns the ID of the swap, or an `Error`.
+
+        #[ink(message)]
+        pub fn propose_queue_swap(&mut self, a: AccountId, b: AccountId) -> Result<u128> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if caller != a && caller != b {
+                return Err(Error::Unauthorized);
+            }
+
+            if a == b || !self.queue_contains(a) || !self.queue_contains(b) {
+                return Err(Error::NotContributor);
+            }
+
+            let swap_id = self.next_swap_id;
+            self.next_swap_id = self.next_swap_id.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.queue_swaps.insert(swap_id, &QueueSwap {
+                a,
+                b,
+                confirmed_a: false,
+                confirmed_b: false,
+            });
+            self.env().emit_event(SwapProposed {
+                swap_id,
+                a,
+                b,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(swap_id)
+        }
+
+        /// Confirms a proposed swap of payout positions.
+        ///
+        /// The `confirm_swap` function performs the following operations:
+        /// - Checks if the swap exists. If not, it returns a `SwapNotFound` error.
+        /// - Checks if the caller is one of the two members. If not, it returns an `Unauthorized` error.
+        /// - Records the confirmation. Once both members have confirmed, it checks that neither has been paid out or has a pending request,
+        ///   otherwise it returns a `NotContributor` or `RequestPending` error, swaps every payout slot of the two members and emits a `PositionsSwapped` event.
+        ///
+        /// Returns `Ok(())` if the confirmation was recorded, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn confirm_swap(&mut self, swap_id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let mut swap = self.queue_swaps.get(swap_id).ok_or(Error::SwapNotFound)?;
+
+            if caller == swap.a {
+                swap.confirmed_a = true;
+            } else if caller == swap.b {
+                swap.confirmed_b = true;
+            } else {
+                return Err(Error::Unauthorized);
+            }
+
+            if !(swap.confirmed_a && swap.confirmed_b) {
+                self.queue_swaps.insert(swap_id, &swap);
+                return Ok(());
+            }
+
+            let QueueSwap { a, b, .. } = swap;
+            if !self.queue_contains(a) || !self.queue_contains(b) {
+                return Err(Error::NotContributor);
+            }
+            if self.requests.iter().any(|(requester, _, _)| requester == &a || requester == &b) {
+                return Err(Error::RequestPending);
+            }
+
+            self.queue_swap(a, b);
+            self.queue_swaps.remove(swap_id);
+            self.env().emit_event(PositionsSwapped {
+                swap_id,
+                a,
+                b,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the proposed swap with ID `swap_id`, if it is still pending.
+        #[ink(message)]
+        pub fn get_queue_swap(&self, swap_id: u128) -> Option<QueueSwap> {
+            self.queue_swaps.get(swap_id)
+        }
+
+        /// Commits the caller to the hash of their installment for the current round.
+        ///
+        /// `commitment` is the BLAKE2x256 hash of the SCALE-encoded `(amount, salt)` pair, as returned by `get_commitment_hash`.
+        /// Compute it off-chain so the amount never leaves the member's device before the reveal. A new commitment replaces the previous one.
+        ///
+        /// Returns `Ok(())` if the commitment was stored, a `NotContributor` error if the caller is not a member, or a `CommitPhaseClosed`
+        /// error if the commit deadline of the round has passed.
+
+        #[ink(message)]
+        pub fn commit_contribution(&mut self, commitment: Hash) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            if self.is_reveal_only() {
+                return Err(Error::CommitPhaseClosed);
+            }
+
+            self.commitments.insert(caller, &(commitment, self.current_round));
+            Ok(())
+        }
+
+        /// Pays the installment the caller committed to in `commit_contribution`.
+        ///
+        /// The `reveal_contribution` function performs the following operations:
+        /// - Checks if the commit deadline of the round has passed, if the pool has one. If not, it returns a `RevealNotOpen` error.
+        /// - Checks if the caller committed to an installment in the current round. If not, it returns a `NoCommitment` error.
+        /// - Checks if the transferred value and `salt` hash to the commitment. If not, it returns a `CommitmentMismatch` error.
+        /// - Pays the installment like `contribute_round` and clears the commitment.
+        ///
+        /// Returns `Ok(())` if the installment is accepted, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn reveal_contribution(&mut self, salt: [u8; 32]) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let value = self.env().transferred_value();
+            if self.get_commit_deadline().is_some() && !self.is_reveal_only() {
+                return Err(Error::RevealNotOpen);
+            }
+
+            let commitment = match self.commitments.get(caller) {
+                Some((commitment, round)) if round == self.current_round => commitment,
+                _ => return Err(Error::NoCommitment),
+            };
+            if self.get_commitment_hash(value, salt) != commitment {
+                return Err(Error::CommitmentMismatch);
+            }
+
+            self.pay_installment(caller, value)?;
+            self.commitments.remove(caller);
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Returns the commitment of an installment of `amount` with `salt`, for testing commitments computed off-chain.
+        #[ink(message)]
+        pub fn get_commitment_hash(&self, amount: Balance, salt: [u8; 32]) -> Hash {
+            Hash::from(self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(amount, salt)))
+        }
+
+        /// Returns the timestamp at which commitments close for the current round and only reveals are accepted,
+        /// or `None` if the pool has no `commit_period`.
+        #[ink(message)]
+        pub fn get_commit_deadline(&self) -> Option<Timestamp> {
+            match self.config.commit_period {
+                0 => None,
+                period => Some(self.round_opened_at.saturating_add(period)),
+            }
+        }
+
+        /// Returns the overpayment `account` has credited toward future installments.
+        #[ink(message)]
+        pub fn get_prepaid(&self, account: AccountId) -> Balance {
+            self.prepaid.get(account).unwrap_or(0)
+        }
+
+        /// Sets the PSP22 token used to pull recurring installments.
+        ///
+        /// Only the owner can call this function, otherwise it returns a `NotContractOwner` error.
+
+        #[ink(message)]
+        pub fn set_payment_token(&mut self, token: Option<AccountId>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.payment_token = token;
+            Ok(())
+        }
+
+        /// Sets the DEX router that swaps alternative assets into the native token, or removes it with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+
+        #[ink(message)]
+        pub fn set_converter(&mut self, converter: Option<AccountId>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.converter = converter;
+            Ok(())
+        }
+
+        /// Returns the DEX router that swaps alternative assets into the native token, if any.
+        #[ink(message)]
+        pub fn get_converter(&self) -> Option<AccountId> {
+            self.converter
+        }
+
+        /// Accepts or stops accepting a PSP22 `asset` for installments.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+
+        #[ink(message)]
+        pub fn set_accepted_asset(&mut self, asset: AccountId, accepted: bool) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            if accepted {
+                self.accepted_assets.insert(asset, &true);
+            } else {
+                self.accepted_assets.remove(asset);
+            }
+            Ok(())
+        }
+
+        /// Returns whether members can pay their installments with `asset`.
+        #[ink(message)]
+        pub fn is_accepted_asset(&self, asset: AccountId) -> bool {
+            self.accepted_assets.contains(asset)
+        }
+
+        /// Sets the price oracle that pegs the minimum amount to a fiat reference, or removes it with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// Returns an `InvalidConfig` error if the fiat amount is zero.
+
+        #[ink(message)]
+        pub fn set_price_oracle(&mut self, price_oracle: Option<PriceOracle>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            if matches!(price_oracle, Some(oracle) if oracle.fiat_amount == 0) {
+                return Err(Error::InvalidConfig);
+            }
+            self.price_oracle = price_oracle;
+            Ok(())
+        }
+
+        /// Returns the price oracle of the pool, if any.
+        #[ink(message)]
+        pub fn get_price_oracle(&self) -> Option<PriceOracle> {
+            self.price_oracle
+        }
+
+        /// Returns the minimum amount per weight unit in native token units right now.
+        ///
+        /// With a price oracle, the fiat minimum is converted at the latest price. If the price is older than `max_age`,
+        /// is zero, or the oracle cannot be queried, the fixed `min_amount` of the config applies.
+        #[ink(message)]
+        pub fn get_min_amount(&self) -> Balance {
+            match (self.price_oracle, self.oracle_price()) {
+                (Some(PriceOracle { fiat_amount, .. }), Some(price)) => fiat_amount.saturating_mul(price),
+                _ => self.config.min_amount,
+            }
+        }
+
+        /// Returns the contributions of contribution cycle `cycle`, valued at the oracle price when each was made.
+        ///
+        /// Unlike a valuation at today's price, the report does not move with the market, so it shows what members put in
+        /// in fiat terms. The fiat value of each contribution is also recorded in its receipt.
+        #[ink(message)]
+        pub fn get_cycle_value_report(&self, cycle: u128) -> CycleValueReport {
+            self.cycle_values.get(cycle).unwrap_or_default()
+        }
+
+        /// Sets the anti-sybil constraints new members have to meet.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// The constraints only apply to new members: existing members keep their payout slots.
+
+        #[ink(message)]
+        pub fn set_join_constraints(&mut self, constraints: JoinConstraints) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.join_constraints = constraints;
+            Ok(())
+        }
+
+        /// Returns the anti-sybil constraints new members have to meet.
+        #[ink(message)]
+        pub fn get_join_constraints(&self) -> JoinConstraints {
+            self.join_constraints
+        }
+
+        /// Sets the KYC attestation registry new members must be attested by, or removes it with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// When set, `contribute` and `join` call `AttestationRegistry::is_attested` on the registry and reject unattested accounts.
+
+        #[ink(message)]
+        pub fn set_attestation_registry(&mut self, registry: Option<AccountId>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.attestation_registry = registry;
+            Ok(())
+        }
+
+        /// Sets the XCM gateway contract that routes payouts to remote beneficiaries, or removes it with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// The gateway must implement the `XcmGateway` trait. Without a gateway, payouts go to the member's own account.
+
+        #[ink(message)]
+        pub fn set_xcm_gateway(&mut self, gateway: Option<AccountId>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.xcm_gateway = gateway;
+            Ok(())
+        }
+
+        /// Returns the XCM gateway contract, if any.
+        #[ink(message)]
+        pub fn get_xcm_gateway(&self) -> Option<AccountId> {
+            self.xcm_gateway
+        }
+
+        /// Registers the account on another parachain that receives the caller's pushed payouts, or removes it with `None`.
+        ///
+        /// The `set_remote_beneficiary` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Stores or removes the beneficiary and emits a `RemoteBeneficiarySet` event.
+        ///
+        /// Escrowed, split and pull payouts are still claimed from the member's own account. Returns `Ok(())`, or an `Error`.
+
+        #[ink(message)]
+        pub fn set_remote_beneficiary(&mut self, beneficiary: Option<RemoteBeneficiary>) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+
+            if let Some(beneficiary) = beneficiary {
+                self.remote_beneficiaries.insert(caller, &beneficiary);
+            } else {
+                self.remote_beneficiaries.remove(caller);
+            }
+            self.env().emit_event(RemoteBeneficiarySet {
+                account: caller,
+                beneficiary,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the remote-chain beneficiary `account` registered for their payouts, if any.
+        #[ink(message)]
+        pub fn get_remote_beneficiary(&self, account: AccountId) -> Option<RemoteBeneficiary> {
+            self.remote_beneficiaries.get(account)
+        }
+
+        /// Returns the KYC attestation registry new members must be attested by, if any.
+        #[ink(message)]
+        pub fn get_attestation_registry(&self) -> Option<AccountId> {
+            self.attestation_registry
+        }
+
+        /// Caps the total value the pool may hold, or removes the cap with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// While a cap is set, contributions that would raise the accounted balance of the pool above it are rejected with a `CapExceeded` error.
+        /// Lowering the cap below the current balance does not touch the funds already held.
+
+        #[ink(message)]
+        pub fn set_tvl_cap(&mut self, cap: Option<Balance>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.tvl_cap = cap;
+            Ok(())
+        }
+
+        /// Returns the most native funds the pool may hold, if capped.
+        #[ink(message)]
+        pub fn get_tvl_cap(&self) -> Option<Balance> {
+            self.tvl_cap
+        }
+
+        /// Records the current block as the first interaction of the caller, which starts their account age.
+        ///
+        /// Checking in again keeps the first block. Returns the block at which the caller first checked in.
+
+        #[ink(message)]
+        pub fn check_in(&mut self) -> Result<BlockNumber> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let first_seen = match self.first_seen.get(caller) {
+                Some(block) => block,
+                None => {
+                    let block = self.env().block_number();
+                    self.first_seen.insert(caller, &block);
+                    block
+                }
+            };
+            Ok(first_seen)
+        }
+
+        /// Returns the block at which `account` first checked in, if it has.
+        #[ink(message)]
+        pub fn get_first_seen(&self, account: AccountId) -> Option<BlockNumber> {
+            self.first_seen.get(account)
+        }
+
+        /// Sets the token an account must hold to join the pool, or removes the gate with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// The gate only applies to new members: existing members keep their payout slots.
+
+        #[ink(message)]
+        pub fn set_membership_gate(&mut self, gate: Option<MembershipGate>) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.membership_gate = gate;
+            Ok(())
+        }
+
+        /// Returns the token an account must hold to join the pool, if any.
+        #[ink(message)]
+        pub fn get_membership_gate(&self) -> Option<MembershipGate> {
+            self.membership_gate
+        }
+
+        /// Authorizes a keeper to pull the caller's installment for up to `max_rounds` rounds.
+        ///
+        /// The `authorize_recurring` function is paired with a PSP22 allowance granted by the member to this contract. It performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if `amount` is less than the minimum amount multiplied by the caller's weight. If so, it returns a `LowAmount` error.
+        /// - Stores the authorization, replacing any previous one, and emits a `RecurringAuthorized` event.
+        ///
+        /// Passing `max_rounds = 0` revokes the authorization.
+
+        #[ink(message)]
+        pub fn authorize_recurring(&mut self, amount: Balance, max_rounds: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+
+            if max_rounds == 0 {
+                self.recurring.remove(caller);
+                return Ok(());
+            }
+
+            if amount < self.installment_for(self.weight_of(caller))? {
+                return Err(Error::LowAmount);
+            }
+
+            self.recurring.insert(caller, &RecurringIntent {
+                amount,
+                remaining_rounds: max_rounds,
+            });
+
+            self.env().emit_event(RecurringAuthorized {
+                member: caller,
+                amount,
+                max_rounds,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the recurring contribution authorization of `account`, if any.
+        #[ink(message)]
+        pub fn get_recurring(&self, account: AccountId) -> Option<RecurringIntent> {
+            self.recurring.get(account)
+        }
+
+        /// Pulls a member's installment for the current round from their PSP22 allowance.
+        ///
+        /// The `pull_contribution` function can be called by any keeper. It performs the following operations:
+        /// - Checks if the member has a remaining recurring authorization. If not, it returns a `NoRecurringAuthorization` error.
+        /// - Checks if the member has already paid this round. If so, it returns an `AlreadyPaidThisRound` error.
+        /// - Checks if the payment token and a converter are configured. If not, it returns a `PaymentTokenNotSet` or `ConverterNotSet` error.
+        /// - Pulls the authorized amount of the payment token and swaps it into the native token like `contribute_round_with_asset`.
+        ///   If a call fails, it returns a `TransferError`.
+        /// - Pays the installment with the received amount like `contribute_round`, decrements the remaining rounds, and emits a `ContributionPulled` event.
+        ///
+        /// Returns `Ok(())` if the installment was collected, or an `Error` if not. Any error reverts the transfer and the swap.
+
+        #[ink(message)]
+        pub fn pull_contribution(&mut self, member: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let intent = match self.recurring.get(member) {
+                Some(intent) if intent.remaining_rounds > 0 => intent,
+                _ => return Err(Error::NoRecurringAuthorization),
+            };
+
+            if self.has_contributed_this_round(member) {
+                return Err(self.already_paid(member));
+            }
+
+            let token = self.payment_token.ok_or(Error::PaymentTokenNotSet)?;
+            let converter = self.converter.ok_or(Error::ConverterNotSet)?;
+            let received = self.convert_to_native(member, converter, token, intent.amount)?;
+            self.settle_pull(member, intent, received)
+        }
+
+        /// Returns the index of the current scheduled period, or 0 if no schedule is configured.
+        #[ink(message)]
+        pub fn current_period(&self) -> u64 {
+            match self.config.period_length {
+                0 => 0,
+                length => self.schedule_clock() / length,
+            }
+        }
+
+        /// Checks if contributions are accepted right now, i.e. no schedule is configured or the current period is within its contribution window.
+        #[ink(message)]
+        pub fn is_contribution_window_open(&self) -> bool {
+            match self.config.period_length {
+                0 => true,
+                length => self.schedule_clock() % length < self.config.contribution_window,
+            }
+        }
+
+        /// Returns the current payout round.
+        #[ink(message)]
+        pub fn get_current_round(&self) -> u128 {
+            self.current_round
+        }
+
+        /// Estimates the round in which `account` receives their next payout, from their position in the payout queue
+        /// and the number of members each round pays out to.
+        ///
+        /// The estimate is exact for the join order. With the need score order, or if members defer or are skipped, the order can still change.
+        /// Returns `None` if `account` has no payout slot left in the current cycle.
+        #[ink(message)]
+        pub fn estimated_payout_round(&self, account: AccountId) -> Option<u128> {
+            let position = self.queue_position(account)? as u128;
+            let winners_per_round = self.config.winners_per_round.max(1) as u128;
+            Some(self.current_round.saturating_add(position / winners_per_round))
+        }
+
+        /// Estimates the timestamp of the next payout of `account`, from their estimated payout round and the length of a round.
+        ///
+        /// A round lasts one scheduled period, or the contribution period if no schedule is configured. Returns `None` if `account` has no
+        /// payout slot left, or if rounds have no length in milliseconds, i.e. neither is configured or the schedule counts blocks.
+        #[ink(message)]
+        pub fn estimated_payout_timestamp(&self, account: AccountId) -> Option<Timestamp> {
+            let rounds_ahead = self.estimated_payout_round(account)? - self.current_round;
+            let round_length = match (self.config.period_length, self.config.period_unit) {
+                (0, _) => self.config.contribution_period,
+                (length, PeriodUnit::Milliseconds) => length,
+                (_, PeriodUnit::Blocks) => 0,
+            };
+            if round_length == 0 {
+                return None;
+            }
+            Some(self.round_opened_at.saturating_add(round_length.saturating_mul(rounds_ahead as u64)))
+        }
+
+        /// Returns every member with whether they have paid their installment for the current round.
+        #[ink(message)]
+        pub fn round_status(&self) -> Vec<(AccountId, bool)> {
+            self.balance
+                .iter()
+                .map(|(member, _)| (*member, self.has_contributed_this_round(*member)))
+                .collect()
+        }
+
+        /// Checks if `account` paid an installment for `round`.
+        #[ink(message)]
+        pub fn has_paid(&self, account: AccountId, round: u128) -> bool {
+            self.paid_rounds.contains((account, round))
+        }
+
+        /// Checks if every member has paid their installment for the current round.
+        ///
+        /// Returns `true` if all members have contributed this round, or `false` if at least one has not.
+        #[ink(message)]
+        pub fn all_contributed_this_round(&self) -> bool {
+            self.balance
+                .iter()
+                .all(|(account_id, _)| self.has_contributed_this_round(*account_id))
+        }
+
+        /// Retrieves the list of contributors and their balances.
+        ///
+        /// The `get_contributors` function iterates over the list of contributors, retrieves the balance for each contributor using the `balance_of` function, and adds a tuple of the account ID and balance to the `contributors` vector.
+        /// Weighted members that occupy several payout slots are only listed once.
+        /// In private mode, the balances of other accounts are reported as 0 unless the caller is the owner.
+        ///
+        /// Returns a vector of tuples, where each tuple contains an account ID and the corresponding balance.
+
+        #[ink(message)]
+        pub fn get_contributors(&self) -> Vec<(AccountId, Balance)> {
+            let mut contributors: Vec<(AccountId, Balance)> = Vec::new();
+            for account_id in self.queue_accounts() {
+                if contributors.iter().any(|(member, _)| member == &account_id) {
+                    continue;
+                }
+                let balance = self.balance_of(account_id);
+                contributors.push((account_id, balance));
+            }
+            contributors
+        }
+
+        /// Returns the timestamp of the last interaction of `account` with the pool, if any.
+        #[ink(message)]
+        pub fn get_last_active_at(&self, account: AccountId) -> Option<Timestamp> {
+            self.last_active_at.get(account)
+        }
+
+        /// Returns the members who have not interacted with the pool for more than `threshold_ms` milliseconds.
+        ///
+        /// Members without any recorded activity are listed as well. Payments, requests, claims, withdrawals, deferrals and votes count as activity.
+        #[ink(message)]
+        pub fn inactive_members(&self, threshold_ms: Timestamp) -> Vec<AccountId> {
+            let now = self.env().block_timestamp();
+            self.balance
+                .iter()
+                .map(|(member, _)| *member)
+                .filter(|member| match self.last_active_at.get(member) {
+                    Some(active_at) => now.saturating_sub(active_at) > threshold_ms,
+                    None => true,
+                })
+                .collect()
+        }
+
+        /// Returns the Merkle root of the member set.
+        ///
+        /// Each member is a leaf holding the BLAKE2b-256 hash of their SCALE-encoded account, in a tree of depth `MEMBER_TREE_DEPTH`
+        /// whose empty leaves are zero. The root is updated when a member joins or is removed.
+        #[ink(message)]
+        pub fn get_member_root(&self) -> Hash {
+            match self.member_tree.get((MEMBER_TREE_DEPTH, 0)) {
+                Some(root) => root,
+                None => self.empty_member_nodes()[MEMBER_TREE_DEPTH as usize],
+            }
+        }
+
+        /// Returns the leaf index of `account` and the sibling of each node on the path from its leaf to the root, if it is a member.
+        #[ink(message)]
+        pub fn get_member_proof(&self, account: AccountId) -> Option<(u32, Vec<Hash>)> {
+            let leaf = self.member_leaf.get(account)?;
+            let zeros = self.empty_member_nodes();
+            let mut index = leaf;
+            let mut siblings = Vec::with_capacity(MEMBER_TREE_DEPTH as usize);
+            for level in 0..MEMBER_TREE_DEPTH {
+                siblings.push(self.member_tree.get((level, index ^ 1)).unwrap_or(zeros[level as usize]));
+                index /= 2;
+            }
+            Some((leaf, siblings))
+        }
+
+        /// Checks a membership proof of `account` against the current member root.
+        ///
+        /// Returns `true` if hashing the leaf of `account` at `index` with `siblings`, from the leaf up, yields the member root.
+        #[ink(message)]
+        pub fn verify_member_proof(&self, account: AccountId, index: u32, siblings: Vec<Hash>) -> bool {
+            if siblings.len() != MEMBER_TREE_DEPTH as usize {
+                return false;
+            }
+            let mut index = index;
+            let mut node = self.member_leaf_hash(account);
+            for sibling in siblings {
+                node = if index.is_multiple_of(2) {
+                    self.hash_member_nodes(node, sibling)
+                } else {
+                    self.hash_member_nodes(sibling, node)
+                };
+                index /= 2;
+            }
+            index == 0 && node == self.get_member_root()
+        }
+
+        /// Returns the contributors and their balances, as `get_contributors` does, with the display name each of them registered.
+        #[ink(message)]
+        pub fn get_contributors_with_aliases(&self) -> Vec<(AccountId, Balance, Option<Vec<u8>>)> {
+            self.get_contributors()
+                .into_iter()
+                .map(|(account, balance)| (account, balance, self.aliases.get(account)))
+                .collect()
+        }
+
+        /// Allows a contributor to request tokens without a memo.
+        ///
+        /// This is a shorthand for `request_token_with_memo(None)`.
+
+        #[ink(message)]
+        pub fn request_token(&mut self) -> Result<()> {
+            self.request_token_with_memo(None)
+        }
+
+        /// Allows a contributor to request tokens, optionally stating why.
+        ///
+        /// The `request_token_with_memo` function is called when a contributor wants to request tokens. `memo_hash` is the hash of an off-chain note,
+        /// such as the IPFS CID of the stated purpose, so approvers and members can see why funds are being requested. It performs the following operations:
+        /// - Checks if the number of contributors has reached the maximum limit, or the quorum after the enrollment deadline. If not, it returns a `NotPaymentPhase` error.
+        /// - Checks if the caller, or the beneficiary of the caller's team, is the next eligible requester. If not, it returns a `NotNextContributor` error.
+        /// - If the caller is the next eligible requester, it assigns the next request ID, adds a request for the current round's pot to the `requests` vector and stores the memo hash in the request details.
+        /// - Emits a `RequestCreated` event with the request ID and memo hash.
+        ///
+        /// Returns `Ok(())` if the token request is successful, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn request_token_with_memo(&mut self, memo_hash: Option<Hash>) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.slot_of(self.env().caller());
+
+            self.can_request(caller)?;
+
+            let amount = self.round_pot;
+            let request_id = self.next_request_id;
+            self.next_request_id = self.next_request_id.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.requests.push((caller, amount, request_id));
+            self.request_details.insert(request_id, &RequestInfo {
+                requester: caller,
+                amount,
+                status: RequestStatus::Pending,
+                created_at: self.env().block_timestamp(),
+                decided_at: None,
+                memo_hash,
+            });
+
+            self.env().emit_event(RequestCreated {
+                request_id,
+                requester: caller,
+                amount,
+                memo_hash,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.record_activity(self.env().caller());
+            Ok(())
+        }
+
+        /// Checks whether `account` could contribute `amount` right now.
+        ///
+        /// The `can_contribute` function runs the same validation as `contribute` without mutating any state,
+        /// so frontends can disable buttons and show the exact error before submitting a transaction.
+        ///
+        /// Returns `Ok(())` if the contribution would be accepted, or the `Error` that `contribute` would return.
+
+        #[ink(message)]
+        pub fn can_contribute(&self, account: AccountId, amount: Balance) -> Result<()> {
+            self.validate_contribution(account, Some(amount), 1)
+        }
+
+        /// Returns the membership tier of `account`, or 0 if the account is not a contributor.
+        #[ink(message)]
+        pub fn weight_of(&self, account: AccountId) -> u8 {
+            self.weights.get(account).unwrap_or(0)
+        }
+
+        /// Checks if the pool is in the payment phase.
+        ///
+        /// Payouts start once the number of contributors reaches the maximum or, if a quorum is configured,
+        /// once the enrollment deadline has passed with at least `min_contributors` members. The quorum also closes enrollment.
+        #[ink(message)]
+        pub fn is_payment_phase(&self) -> bool {
+            if self.contributors_count == self.config.max_contributors {
+                return true;
+            }
+
+            let quorum = self.config.min_contributors;
+            let deadline = self.config.enrollment_deadline;
+            quorum > 0
+                && deadline > 0
+                && self.contributors_count >= quorum
+                && self.env().block_timestamp() >= deadline
+        }
+
+        /// Checks whether `account` could request tokens right now.
+        ///
+        /// The `can_request` function runs the same validation as `request_token` without mutating any state.
+        ///
+        /// Returns `Ok(())` if the request would be accepted, or the `Error` that `request_token` would return.
+
+        #[ink(message)]
+        pub fn can_request(&self, account: AccountId) -> Result<()> {
+            self.ensure_active()?;
+
+            if !self.is_payment_phase() {
+                return Err(Error::NotPaymentPhase);
+            }
+
+            if Some(account) != self.get_next_requester() {
+                return Err(Error::NotNextContributor);
+            }
+            Ok(())
+        }
+
+        /// Allows the contract owner to approve a token request.
+        ///
+        /// The `approve_request` function is called when the contract owner wants to approve a token request. It performs the following operations:
+        /// - Checks if the caller holds the `Approver` role, unless the caller is an automation operator with the `AUTOMATE_APPROVE` permission. If not, it returns an `Unauthorized` error.
+        /// - Checks if `request_id` is a pending request. If not, it returns a `NoPendingRequest` error.
+        /// - Checks again if the requester is eligible, as `can_request` does at request time, and returns the same error if not.
+        /// - Re-validates the requested amount against the current round's pot, since contributions may have arrived or been refunded since the request,
+        ///   and updates the amount in the request details. Only the installments of the current round are paid out, never earlier rounds' contributions.
+        /// - Checks if every member has contributed to the current round. If not, and the grace period of the round has lapsed,
+        ///   the insurance reserve covers the missing installments and adds them to the payout. Otherwise it returns a `RoundIncomplete` error.
+        /// - Checks if a payout to the owner was co-signed, when the owner policy requires it. If not, it returns a `CoSignatureRequired` error.
+        /// - Adds the current round's sponsorship and an even share of the unallocated sponsorship to the requested amount.
+        /// - Checks if the contract's available balance covers the amount. If not, it returns an `InsufficientContractBalance` error.
+        /// - If the pool pays out to several winners per round, picks the next members in the queue as co-winners and splits the amount between them
+        ///   evenly or by weight, as configured. Each co-winner's share is recorded as an approved request of its own, and every step below applies to each winner.
+        ///   Co-winners whose share would be below the existential deposit are left out and keep their slot.
+        /// - Deducts the installments other members covered for the requester, plus interest, and credits them to the creditors' pending withdrawals.
+        /// - If payout lock-ups are enabled, holds back the installments the requester still owes in the cycle.
+        /// - Pays the installment of the new round from the lock-up of every member who has one.
+        /// - If the requester chose a split payout, holds the amount until they claim it in installments, no more installments than keep each one at or above the existential deposit. If the pool uses pull delivery, adds the amount to the requester's pending withdrawal. If a claim window is configured, holds the amount in escrow until the requester claims it. Otherwise attempts to transfer the requested amount of tokens to the requester, through the XCM gateway if they registered a
+        ///   remote beneficiary. If the transfer fails, the payout moves to the failed-payout queue,
+        ///   from which `retry_payout` or `redirect_payout` deliver it, so the rotation carries on.
+        /// - If the transfer is successful, it marks the request as approved and any other pending request as cancelled, resets the `requests` vector, removes the requester's payout slot, records the payout for the need score, increments the `completed_payouts` count and the `current_round`, and logs the number of completed payouts.
+        /// - Adds the payout to the `payout_history`, resets the round's pot, and starts the next contribution cycle once every slot has been paid, emitting a `CycleStats` event for the finished one.
+        /// - Emits a `Transfer` event, or a `PayoutEscrowed` event for an escrowed payout, and a `RequestApproved` event with the amount of tokens paid out.
+        ///
+        /// Returns `Ok(())` if the approval and transfer are successful, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn approve_request(&mut self, request_id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.is_automated(caller, AUTOMATE_APPROVE) {
+                self.ensure_role(caller, Role::Approver)?;
+            }
+            self.approve_next(request_id)
+        }
+
+        /// Approves any pending request that waited longer than the approval timeout. Anyone can call this function,
+        /// so an absent owner cannot freeze the payouts.
+        ///
+        /// The `force_approve` function performs the following operations:
+        /// - Checks if `request_id` is the oldest pending request. If not, it returns a `NotContributor` error.
+        /// - Checks if an approval timeout is configured and has passed since the request was created. If not, it returns a `TimeoutNotReached` error.
+        /// - With the `ForceApprove` action, approves and pays the request like `approve_request`, with the same checks.
+        /// - With the `Expire` action, marks the request as expired, drops it and emits a `RequestExpired` event.
+        ///
+        /// Returns `Ok(())` if the request was resolved, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn force_approve(&mut self, request_id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let requester = match self.requests.first() {
+                Some((requester, _, pending_id)) if *pending_id == request_id => *requester,
+                _ => return Err(Error::NotContributor),
+            };
+
+            let timeout = self.config.approval_timeout;
+            let created_at = self.request_details.get(request_id).map_or(0, |details| details.created_at);
+            if timeout == 0 || self.env().block_timestamp() < created_at.saturating_add(timeout) {
+                return Err(Error::TimeoutNotReached);
+            }
+
+            match self.config.approval_timeout_action {
+                ApprovalTimeoutAction::ForceApprove => self.approve_next(request_id),
+                ApprovalTimeoutAction::Expire => {
+                    self.close_request(request_id, RequestStatus::Expired);
+                    self.requests.remove(0);
+                    self.env().emit_event(RequestExpired {
+                        request_id,
+                        requester,
+                        pool_id: self.env().account_id(),
+                        cycle: self.contribution_cycle,
+                        round: self.current_round,
+                    });
+                    Ok(())
+                }
+            }
+        }
+
+        /// Pays out the pending request `request_id`, as described in `approve_request`.
+        fn approve_next(&mut self, request_id: u128) -> Result<()> {
+            let requester = match self.requests.iter().find(|(_, _, pending_id)| *pending_id == request_id) {
+                Some((requester, _, _)) => *requester,
+                None => return Err(Error::NoPendingRequest),
+            };
+            self.can_request(requester)?;
+
+            let insured = if self.all_contributed_this_round() {
+                0
+            } else {
+                self.cover_defaults()?
+            };
+
+            let requested = self.round_pot;
+            if let Some(mut details) = self.request_details.get(request_id) {
+                if details.amount != requested {
+                    details.amount = requested;
+                    self.request_details.insert(request_id, &details);
+                }
+            }
+            let (cycle, round) = (self.contribution_cycle, self.current_round);
+            if requester == self.owner && self.owner_policy == OwnerPolicy::CoSigned && !self.cosigners.contains(request_id) {
+                return Err(Error::CoSignatureRequired);
+            }
+            let round_sponsorship = self.get_round_sponsorship(self.current_round);
+            self.collect_sponsorship_dust()?;
+            let even_sponsorship = self.even_sponsorship_share();
+            let gross = Self::checked_sum([requested, round_sponsorship, even_sponsorship, insured]).ok_or(Error::ArithmeticOverflow)?;
+            if gross > self.get_available_balance().saturating_sub(self.escrowed_total) {
+                return Err(Error::InsufficientContractBalance);
+            }
+            let mut winners = self.round_winners(requester);
+            let mut shares = self.winner_shares(&winners, gross)?;
+            // Co-winners whose share would be below the existential deposit keep their slot for a later round
+            while winners.len() > 1 && shares.iter().any(|share| *share < self.env().minimum_balance()) {
+                winners.pop();
+                shares = self.winner_shares(&winners, gross)?;
+            }
+            let remaining_rounds = (self.queue_len().saturating_sub(winners.len() as u32) as u128)
+                .div_ceil(winners.len() as u128);
+            for (_, _, pending_id) in self.requests.clone() {
+                let status = if pending_id == request_id {
+                    RequestStatus::Approved
+                } else {
+                    RequestStatus::Cancelled
+                };
+                self.close_request(pending_id, status);
+            }
+            self.requests = Vec::default();
+            let mut payouts = Vec::with_capacity(winners.len());
+            for (winner, share) in winners.into_iter().zip(shares) {
+                let payout_id = if winner == requester {
+                    request_id
+                } else {
+                    self.record_co_winner(winner, share)?
+                };
+                payouts.push(self.pay_winner(winner, payout_id, share, remaining_rounds)?);
+            }
+            self.unallocated_sponsorship = self.unallocated_sponsorship.checked_sub(even_sponsorship).ok_or(Error::ArithmeticOverflow)?;
+            self.round_sponsorship.remove(self.current_round);
+            self.current_round = self.current_round.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.round_opened_at = self.env().block_timestamp();
+            self.start_turn();
+            self.principal = self.principal.saturating_sub(requested);
+            self.round_pot = 0;
+            self.sponsorship_balance = self.sponsorship_balance.saturating_sub(round_sponsorship + even_sponsorship);
+            for payout in &payouts {
+                self.repay_cover_debts(payout.recipient, payout.debt)?;
+                if payout.lockup > 0 {
+                    let lockup = self.get_lockup(payout.recipient).checked_add(payout.lockup).ok_or(Error::ArithmeticOverflow)?;
+                    self.lockups.insert(payout.recipient, &lockup);
+                    self.escrowed_total = self.escrowed_total.checked_add(payout.lockup).ok_or(Error::ArithmeticOverflow)?;
+                }
+            }
+            self.apply_lockups()?;
+            self.next_contribution_cycle()?;
+
+            for payout in payouts {
+                self.deliver_winner(payout, cycle, round)?;
+            }
+            self.notify_registry_stats();
+            Ok(())
+        }
+
+        /// Returns the winners of the round requested by `requester`: the requester first,
+        /// followed by the next distinct members in the queue up to `winners_per_round`.
+        fn round_winners(&self, requester: AccountId) -> Vec<AccountId> {
+            let mut winners = vec![requester];
+            for index in 0..self.queue_len() {
+                if winners.len() as u32 >= self.config.winners_per_round {
+                    break;
+                }
+                if let Some(member) = self.queue_get(index) {
+                    if !winners.contains(&member) {
+                        winners.push(member);
+                    }
+                }
+            }
+            winners
+        }
+
+        /// Splits `gross` between `winners` following the `winner_split` setting. The remainder goes to the first winner, the requester.
+        /// Returns an `ArithmeticOverflow` error if a weighted share would overflow.
+        fn winner_shares(&self, winners: &[AccountId], gross: Balance) -> Result<Vec<Balance>> {
+            let parts: Vec<u128> = match self.config.winner_split {
+                WinnerSplit::Even => vec![1; winners.len()],
+                WinnerSplit::ByWeight => winners.iter().map(|winner| self.weight_of(*winner).max(1) as u128).collect(),
+            };
+            let total: u128 = parts.iter().sum();
+            let mut shares = parts
+                .iter()
+                .map(|part| gross.checked_mul(*part).map(|weighted| weighted / total).ok_or(Error::ArithmeticOverflow))
+                .collect::<Result<Vec<Balance>>>()?;
+            shares[0] += gross - shares.iter().sum::<Balance>();
+            Ok(shares)
+        }
+
+        /// Records the `share` of co-winner `winner` as an approved request and returns its ID.
+        fn record_co_winner(&mut self, winner: AccountId, share: Balance) -> Result<u128> {
+            let request_id = self.next_request_id;
+            self.next_request_id = self.next_request_id.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            let now = self.env().block_timestamp();
+            self.request_details.insert(request_id, &RequestInfo {
+                requester: winner,
+                amount: share,
+                status: RequestStatus::Approved,
+                created_at: now,
+                decided_at: Some(now),
+                memo_hash: None,
+            });
+            Ok(request_id)
+        }
+
+        /// Pays `share` to `winner` under `request_id`: deducts their cover debts and lock-up, attempts a push transfer
+        /// if the payout is not held, and removes their payout slot.
+        ///
+        /// Returns the `WinnerPayout` that `deliver_winner` completes once the round is closed.
+        fn pay_winner(&mut self, winner: AccountId, request_id: u128, share: Balance, remaining_rounds: u128) -> Result<WinnerPayout> {
+            let debt = self.cover_debt_of(winner).min(share);
+            let lockup = if self.config.payout_lockup {
+                let installment = self.installment_for(self.weight_of(winner))?;
+                installment.checked_mul(remaining_rounds).ok_or(Error::ArithmeticOverflow)?.min(share - debt)
+            } else {
+                0
+            };
+            let amount = share - debt - lockup;
+            let split = self.get_payout_preference(winner) == PayoutPreference::Split;
+            let pull = !split && self.config.payout_delivery == PayoutDelivery::Pull;
+            let escrowed = split || pull || self.config.claim_window > 0;
+            let failed = !escrowed && !self.push_payout(winner, amount);
+            if let Some(index) = self.queue_position(winner) {
+                self.queue_remove(index);
+            }
+            self.last_paid_round.insert(winner, &self.current_round);
+            let payouts_received = self.payouts_received.get(winner).unwrap_or(0).checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.payouts_received.insert(winner, &payouts_received);
+            let received = self.received.get(winner).unwrap_or(0).checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.received.insert(winner, &received);
+            self.completed_payouts = self.completed_payouts.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.payout_history.push((winner, amount));
+            if !escrowed && !failed {
+                self.held_balance = self.held_balance.saturating_sub(amount);
+            }
+            self.total_payouts = self.total_payouts.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.total_paid_out = self.total_paid_out.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            Ok(WinnerPayout { recipient: winner, request_id, amount, debt, lockup, split, pull, escrowed, failed })
+        }
+
+        /// Holds or records the delivery of `payout`, paid in round `round` of cycle `cycle`, and emits its events.
+        /// Returns an `ArithmeticOverflow` error if a held balance would overflow.
+        fn deliver_winner(&mut self, payout: WinnerPayout, cycle: u128, round: u128) -> Result<()> {
+            let WinnerPayout { recipient: requester, request_id, amount, split, pull, escrowed, failed, .. } = payout;
+            if split {
+                let mut pending = match self.pending_payouts.get(requester) {
+                    Some(pending) => PendingPayout {
+                        remaining: pending.remaining.checked_add(amount).ok_or(Error::ArithmeticOverflow)?,
+                        installments_left: pending.installments_left.max(self.queue_len() as u128 + 1),
+                        claimed_until: pending.claimed_until,
+                    },
+                    None => PendingPayout {
+                        remaining: amount,
+                        installments_left: self.queue_len() as u128 + 1,
+                        claimed_until: self.current_round - 1,
+                    },
+                };
+                // Every installment must be transferable on its own
+                if let Some(transferable) = pending.remaining.checked_div(self.env().minimum_balance()) {
+                    pending.installments_left = pending.installments_left.min(transferable).max(1);
+                }
+                let installments = pending.installments_left;
+                self.pending_payouts.insert(requester, &pending);
+                self.escrowed_total = self.escrowed_total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.env().emit_event(PayoutSplit {
+                    requester,
+                    amount,
+                    installments,
+                    pool_id: self.env().account_id(),
+                    cycle,
+                    round,
+                });
+            } else if pull {
+                let withdrawal = self.withdrawals.get(requester).unwrap_or(0).checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.withdrawals.insert(requester, &withdrawal);
+                self.escrowed_total = self.escrowed_total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.env().emit_event(PayoutQueued {
+                    requester,
+                    amount,
+                    pool_id: self.env().account_id(),
+                    cycle,
+                    round,
+                });
+            } else if escrowed {
+                let (pending, _) = self.escrow.get(requester).unwrap_or((0, 0));
+                let expires_at = self.env().block_timestamp().saturating_add(self.config.claim_window);
+                let pending = pending.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.escrow.insert(requester, &(pending, expires_at));
+                self.escrowed_total = self.escrowed_total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.env().emit_event(PayoutEscrowed {
+                    requester,
+                    amount,
+                    expires_at,
+                    pool_id: self.env().account_id(),
+                    cycle,
+                    round,
+                });
+            } else if failed {
+                self.failed_payouts.insert(request_id, &FailedPayout {
+                    recipient: requester,
+                    amount,
+                    redirect_to: None,
+                    owner_consent: false,
+                    recipient_consent: false,
+                });
+                self.failed_payout_ids.push(request_id);
+                self.escrowed_total = self.escrowed_total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.env().emit_event(PayoutFailed {
+                    requester,
+                    request_id,
+                    amount,
+                    pool_id: self.env().account_id(),
+                    cycle,
+                    round,
+                });
+            } else {
+                self.env().emit_event(Transfer {
+                    from: Some(self.owner),
+                    to: Some(requester),
+                    value:amount,
+                });
+            }
+            self.env().emit_event(RequestApproved {
+                request_id,
+                requester,
+                amount,
+                pool_id: self.env().account_id(),
+                cycle,
+                round,
+            });
+            Ok(())
+        }
+
+        /// Retries the transfer of a payout that failed at approval.
+        ///
+        /// The `retry_payout` function performs the following operations:
+        /// - Checks if a failed payout is queued under `request_id`. If not, it returns a `FailedPayoutNotFound` error.
+        /// - Attempts to transfer the payout to its recipient. If the transfer fails again, it returns a `TransferError` and the payout stays queued.
+        /// - Emits a `Transfer` event.
+        ///
+        /// Anyone can retry, since the funds only go to the recipient. Returns `Ok(())` if the payout was delivered, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn retry_payout(&mut self, request_id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let failed = self.failed_payouts.get(request_id).ok_or(Error::FailedPayoutNotFound)?;
+            self.deliver_failed_payout(request_id, failed, failed.recipient)
+        }
+
+        /// Consents to sending a failed payout to another account.
+        ///
+        /// The `redirect_payout` function performs the following operations:
+        /// - Checks if a failed payout is queued under `request_id`. If not, it returns a `FailedPayoutNotFound` error.
+        /// - Checks if the caller holds the `Admin` role or is the recipient. If not, it returns an `Unauthorized` error.
+        /// - Records the consent of the caller to `new_dest`. Proposing another destination than the pending one drops the earlier consent.
+        /// - Once both an admin and the recipient consented, transfers the payout to `new_dest` and emits a `PayoutRedirected` event.
+        ///   If the transfer fails, it returns a `TransferError`.
+        ///
+        /// Returns `Ok(())` if the consent was recorded or the payout delivered, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn redirect_payout(&mut self, request_id: u128, new_dest: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let mut failed = self.failed_payouts.get(request_id).ok_or(Error::FailedPayoutNotFound)?;
+            let admin = self.ensure_role(caller, Role::Admin).is_ok();
+            if !admin && caller != failed.recipient {
+                return Err(Error::Unauthorized);
+            }
+
+            if failed.redirect_to != Some(new_dest) {
+                failed.redirect_to = Some(new_dest);
+                failed.owner_consent = false;
+                failed.recipient_consent = false;
+            }
+            if admin {
+                failed.owner_consent = true;
+            }
+            if caller == failed.recipient {
+                failed.recipient_consent = true;
+            }
+
+            if !(failed.owner_consent && failed.recipient_consent) {
+                self.failed_payouts.insert(request_id, &failed);
+                return Ok(());
+            }
+            self.deliver_failed_payout(request_id, failed, new_dest)?;
+            self.env().emit_event(PayoutRedirected {
+                from: failed.recipient,
+                to: new_dest,
+                request_id,
+                amount: failed.amount,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the failed payout queued under `request_id`, if any.
+        #[ink(message)]
+        pub fn get_failed_payout(&self, request_id: u128) -> Option<FailedPayout> {
+            self.failed_payouts.get(request_id)
+        }
+
+        /// Allows a recipient to claim their escrowed payout.
+        ///
+        /// The `claim` function performs the following operations:
+        /// - Checks if the caller has an escrowed payout. If not, it returns a `NothingToClaim` error.
+        /// - Checks if the payout is frozen by an open dispute. If so, it returns a `PayoutDisputed` error.
+        /// - Checks if the claim window is still open. If not, it returns a `ClaimWindowClosed` error.
+        /// - Attempts to transfer the payout to the caller. If the transfer fails, it returns a `TransferError`.
+        /// - Emits a `Transfer` event.
+        ///
+        /// Returns `Ok(())` if the payout was claimed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let (amount, expires_at) = self.escrow.get(caller).ok_or(Error::NothingToClaim)?;
+            if self.disputed_escrow.contains(caller) {
+                return Err(Error::PayoutDisputed);
+            }
+            if self.env().block_timestamp() > expires_at {
+                return Err(Error::ClaimWindowClosed);
+            }
+
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::TransferError);
+            }
+            self.escrow.remove(caller);
+            self.escrowed_total = self.escrowed_total.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.saturating_sub(amount);
+
+            self.env().emit_event(Transfer {
+                from: Some(self.owner),
+                to: Some(caller),
+                value: amount,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Returns an unclaimed payout to the pool once its claim window has ended. Anyone can call this function.
+        ///
+        /// The `expire_escrow` function performs the following operations:
+        /// - Checks if `account` has an escrowed payout. If not, it returns a `NothingToClaim` error.
+        /// - Checks if the payout is frozen by an open dispute. If so, it returns a `PayoutDisputed` error.
+        /// - Checks if the claim window has ended. If not, it returns a `ClaimWindowOpen` error.
+        /// - Adds the payout to the unallocated sponsorship, so it is split evenly among the remaining payouts.
+        /// - Emits an `EscrowExpired` event.
+        ///
+        /// Returns `Ok(())` if the payout was returned to the pool, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn expire_escrow(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            let (amount, expires_at) = self.escrow.get(account).ok_or(Error::NothingToClaim)?;
+            if self.disputed_escrow.contains(account) {
+                return Err(Error::PayoutDisputed);
+            }
+            if self.env().block_timestamp() <= expires_at {
+                return Err(Error::ClaimWindowOpen);
+            }
+
+            self.escrow.remove(account);
+            self.escrowed_total = self.escrowed_total.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.unallocated_sponsorship = self.unallocated_sponsorship.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.sponsorship_balance = self.sponsorship_balance.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+
+            self.env().emit_event(EscrowExpired {
+                account,
+                amount,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Sets the external arbitration contract and the fee to raise a dispute, or removes the arbitrator with `None`.
+        ///
+        /// Only an admin can call this function, otherwise it returns a `NotContractOwner` error.
+        /// The arbitrator must implement the `Arbitrator` trait and rules by calling `resolve_dispute`.
+
+        #[ink(message)]
+        pub fn set_arbitrator(&mut self, arbitrator: Option<AccountId>, fee: Balance) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.arbitrator = arbitrator;
+            self.arbitration_fee = fee;
+            Ok(())
+        }
+
+        /// Returns the arbitration contract, if any, and the fee to raise a dispute.
+        #[ink(message)]
+        pub fn get_arbitrator(&self) -> (Option<AccountId>, Balance) {
+            (self.arbitrator, self.arbitration_fee)
+        }
+
+        /// Disputes the escrowed payout of `recipient` and escalates it to the arbitrator.
+        ///
+        /// The `raise_dispute` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if an arbitrator is configured. If not, it returns an `ArbitratorNotSet` error.
+        /// - Checks if `recipient` has an escrowed payout. If not, it returns a `NothingToClaim` error.
+        /// - Checks if the payout is already disputed. If so, it returns a `PayoutDisputed` error.
+        /// - Checks if the transferred value covers the arbitration fee. If not, it returns a `LowAmount` error.
+        /// - Calls `Arbitrator::open_case` on the arbitrator, forwarding the transferred value as its fee.
+        ///   If the call fails or the case is refused, it returns an `ArbitrationFailed` error.
+        /// - Freezes the payout until the ruling and emits a `DisputeRaised` event.
+        ///
+        /// Returns the ID of the dispute, or an `Error`.
+
+        #[ink(message, payable)]
+        pub fn raise_dispute(&mut self, recipient: AccountId) -> Result<u128> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            let arbitrator = self.arbitrator.ok_or(Error::ArbitratorNotSet)?;
+            let (amount, _) = self.escrow.get(recipient).ok_or(Error::NothingToClaim)?;
+            if self.disputed_escrow.contains(recipient) {
+                return Err(Error::PayoutDisputed);
+            }
+            let fee = self.env().transferred_value();
+            if fee < self.arbitration_fee {
+                return Err(Error::LowAmount);
+            }
+
+            let dispute_id = self.next_dispute_id;
+            let result = build_call::<crate::RaiserEnvironment>()
+                .call(arbitrator)
+                .transferred_value(fee)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("Arbitrator::open_case")))
+                        .push_arg(dispute_id)
+                        .push_arg(caller)
+                        .push_arg(recipient)
+                        .push_arg(amount),
+                )
+                .returns::<bool>()
+                .try_invoke();
+            if !matches!(result, Ok(Ok(true))) {
+                return Err(Error::ArbitrationFailed);
+            }
+
+            self.next_dispute_id = self.next_dispute_id.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.disputes.insert(dispute_id, &Dispute {
+                claimant: caller,
+                recipient,
+                amount,
+                ruling: None,
+            });
+            self.disputed_escrow.insert(recipient, &dispute_id);
+            self.env().emit_event(DisputeRaised {
+                claimant: caller,
+                recipient,
+                dispute_id,
+                amount,
+                fee,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(dispute_id)
+        }
+
+        /// Enforces the ruling of the arbitrator on a dispute.
+        ///
+        /// The `resolve_dispute` function performs the following operations:
+        /// - Checks if `dispute_id` is an open dispute. If not, it returns a `DisputeNotFound` error.
+        /// - Checks if the caller is the arbitrator. If not, it returns an `Unauthorized` error.
+        /// - Checks if a split ruling gives at most 10,000 basis points to the recipient. If not, it returns an `InvalidRuling` error.
+        /// - Keeps the recipient's share in escrow with a fresh claim window, and returns the rest to the pool like an expired escrow.
+        /// - Unfreezes the payout and emits a `DisputeResolved` event.
+        ///
+        /// Returns `Ok(())` if the ruling was enforced, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, dispute_id: u128, ruling: Ruling) -> Result<()> {
+            self.ensure_not_archived()?;
+            let mut dispute = match self.disputes.get(dispute_id) {
+                Some(open) if open.ruling.is_none() => open,
+                _ => return Err(Error::DisputeNotFound),
+            };
+            if Some(self.env().caller()) != self.arbitrator {
+                return Err(Error::Unauthorized);
+            }
+
+            let recipient = dispute.recipient;
+            let (amount, _) = self.escrow.get(recipient).unwrap_or((0, 0));
+            let kept = match ruling {
+                Ruling::Release => amount,
+                Ruling::Refund => 0,
+                Ruling::Split(bps) if bps <= 10_000 => amount.checked_mul(bps as u128).ok_or(Error::ArithmeticOverflow)? / 10_000,
+                Ruling::Split(_) => return Err(Error::InvalidRuling),
+            };
+            let returned = amount - kept;
+            if kept > 0 {
+                let expires_at = self.env().block_timestamp().saturating_add(self.config.claim_window);
+                self.escrow.insert(recipient, &(kept, expires_at));
+            } else {
+                self.escrow.remove(recipient);
+            }
+            self.escrowed_total = self.escrowed_total.checked_sub(returned).ok_or(Error::ArithmeticOverflow)?;
+            self.unallocated_sponsorship = self.unallocated_sponsorship.checked_add(returned).ok_or(Error::ArithmeticOverflow)?;
+            self.sponsorship_balance = self.sponsorship_balance.checked_add(returned).ok_or(Error::ArithmeticOverflow)?;
+
+            self.disputed_escrow.remove(recipient);
+            dispute.ruling = Some(ruling);
+            self.disputes.insert(dispute_id, &dispute);
+            self.env().emit_event(DisputeResolved {
+                recipient,
+                dispute_id,
+                ruling,
+                returned,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the dispute with the given ID, if any.
+        #[ink(message)]
+        pub fn get_dispute(&self, dispute_id: u128) -> Option<Dispute> {
+            self.disputes.get(dispute_id)
+        }
+
+        /// Returns the escrowed payout of `account` and the timestamp at which its claim window ends, if any.
+        #[ink(message)]
+        pub fn get_escrow(&self, account: AccountId) -> Option<(Balance, Timestamp)> {
+            self.escrow.get(account)
+        }
+
+        /// Allows a recipient to withdraw the payouts credited to them in a pull-delivery pool, or an enrollment refund that could not be transferred.
+        ///
+        /// The `withdraw_payout` function performs the following operations:
+        /// - Checks if the caller has a pending withdrawal. If not, it returns a `NothingToClaim` error.
+        /// - Attempts to transfer the whole pending amount to the caller. If the transfer fails, it returns a `TransferError` and the amount stays withdrawable.
+        /// - Emits a `Transfer` event.
+        ///
+        /// Returns `Ok(())` if the payout was withdrawn, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn withdraw_payout(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let amount = self.withdrawals.get(caller).ok_or(Error::NothingToClaim)?;
+
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::TransferError);
+            }
+            self.withdrawals.remove(caller);
+            self.escrowed_total = self.escrowed_total.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.saturating_sub(amount);
+
+            self.env().emit_event(Transfer {
+                from: Some(self.owner),
+                to: Some(caller),
+                value: amount,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Returns the amount `account` can withdraw with `withdraw_payout`.
+        #[ink(message)]
+        pub fn get_pending_withdrawal(&self, account: AccountId) -> Balance {
+            self.withdrawals.get(account).unwrap_or(0)
+        }
+
+        /// Co-signs a pending payout request of the owner.
+        ///
+        /// The `cosign_request` function performs the following operations:
+        /// - Checks if the caller holds the `Approver` role. If not, it returns an `Unauthorized` error.
+        /// - Checks if the caller is the owner. If so, it returns a `CoSignatureRequired` error.
+        /// - Checks if `request_id` is a pending request. If not, it returns a `NotContributor` error.
+        /// - Records the caller as the co-signer, which lets `approve_request` pay the owner out.
+        ///
+        /// Returns `Ok(())` if the request was co-signed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn cosign_request(&mut self, request_id: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            self.ensure_role(caller, Role::Approver)?;
+
+            if caller == self.owner {
+                return Err(Error::CoSignatureRequired);
+            }
+
+            match self.request_details.get(request_id) {
+                Some(info) if info.status == RequestStatus::Pending => {}
+                _ => return Err(Error::NotContributor),
+            }
+
+            self.cosigners.insert(request_id, &caller);
+            Ok(())
+        }
+
+        /// Retrieves the details of a payout request.
+        ///
+        /// Returns the `RequestInfo` of the request with the given ID, or `None` if no such request exists.
+        #[ink(message)]
+        pub fn get_request_details(&self, request_id: u128) -> Option<RequestInfo> {
+            self.request_details.get(request_id)
+        }
+
+        /// Removes a contributor from the pool and refunds their contribution.
+        ///
+        /// The `remove_contributor` function is called by the contract owner to handle a compromised or inactive member's slot. It performs the following operations:
+        /// - Checks if the caller is the contract owner. If not, it returns a `NotContractOwner` error.
+        /// - Checks if `account` is a contributor. If not, it returns a `NotContributor` error.
+        /// - Refunds the member's balance. If the transfer fails, it returns a `TransferError`.
+        /// - Removes every payout slot of the member from the contributors list, the `contributed`, `weights` and `address_to_amount_funded` mappings, the `balance` vector, and cancels any pending request.
+        /// - Emits a `MemberRemoved` event with the refunded amount.
+        ///
+        /// Returns `Ok(())` if the member was removed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn remove_contributor(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            if !self.queue_contains(account) {
+                return Err(Error::NotContributor);
+            }
+
+            let prepaid = self.get_prepaid(account);
+            let refund = self.ledger_balance(account).checked_add(prepaid).ok_or(Error::ArithmeticOverflow)?;
+            if refund > 0 && self.env().transfer(account, refund).is_err() {
+                return Err(Error::TransferError);
+            }
+
+            self.remove_member(account, refund, prepaid);
+
+            self.env().emit_event(MemberRemoved {
+                member: account,
+                refund,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Checks if the pool is stuck: it has members and is not completed, but no payout has opened a new round
+        /// for `RESCUE_TIMEOUT` past the contribution deadline and the grace period of the current round.
+        #[ink(message)]
+        pub fn is_stuck(&self) -> bool {
+            let stalled_since = self.get_contribution_deadline().saturating_add(self.config.grace_period);
+            self.contributors_count > 0
+                && self.status != PoolStatus::Completed
+                && self.env().block_timestamp() > stalled_since.saturating_add(RESCUE_TIMEOUT)
+        }
+
+        /// Allows a member to reclaim their net contribution from a stuck pool, without the owner.
+        ///
+        /// The `rescue_withdraw` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if the pool is stuck, as reported by `is_stuck`. If not, it returns a `NotStuck` error.
+        /// - Refunds the caller's contributions and prepaid installments, minus the payouts they received. If the transfer fails, it returns a `TransferError`.
+        /// - Removes the caller from the pool like `remove_contributor` and emits a `FundsRescued` event.
+        ///
+        /// Returns `Ok(())` if the funds were rescued, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn rescue_withdraw(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            if !self.is_stuck() {
+                return Err(Error::NotStuck);
+            }
+
+            let prepaid = self.get_prepaid(caller);
+            let refund = self.ledger_balance(caller).checked_add(prepaid).ok_or(Error::ArithmeticOverflow)?.saturating_sub(self.received.get(caller).unwrap_or(0));
+            if refund > 0 && self.env().transfer(caller, refund).is_err() {
+                return Err(Error::TransferError);
+            }
+            self.remove_member(caller, refund, prepaid.min(refund));
+
+            self.env().emit_event(FundsRescued {
+                member: caller,
+                amount: refund,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Allows the next eligible requester to decline the current payout and move to the back of the queue.
+        ///
+        /// The `defer_turn` function is called when the next eligible requester does not need the pot this round. It performs the following operations:
+        /// - Checks if the caller is the next eligible requester. If not, it returns a `NotNextContributor` error.
+        /// - Cancels any pending request of the caller.
+        /// - Moves the caller's payout slot to the back of the queue and excludes them from the need score selection this round, making the following member eligible.
+        /// - Emits a `TurnDeferred` event with the new next requester.
+        ///
+        /// Returns `Ok(())` if the turn was deferred, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn defer_turn(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+
+            if Some(caller) != self.get_next_requester() {
+                return Err(Error::NotNextContributor);
+            }
+
+            self.cancel_requests_of(caller);
+            self.defer_next_slot(caller);
+
+            self.env().emit_event(TurnDeferred {
+                member: caller,
+                next_requester: self.get_next_requester(),
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Skips the next requester if they neither requested nor deferred their payout in time.
+        ///
+        /// The `skip_inactive` function can be called by anyone so that a single absent member cannot stall the rotation. It performs the following operations:
+        /// - Checks if the pool is in the payment phase. If not, it returns a `NotPaymentPhase` error.
+        /// - Checks if a request timeout is configured and has passed since the turn started. If not, it returns a `TimeoutNotReached` error.
+        /// - Checks if the next requester has a pending request. If so, it returns a `RequestPending` error.
+        /// - Moves the requester's payout slot to the back of the queue and records a missed turn.
+        /// - Emits a `MemberSkipped` event.
+        ///
+        /// Returns `Ok(())` if the requester was skipped, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn skip_inactive(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            if !self.is_payment_phase() {
+                return Err(Error::NotPaymentPhase);
+            }
+
+            let timeout = self.config.request_timeout;
+            let now = self.env().block_timestamp();
+            if timeout == 0 || now < self.turn_started_at.saturating_add(timeout) {
+                return Err(Error::TimeoutNotReached);
+            }
+
+            let member = match self.get_next_requester() {
+                Some(member) => member,
+                None => return Err(Error::NotContributor),
+            };
+
+            if self.requests.iter().any(|(requester, _, _)| requester == &member) {
+                return Err(Error::RequestPending);
+            }
+
+            self.defer_next_slot(member);
+
+            let missed_turns = self.missed_turns_of(member).checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.missed_turns.insert(member, &missed_turns);
+
+            self.env().emit_event(MemberSkipped {
+                member,
+                missed_turns,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Emits the contribution reminders of the current round. Anyone can call this function, typically a keeper.
+        ///
+        /// The `tick` function performs the following operations:
+        /// - Checks if a contribution period is configured. If not, it returns a `RemindersDisabled` error.
+        /// - The first time it is called in a round, emits a `ContributionDue` event for every member who has not paid their installment yet.
+        /// - The first time it is called after the deadline and the grace period have passed, emits a `ContributionOverdue` event for every member who still has not paid.
+        ///
+        /// Each event is emitted at most once per member and round, so off-chain services can notify members without polling state.
+        ///
+        /// Returns `Ok(())` if the reminders are up to date, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn tick(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            if self.config.contribution_period == 0 {
+                return Err(Error::RemindersDisabled);
+            }
+
+            let round = self.current_round;
+            let deadline = self.get_contribution_deadline();
+            let overdue = self.env().block_timestamp() > deadline.saturating_add(self.config.grace_period);
+            let send_due = self.due_notified_round < round;
+            let send_overdue = overdue && self.overdue_notified_round < round;
+            if !send_due && !send_overdue {
+                return Ok(());
+            }
+
+            for (member, _) in &self.balance {
+                if self.has_contributed_this_round(*member) {
+                    continue;
+                }
+                if send_due {
+                    self.env().emit_event(ContributionDue {
+                        member: *member,
+                        deadline,
+                        pool_id: self.env().account_id(),
+                        cycle: self.contribution_cycle,
+                        round,
+                    });
+                }
+                if send_overdue {
+                    self.env().emit_event(ContributionOverdue {
+                        member: *member,
+                        deadline,
+                        pool_id: self.env().account_id(),
+                        cycle: self.contribution_cycle,
+                        round,
+                    });
+                }
+            }
+
+            self.due_notified_round = round;
+            if send_overdue {
+                self.overdue_notified_round = round;
+            }
+            Ok(())
+        }
+
+        /// Returns up to `limit` members with the most installments paid by the contribution deadline, highest score first.
+        ///
+        /// Without a contribution period every installment counts as on time. The ranking is kept up to date with each payment,
+        /// for the top `LEADERBOARD_SIZE` members.
+        #[ink(message)]
+        pub fn leaderboard(&self, limit: u32) -> Vec<(AccountId, Score)> {
+            self.leaderboard.iter().take(limit as usize).copied().collect()
+        }
+
+        /// Returns the timestamp by which members have to pay their installment for the current round.
+        ///
+        /// Includes the extension the members voted for the current round with `vote_extension`, if any.
+        #[ink(message)]
+        pub fn get_contribution_deadline(&self) -> Timestamp {
+            let extension = match self.extension {
+                Some(extension) if extension.executed && extension.round == self.current_round => extension.ms,
+                _ => 0,
+            };
+            self.round_opened_at
+                .saturating_add(self.config.contribution_period)
+                .saturating_add(extension)
+        }
+
+        /// Proposes to extend the contribution deadline of the current round by `ms` milliseconds.
+        ///
+        /// The `propose_extension` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if `ms` is between 1 and `MAX_DEADLINE_EXTENSION`. If not, it returns an `InvalidExtension` error.
+        /// - Checks if the current round already has a proposal. If so, it returns an `ExtensionActive` error.
+        /// - Stores the proposal, emits an `ExtensionProposed` event and counts the caller's vote.
+        ///
+        /// Returns the ID of the proposal, or an `Error`.
+
+        #[ink(message)]
+        pub fn propose_extension(&mut self, ms: Timestamp) -> Result<u128> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            if ms == 0 || ms > MAX_DEADLINE_EXTENSION {
+                return Err(Error::InvalidExtension);
+            }
+            if matches!(self.extension, Some(extension) if extension.round == self.current_round) {
+                return Err(Error::ExtensionActive);
+            }
+
+            let id = self.extension.map_or(Some(1), |previous| previous.id.checked_add(1)).ok_or(Error::ArithmeticOverflow)?;
+            self.extension = Some(DeadlineExtension {
+                id,
+                round: self.current_round,
+                ms,
+                votes: 0,
+                electorate: self.total_weight,
+                executed: false,
+            });
+            self.env().emit_event(ExtensionProposed {
+                proposer: caller,
+                id,
+                ms,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.vote_extension()?;
+            Ok(id)
+        }
+
+        /// Votes for the deadline extension proposal of the current round with the caller's membership tier.
+        ///
+        /// The `vote_extension` function performs the following operations:
+        /// - Checks if the caller is a member. If not, it returns a `NotContributor` error.
+        /// - Checks if the current round has a proposal that has not passed yet. If not, it returns an `ExtensionNotFound` error.
+        /// - Checks if the caller has already voted. If so, it returns an `AlreadyVoted` error.
+        /// - Adds the caller's membership tier to the votes. Once the votes are more than half of the electorate, the larger of the
+        ///   membership weight when the proposal was opened and the current one, extends the deadline and emits a `DeadlineExtended` event.
+        ///
+        /// Returns `Ok(())` if the vote was counted, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn vote_extension(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+            let mut proposal = match self.extension {
+                Some(open) if open.round == self.current_round && !open.executed => open,
+                _ => return Err(Error::ExtensionNotFound),
+            };
+            if self.extension_votes.contains((proposal.id, caller)) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            self.extension_votes.insert((proposal.id, caller), &true);
+            proposal.votes = proposal.votes.checked_add(self.weight_of(caller) as u128).ok_or(Error::ArithmeticOverflow)?;
+            let electorate = proposal.electorate.max(self.total_weight);
+            proposal.executed = electorate > 0 && proposal.votes > electorate / 2;
+            self.extension = Some(proposal);
+            if proposal.executed {
+                self.env().emit_event(DeadlineExtended {
+                    id: proposal.id,
+                    ms: proposal.ms,
+                    deadline: self.get_contribution_deadline(),
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            }
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Returns the latest deadline extension proposal, if any.
+        #[ink(message)]
+        pub fn get_extension(&self) -> Option<DeadlineExtension> {
+            self.extension
+        }
+
+        /// Returns how many times `account` was skipped for being unresponsive.
+        #[ink(message)]
+        pub fn missed_turns_of(&self, account: AccountId) -> u32 {
+            self.missed_turns.get(account).unwrap_or(0)
+        }
+
+        /// Allows a third party to add matching funds to the pot without becoming a member.
+        ///
+        /// The `sponsor_pool` function is called by NGOs or other sponsors that want to subsidize the pool. It performs the following operations:
+        /// - Checks if the transferred value is zero. If so, it returns a `LowAmount` error.
+        /// - With `SponsorDistribution::SplitEvenly`, adds the funds to the pool split evenly across the remaining payouts.
+        /// - With `SponsorDistribution::Round(round)`, checks that the round has not been paid out yet, otherwise it returns an `InvalidRound` error, and adds the funds to that round's payout.
+        /// - Emits a `Sponsored` event.
+        ///
+        /// Returns `Ok(())` if the sponsorship is accepted, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn sponsor_pool(&mut self, distribution: SponsorDistribution) -> Result<()> {
+            self.ensure_not_archived()?;
+            let sponsor = self.env().caller();
+            let amount = self.env().transferred_value();
+
+            if amount == 0 {
+                return Err(Error::LowAmount);
+            }
+
+            let round = self.add_sponsorship(distribution, amount)?;
+            self.sponsored_amount = self.sponsored_amount.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+
+            self.env().emit_event(Sponsored {
+                sponsor,
+                amount,
+                target_round: round,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the native funds the contract holds outside of its own accounting.
+        ///
+        /// Funds sent to the contract address with a plain balance transfer do not execute any message, so they are
+        /// not part of `total_supply` or the sponsorships. They show up here as the available balance above the accounted balance.
+        ///
+        /// The contract has no payable fallback message to credit such funds on arrival: a plain transfer runs no contract code,
+        /// and ink! only allows a `selector = _` message next to a single `selector = @` message, not next to the pool's messages.
+        /// An admin decides instead where the untracked funds go. `allocate_donations` adds them to the payouts as a sponsorship,
+        /// while `distribute_surplus` splits them among the current members. Both take the whole untracked balance.
+        #[ink(message)]
+        pub fn get_untracked_balance(&self) -> Balance {
+            self.get_available_balance().saturating_sub(self.held_balance)
+        }
+
+        /// Allocates the untracked native funds of the contract to the payouts as a donation.
+        ///
+        /// The `allocate_donations` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if there are untracked funds. If not, it returns a `NoUntrackedBalance` error.
+        /// - Adds them to the payouts following `distribution`, like a sponsorship. A past round returns an `InvalidRound` error.
+        /// - Emits a `DonationsAllocated` event.
+        ///
+        /// Returns `Ok(())` if the funds were allocated, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn allocate_donations(&mut self, distribution: SponsorDistribution) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            let amount = self.get_untracked_balance();
+            if amount == 0 {
+                return Err(Error::NoUntrackedBalance);
+            }
+
+            let round = self.add_sponsorship(distribution, amount)?;
+            self.donations = self.donations.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+
+            self.env().emit_event(DonationsAllocated {
+                amount,
+                target_round: round,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Distributes the untracked native funds of the contract among the current members.
+        ///
+        /// The `distribute_surplus` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if there are untracked funds. If not, it returns a `NoUntrackedBalance` error.
+        /// - Checks if the pool has members. If not, it returns a `NoMembers` error.
+        /// - Credits each current member a share of the funds pro rata to their weight, or to their time-weighted credit if the pool
+        ///   enables `time_weighted_credit`, claimable with `claim_surplus`.
+        ///   The rounding remainder stays untracked for the next distribution.
+        ///   Unlike `allocate_donations`, the funds do not grow the payouts and are not counted in the donations.
+        /// - Emits a `SurplusDistributed` event.
+        ///
+        /// Returns `Ok(())` if the surplus was distributed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn distribute_surplus(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            let amount = self.get_untracked_balance();
+            if amount == 0 {
+                return Err(Error::NoUntrackedBalance);
+            }
+            if self.balance.is_empty() || self.total_weight == 0 {
+                return Err(Error::NoMembers);
+            }
+
+            let total_credit = self.get_total_time_credit();
+            let by_credit = self.config.time_weighted_credit && total_credit > 0;
+            let mut distributed: Balance = 0;
+            for (member, _) in self.balance.clone() {
+                let share = if by_credit {
+                    Self::pro_rata(amount, self.get_time_credit(member), total_credit)
+                } else {
+                    amount.checked_mul(self.weight_of(member) as u128).ok_or(Error::ArithmeticOverflow)? / self.total_weight
+                };
+                if share == 0 {
+                    continue;
+                }
+                let claim = self.get_surplus_claim(member).checked_add(share).ok_or(Error::ArithmeticOverflow)?;
+                self.surplus_claims.insert(member, &claim);
+                distributed = distributed.checked_add(share).ok_or(Error::ArithmeticOverflow)?;
+            }
+            self.unclaimed_surplus = self.unclaimed_surplus.checked_add(distributed).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.checked_add(distributed).ok_or(Error::ArithmeticOverflow)?;
+
+            self.env().emit_event(SurplusDistributed {
+                amount: distributed,
+                members: self.balance.len() as u32,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the time-weighted contribution credit of `account`, the sum of each amount they contributed times the milliseconds it has been locked.
+        #[ink(message)]
+        pub fn get_time_credit(&self, account: AccountId) -> u128 {
+            self.time_credits.get(account).unwrap_or_default().accrued(self.env().block_timestamp())
+        }
+
+        /// Returns the time-weighted contribution credit of all members together.
+        #[ink(message)]
+        pub fn get_total_time_credit(&self) -> u128 {
+            self.total_time_credit.accrued(self.env().block_timestamp())
+        }
+
+        /// Allows a member to claim their share of the distributed surplus.
+        ///
+        /// The `claim_surplus` function performs the following operations:
+        /// - Checks if the caller has an unclaimed share. If not, it returns a `NothingToClaim` error.
+        /// - Attempts to transfer the share to the caller. If the transfer fails, it returns a `TransferError` and the share stays claimable.
+        /// - Emits a `SurplusClaimed` event.
+        ///
+        /// Returns `Ok(())` if the share was claimed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn claim_surplus(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let caller = self.env().caller();
+            let amount = self.surplus_claims.get(caller).ok_or(Error::NothingToClaim)?;
+
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::TransferError);
+            }
+            self.surplus_claims.remove(caller);
+            self.unclaimed_surplus = self.unclaimed_surplus.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.saturating_sub(amount);
+
+            self.env().emit_event(SurplusClaimed {
+                account: caller,
+                amount,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.record_activity(caller);
+            Ok(())
+        }
+
+        /// Returns the distributed surplus `account` can claim.
+        #[ink(message)]
+        pub fn get_surplus_claim(&self, account: AccountId) -> Balance {
+            self.surplus_claims.get(account).unwrap_or(0)
+        }
+
+        /// Returns the total distributed surplus the members have not claimed yet.
+        #[ink(message)]
+        pub fn get_unclaimed_surplus(&self) -> Balance {
+            self.unclaimed_surplus
+        }
+
+        /// Returns the total amount of untracked funds allocated to the payouts as donations.
+        #[ink(message)]
+        pub fn get_donations(&self) -> Balance {
+            self.donations
+        }
+
+        /// Returns the total amount of matching funds received from sponsors.
+        #[ink(message)]
+        pub fn get_sponsored_amount(&self) -> Balance {
+            self.sponsored_amount
+        }
+
+        /// Returns the matching funds added to the payout of a specific round.
+        #[ink(message)]
+        pub fn get_round_sponsorship(&self, round: u128) -> Balance {
+            self.round_sponsorship.get(round).unwrap_or(0)
+        }
+        
+        /// This function returns the AccountId of the next eligible requester.
+        /// It does this by picking the payout slot chosen by the configured `PayoutOrder`:
+        /// the first contributor in the queue, or the member with the highest need score.
+        /// If there are no contributors in the queue, it returns `None`.
+        
+        #[ink(message)]
+        pub fn get_next_requester(&self) -> Option<AccountId> {
+            // Look up the slot of the next requester and return the AccountId stored in it.
+            self.next_requester_slot().and_then(|(index, _)| self.queue_get(index))
+        }
+
+        /// Returns the next eligible requester together with their need score, for auditability.
+        ///
+        /// The need score is `rounds since last payout * NEED_SCORE_SCALE / (payouts received + 1)`,
+        /// where members who never received a payout count the rounds since the start of the pool.
+        #[ink(message)]
+        pub fn get_next_requester_score(&self) -> Option<(AccountId, u128)> {
+            self.next_requester_slot().and_then(|(index, score)| self.queue_get(index).map(|account_id| (account_id, score)))
+        }
+        
+        /// Retrieves the number of completed payouts.
+        ///
+        /// The `get_completed_payouts` function is called to get the count of completed payouts from the contract.
+        ///
+        /// Returns the number of completed payouts as a `u128`.
+
+       #[ink(message)]
+        pub fn get_completed_payouts(&self) -> u128 {
+            self.completed_payouts
+        }
+        
+        /// Retrieves the payout history.
+        ///
+        /// The `get_payout_history` function is called to get the history of payouts from the contract. Each payout is represented as a tuple containing the account ID of the recipient and the amount of the payout.
+        ///
+        /// Returns a vector of tuples, where each tuple contains an account ID and the corresponding payout amount.
+
+        #[ink(message)]
+        pub fn get_payout_history(&self) -> Vec<(AccountId, Balance)> {
+            self.payout_history.clone()
+        }
+
+        /// Retrieves the payouts of the current cycle incrementally, without copying the whole history.
+        ///
+        /// The `get_payout_history_from` function returns up to `limit` payouts starting at position `cursor`, in the order they were paid,
+        /// and the cursor to pass on the next call. Payouts are only appended during a cycle, so passing the returned cursor later
+        /// yields exactly the payouts made in the meantime.
+        #[ink(message)]
+        pub fn get_payout_history_from(&self, cursor: u32, limit: u32) -> (Vec<(AccountId, Balance)>, u32) {
+            let payouts: Vec<(AccountId, Balance)> = self
+                .payout_history
+                .iter()
+                .skip(cursor as usize)
+                .take(limit as usize)
+                .copied()
+                .collect();
+            let next_cursor = cursor.saturating_add(payouts.len() as u32);
+            (payouts, next_cursor)
+        }
+    
+        
+        /// Starts the next contribution cycle.
+        ///
+        /// The `next_contribution_cycle` function is called to start a new contribution cycle. It performs the following operations:
+        /// - Checks if all contributors have been paid. If not, it returns `CycleAdvanceOutcome::NotAllPaid` without changing anything.
+        /// - Checks if the length of the payout history is equal to the number of payout slots. If not, it returns `CycleAdvanceOutcome::HistoryMismatch`.
+        /// - Otherwise, it credits the referral bonuses of the members who completed the cycle, ends their memberships by clearing their funded amounts,
+        ///   round payments, tiers and ledger entries, and resets the `total_supply`, the `payout_history` vector, the `contributors_count` and the `total_weight`.
+        ///   It then increments the `contribution_cycle` and resets the `completed_payouts` count. Members who did not enroll in the new cycle can join it again.
+        /// - Cancels the open impeachment proposal, if any, and emits an `ImpeachmentCancelled` event.
+        /// - Applies the settings change queued by `update_config` if it is still valid, or drops it and emits a `ConfigDiscarded` event.
+        /// - Admits the accounts enrolled with `enroll_next_cycle` as the first members of the new cycle.
+        /// - Emits a `CycleAdvanced` event and returns `CycleAdvanceOutcome::Advanced`.
+        ///
+        /// Returns a `PoolArchived` error if the pool is archived, or an `ArithmeticOverflow` error if a counter would overflow.
+
+        #[ink(message)]
+        pub fn next_contribution_cycle(&mut self) -> Result<CycleAdvanceOutcome> {
+            self.ensure_not_archived()?;
+            if !self.all_paid() {
+                return Ok(CycleAdvanceOutcome::NotAllPaid);
+            }
+            if self.payout_history.len() as u128 != self.total_weight {
+                return Ok(CycleAdvanceOutcome::HistoryMismatch);
+            }
+
+            let next_cycle = self.contribution_cycle.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.emit_cycle_stats();
+            self.reward_referrers()?;
+            self.clear_cycle_ledger();
+            self.payout_history = Vec::default();
+            self.contributors_count = 0;
+            self.total_weight = 0;
+            self.contribution_cycle = next_cycle;
+            self.completed_payouts = 0;
+            self.cycle_started_at = self.env().block_timestamp();
+            self.cycle_defaults = 0;
+            self.cycle_first_request.insert(self.contribution_cycle, &self.next_request_id);
+            self.enrollment_order = Vec::default();
+            self.cancel_impeachment();
+            if let Some(patch) = self.pending_config.take() {
+                match self.merge_config(patch.clone()) {
+                    Ok(new) => self.set_config(new),
+                    Err(_) => self.env().emit_event(ConfigDiscarded {
+                        patch,
+                        pool_id: self.env().account_id(),
+                        cycle: self.contribution_cycle,
+                        round: self.current_round,
+                    }),
+                }
+            }
+            self.admit_enrollments()?;
+            self.notify_registry_stats();
+
+            self.env().emit_event(CycleAdvanced {
+                members: self.contributors_count,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(CycleAdvanceOutcome::Advanced)
+        }
+        
+        /// Enrolls the caller in the next cycle while the payouts of the current cycle are underway.
+        ///
+        /// The `enroll_next_cycle` function performs the following operations:
+        /// - Checks if the pool is active and in the payment phase. If not, it returns a `PoolNotActive` or `NotPaymentPhase` error.
+        /// - Checks if the caller is already enrolled. If so, it returns an `AlreadyMember` error with the current round and the enrolled amount.
+        /// - Checks if the next cycle is full. If so, it returns a `PoolFull` error.
+        /// - Checks if the transferred value would raise the funds held above the TVL cap. If so, it returns a `CapExceeded` error.
+        /// - Checks the same admission gates as `contribute`, such as the minimum amount, the membership gate, the owner policy,
+        ///   the account age, personhood and attestation. If one fails, it returns the same error as `contribute`.
+        /// - Holds the transferred value as the caller's first installment and emits a `NextCycleEnrolled` event.
+        ///
+        /// When the current cycle ends, enrolled accounts join the new cycle with a 1x membership tier, in the order they enrolled.
+        /// The admission gates are checked again then, and accounts that no longer pass them are refunded instead.
+        /// Returns `Ok(())` if the caller was enrolled, or an `Error` if not.
+
+        #[ink(message, payable)]
+        pub fn enroll_next_cycle(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_active()?;
+            let caller = self.env().caller();
+            let value = self.env().transferred_value();
+
+            if !self.is_payment_phase() {
+                return Err(Error::NotPaymentPhase);
+            }
+
+            if let Some((_, amount)) = self.next_cycle_enrollments.iter().find(|(member, _)| member == &caller) {
+                return Err(Error::AlreadyMember {
+                    round: self.current_round,
+                    amount: *amount,
+                });
+            }
+
+            if self.next_cycle_enrollments.len() as u128 >= self.config.max_contributors {
+                return Err(Error::PoolFull);
+            }
+
+            self.check_tvl_cap(value)?;
+            self.validate_admission(caller, Some(value), 1)?;
+
+            self.next_cycle_enrollments.push((caller, value));
+            self.held_balance = self.held_balance.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            self.prepaid_total = self.prepaid_total.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            self.env().emit_event(NextCycleEnrolled {
+                member: caller,
+                amount: value,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the accounts enrolled in the next cycle and their first installments.
+        #[ink(message)]
+        pub fn get_next_cycle_enrollments(&self) -> Vec<(AccountId, Balance)> {
+            self.next_cycle_enrollments.clone()
+        }
+
+        /// Checks if all contributors have been paid.
+        ///
+        /// The `all_paid` function is called to check if all contributors have been paid. It iterates over the list of contributors and checks the `paid` status for each contributor in the `address_to_amount_funded` mapping.
+        ///
+        /// Returns `true` if all contributors have been paid, or `false` if at least one contributor has not been paid.
+
+        #[ink(message)]
+        pub fn all_paid(&self) -> bool {
+            for key in self.queue_accounts() {
+                let (_, paid) = self.address_to_amount_funded.get(key).unwrap_or((0, false));
+                if !paid {
+                    return false;
+                }
+            }
+            true
+        }
+
+        /// Returns the state a dapp needs to render the pool in a single call.
+        ///
+        /// The phase is the one `request_token` checks.
+        #[ink(message)]
+        pub fn get_pool_snapshot(&self) -> PoolOverview {
+            let phase = if self.is_payment_phase() {
+                PoolPhase::Payment
+            } else {
+                PoolPhase::Contribution
+            };
+            let pending_request = self
+                .requests
+                .first()
+                .and_then(|(_, _, request_id)| self.request_details.get(request_id));
+
+            PoolOverview {
+                total_supply: self.total_supply,
+                phase,
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+                members: self.contributors_count,
+                next_requester: self.get_next_requester(),
+                pending_request,
+                config: self.config.clone(),
+            }
+        }
+
+        /// Deletes the history of the contribution cycles before `before_cycle` to reclaim storage deposits.
+        ///
+        /// The `prune_history` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if `before_cycle` leaves the current cycle untouched. If not, it returns an `InvalidCycle` error.
+        /// - Removes the details of the requests made in the pruned cycles.
+        /// - Removes the receipts of the pruned cycles of every current member.
+        /// - Emits a `HistoryPruned` event with the number of cycles and storage entries removed.
+        ///
+        /// Lifetime counters such as `get_pool_stats` are kept. Returns `Ok(())` if the history was pruned, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn prune_history(&mut self, before_cycle: u128) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            if before_cycle > self.contribution_cycle {
+                return Err(Error::InvalidCycle);
+            }
+
+            let mut entries_removed: u32 = 0;
+            let first_request = self.cycle_first_request.get(self.pruned_before_cycle).unwrap_or(self.next_request_id);
+            let last_request = self.cycle_first_request.get(before_cycle).unwrap_or(first_request);
+            for request_id in first_request..last_request {
+                if self.request_details.contains(request_id) {
+                    self.request_details.remove(request_id);
+                    entries_removed += 1;
+                }
+            }
+
+            for (member, _) in self.balance.clone() {
+                let count = self.receipt_count.get(member).unwrap_or(0);
+                let mut nonce = self.first_receipt.get(member).unwrap_or(0);
+                while nonce < count {
+                    match self.receipts.get((member, nonce)) {
+                        Some(receipt) if receipt.cycle < before_cycle => {
+                            self.receipts.remove((member, nonce));
+                            entries_removed += 1;
+                        }
+                        _ => break,
+                    }
+                    nonce += 1;
+                }
+                self.first_receipt.insert(member, &nonce);
+            }
+
+            let cycles_removed = before_cycle.saturating_sub(self.pruned_before_cycle);
+            for cycle in self.pruned_before_cycle..before_cycle {
+                self.cycle_first_request.remove(cycle);
+            }
+            self.pruned_before_cycle = self.pruned_before_cycle.max(before_cycle);
+
+            self.env().emit_event(HistoryPruned {
+                cycles_removed,
+                entries_removed,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Moves the pool to `status`.
+        ///
+        /// The `set_status` function performs the following operations:
+        /// - Checks if the transition is allowed. Admins can activate a draft and archive a completed pool, and pausers can suspend
+        ///   and resume an active pool. Pools complete automatically once `refund_members` has run. Otherwise it returns an `InvalidStatusTransition` error.
+        /// - Checks if the caller holds the role the transition needs. If not, it returns a `NotContractOwner` error.
+        /// - Emits a `StatusChanged` event.
+        ///
+        /// Returns `Ok(())` if the status changed, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn set_status(&mut self, status: PoolStatus) -> Result<()> {
+            let role = match (self.status, status) {
+                (PoolStatus::Draft, PoolStatus::Active) | (PoolStatus::Completed, PoolStatus::Archived) => Role::Admin,
+                (PoolStatus::Active, PoolStatus::Suspended) | (PoolStatus::Suspended, PoolStatus::Active) => Role::Pauser,
+                _ => return Err(Error::InvalidStatusTransition),
+            };
+            self.ensure_role(self.env().caller(), role)?;
+
+            self.change_status(status);
+            Ok(())
+        }
+
+        /// Returns the lifecycle status of the pool.
+        #[ink(message)]
+        pub fn get_status(&self) -> PoolStatus {
+            self.status
+        }
+
+        /// Starts winding down the pool.
+        ///
+        /// The `wind_down` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if the pool is already winding down. If so, it returns a `WindingDown` error.
+        /// - Stops new contributions and installments, and allows `refund_members` once `WIND_DOWN_DELAY` has passed.
+        /// - Emits a `WindDownStarted` event.
+        ///
+        /// Returns `Ok(())` if the wind-down started, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn wind_down(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            if self.wind_down_at.is_some() {
+                return Err(Error::WindingDown);
+            }
+
+            let refunds_at = self.env().block_timestamp().saturating_add(WIND_DOWN_DELAY);
+            self.wind_down_at = Some(refunds_at);
+            self.env().emit_event(WindDownStarted {
+                refunds_at,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Refunds every member their net position once the wind-down timelock has passed.
+        ///
+        /// The `refund_members` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if the pool is winding down, the timelock has passed and the members were not refunded yet. If not, it returns a `NotWoundDown` error.
+        /// - Transfers to every member the amount they contributed minus the amount they received, paid from the principal. If the principal
+        ///   does not cover every refund, each refund is reduced in proportion.
+        /// - Returns to every member their prepayment and locked-up payout, and to every account enrolled in the next cycle its first installment.
+        /// - Emits a `MembersRefunded` event.
+        ///
+        /// Funds owed to others, such as escrowed payouts, pending withdrawals, unclaimed surplus and referral rewards, stay claimable.
+        /// Returns `Ok(())` if the members were refunded, or an `Error` if not.
+
+        #[ink(message)]
+        pub fn refund_members(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            match self.wind_down_at {
+                Some(refunds_at) if !self.wound_down && self.env().block_timestamp() >= refunds_at => {}
+                _ => return Err(Error::NotWoundDown),
+            }
+
+            let positions: Vec<(AccountId, Balance)> = self
+                .balance
+                .iter()
+                .map(|(member, contributed)| (*member, contributed.saturating_sub(self.received.get(member).unwrap_or(0))))
+                .filter(|(_, net)| *net > 0)
+                .collect();
+            let owed = positions
+                .iter()
+                .try_fold(0 as Balance, |owed, (_, net)| owed.checked_add(*net))
+                .ok_or(Error::ArithmeticOverflow)?;
+            let available = self.principal.min(owed);
+
+            let mut refunds: Vec<(AccountId, Balance)> = positions
+                .iter()
+                .map(|(member, net)| (*member, if available == owed { *net } else { Self::pro_rata(*net, available, owed) }))
+                .collect();
+            let dust = available - refunds.iter().map(|(_, refund)| refund).sum::<Balance>();
+            if dust > 0 {
+                match (self.config.dust_policy, refunds.last_mut()) {
+                    (DustPolicy::FinalPayout, Some(last)) => last.1 += dust,
+                    _ => {
+                        self.principal -= dust;
+                        self.collect_dust(dust)?;
+                    }
+                }
+            }
+
+            let mut refunded: Balance = 0;
+            for (member, refund) in &refunds {
+                if *refund > 0 && self.env().transfer(*member, *refund).is_err() {
+                    return Err(Error::TransferError);
+                }
+                refunded = refunded.checked_add(*refund).ok_or(Error::ArithmeticOverflow)?;
+            }
+            self.principal = self.principal.saturating_sub(refunded);
+
+            // Prepayments, lockups and enrollments belong to a single account each, so they are returned in full
+            let members: Vec<AccountId> = self.balance.iter().map(|(member, _)| *member).collect();
+            for member in members {
+                let prepaid = self.get_prepaid(member);
+                let lockup = self.get_lockup(member);
+                let amount = prepaid.checked_add(lockup).ok_or(Error::ArithmeticOverflow)?;
+                if amount == 0 {
+                    continue;
+                }
+                if self.env().transfer(member, amount).is_err() {
+                    return Err(Error::TransferError);
+                }
+                self.prepaid.remove(member);
+                self.lockups.remove(member);
+                self.prepaid_total = self.prepaid_total.saturating_sub(prepaid);
+                self.escrowed_total = self.escrowed_total.saturating_sub(lockup);
+                refunded = refunded.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            }
+            for (account, amount) in core::mem::take(&mut self.next_cycle_enrollments) {
+                if self.env().transfer(account, amount).is_err() {
+                    return Err(Error::TransferError);
+                }
+                self.prepaid_total = self.prepaid_total.saturating_sub(amount);
+                refunded = refunded.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            }
+
+            self.wound_down = true;
+            self.change_status(PoolStatus::Completed);
+            self.held_balance = self.held_balance.saturating_sub(refunded);
+            self.env().emit_event(MembersRefunded {
+                members: positions.len() as u32,
+                amount: refunded,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            self.notify_registry_stats();
+            Ok(())
+        }
+
+        /// Removes the contract once the members were refunded and every claim was paid out.
+        ///
+        /// The `terminate` function performs the following operations:
+        /// - Checks if the caller holds the `Admin` role. If not, it returns a `NotContractOwner` error.
+        /// - Checks if `refund_members` has run. If not, it returns a `NotWoundDown` error.
+        /// - Checks if escrowed payouts, pending withdrawals, unclaimed surplus, referral rewards or prepayments are still owed.
+        ///   If so, it returns a `ClaimsOutstanding` error.
+        /// - Shares the remaining balance, such as unspent sponsorships, fees, insurance and dust, evenly among the members
+        ///   and removes the contract. The last member also receives the rounding remainder and the existential deposit.
+        ///
+        /// Without members, the remaining balance goes to the owner.
+
+        #[ink(message)]
+        pub fn terminate(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+
+            if !self.wound_down {
+                return Err(Error::NotWoundDown);
+            }
+            if self.escrowed_total > 0
+                || self.unclaimed_surplus > 0
+                || self.rewards_owed > 0
+                || self.prepaid_total > 0
+                || !self.next_cycle_enrollments.is_empty()
+            {
+                return Err(Error::ClaimsOutstanding);
+            }
+
+            let members: Vec<AccountId> = self.balance.iter().map(|(member, _)| *member).collect();
+            let (last, others) = match members.split_last() {
+                Some(split) => split,
+                None => self.env().terminate_contract(self.owner),
+            };
+            let share = self.get_available_balance() / members.len() as u128;
+            for member in others {
+                if share > 0 && self.env().transfer(*member, share).is_err() {
+                    return Err(Error::TransferError);
+                }
+            }
+            self.env().terminate_contract(*last)
+        }
+
+        /// Returns the rounding remainders collected under the `Accumulate` dust policy.
+        #[ink(message)]
+        pub fn dust_balance(&self) -> Balance {
+            self.dust
+        }
+
+        /// Adds the transferred value to the insurance reserve. Anyone can fund the reserve.
+        #[ink(message, payable)]
+        pub fn fund_insurance(&mut self) -> Result<()> {
+            self.ensure_not_archived()?;
+            let value = self.env().transferred_value();
+            if value == 0 {
+                return Err(Error::LowAmount);
+            }
+
+            self.insurance = self.insurance.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Returns the insurance reserve available to cover defaulted installments.
+        #[ink(message)]
+        pub fn insurance_balance(&self) -> Balance {
+            self.insurance
+        }
+
+        /// Returns the total of the installments the insurance reserve has covered for `account`.
+        #[ink(message)]
+        pub fn covered_defaults_of(&self, account: AccountId) -> Balance {
+            self.covered_defaults.get(account).unwrap_or(0)
+        }
+
+        /// Returns the lifetime statistics of the pool.
+        ///
+        /// A pool deployed by another pool also reports these figures to it, which sums them up in `global_stats`.
+        #[ink(message)]
+        pub fn get_pool_stats(&self) -> PoolStats {
+            PoolStats {
+                total_value_locked: self.held_balance,
+                total_payouts: self.total_payouts,
+                total_paid_out: self.total_paid_out,
+                members: self.contributors_count,
+                active: self.contributors_count > 0,
+            }
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn get_total_supply(&self) -> Balance {
+          //  let balance: u128 =  self.env().balance();
+            let balance: u128 =  self.total_supply;
+            balance
+        }
+
+        /// Returns the native balance the contract can pay out.
+        ///
+        /// The storage deposits of the contract are held outside of `self.env().balance()`, so the only reservation
+        /// left to subtract is the existential deposit that keeps the contract account alive.
+        #[ink(message)]
+        pub fn get_available_balance(&self) -> Balance {
+            self.env().balance().saturating_sub(self.env().minimum_balance())
+        }
+
+        /// Retrieves the total number of contributors.
+        ///
+        /// The `total_contributors` function is called to get the total count of contributors from the contract.
+        ///
+        /// Returns the total number of contributors as a `u128`.
+         #[ink(message)]
+         pub fn total_contributors(&self) -> u128 {
+             self.contributors_count
+         }
+
+        /// Retrieves the balance of a specific account.
+        ///
+        /// The `balance_of` function is called to get the balance of a specific account from the contract. It iterates over the `balance` vector and returns the balance for the given account ID.
+        ///
+        /// Returns the balance of the given account as a `Balance`. If the account does not exist in the `balance` vector, it returns 0.
+        /// In private mode, it also returns 0 unless the caller is the account itself or the owner.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            if !self.can_view(owner) {
+                return 0;
+            }
+            self.ledger_balance(owner)
+        }
+
+        /// Retrieves a page of the contribution receipts of `account`.
+        ///
+        /// The `get_receipts` function returns up to `limit` receipts starting at nonce `offset`, in the order the contributions were made.
+        /// In private mode, it returns an empty list unless the caller is the account itself or the owner.
+        #[ink(message)]
+        pub fn get_receipts(&self, account: AccountId, offset: u32, limit: u32) -> Vec<Receipt> {
+            if !self.can_view(account) {
+                return Vec::new();
+            }
+
+            let count = self.receipt_count.get(account).unwrap_or(0);
+            let end = offset.saturating_add(limit).min(count);
+            (offset..end)
+                .filter_map(|nonce| self.receipts.get((account, nonce)))
+                .collect()
+        }
+
+        /// Retrieves the contribution receipts of `account` incrementally.
+        ///
+        /// The `get_contribution_history` function returns up to `limit` receipts starting at nonce `cursor`, in the order the contributions were made,
+        /// and the cursor to pass on the next call. Pruned receipts are skipped. Receipts are only appended, so passing the returned cursor later
+        /// yields exactly the contributions made in the meantime.
+        /// In private mode, it returns no receipts and the unchanged cursor unless the caller is the account itself or the owner.
+        #[ink(message)]
+        pub fn get_contribution_history(&self, account: AccountId, cursor: u32, limit: u32) -> (Vec<Receipt>, u32) {
+            if !self.can_view(account) {
+                return (Vec::new(), cursor);
+            }
+
+            let count = self.receipt_count.get(account).unwrap_or(0);
+            let start = cursor.max(self.first_receipt.get(account).unwrap_or(0)).min(count);
+            let end = start.saturating_add(limit).min(count);
+            let receipts = (start..end)
+                .filter_map(|nonce| self.receipts.get((account, nonce)))
+                .collect();
+            (receipts, end)
+        }
+
+        /// Retrieves a page of the payout requests awaiting approval.
+        ///
+        /// The `get_pending_requests` function returns up to `limit` requests starting at position `offset` of the queue, oldest first,
+        /// as `(request_id, requester, amount, created_at)` tuples.
+        #[ink(message)]
+        pub fn get_pending_requests(&self, offset: u32, limit: u32) -> Vec<(u128, AccountId, Balance, Timestamp)> {
+            self.requests
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .map(|(requester, amount, request_id)| {
+                    let created_at = self.request_details.get(request_id).map_or(0, |details| details.created_at);
+                    (*request_id, *requester, *amount, created_at)
+                })
+                .collect()
+        }
+
+        /// Enables or disables private mode.
+        ///
+        /// In private mode, individual contribution amounts are only visible to the account itself and the owner.
+        /// Only the owner can call this function, otherwise it returns a `NotContractOwner` error.
+        #[ink(message)]
+        pub fn set_private_mode(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_not_archived()?;
+            self.ensure_role(self.env().caller(), Role::Admin)?;
+            self.config.private_mode = enabled;
+            Ok(())
+        }
+
+        /// Returns whether private mode is enabled.
+        #[ink(message)]
+        pub fn is_private_mode(&self) -> bool {
+            self.config.private_mode
+        }
+
+        /// Returns the balance recorded for `owner` in the `balance` vector, regardless of private mode.
+        fn ledger_balance(&self, owner: AccountId) -> Balance {
+            for (account_id, balance) in &self.balance {
+                if account_id == &owner {
+                    return *balance;
+                }
+            }
+            0
+        }
+
+        /// Returns the native funds the contract accounts for, split by purpose.
+        ///
+        /// `total_supply` only counts contributions, so fee, escrow and sponsorship features each keep their own sub-balance.
+        /// Their sum is checked against the accounted balance by `verify_invariants`.
+        #[ink(message)]
+        pub fn get_balances_breakdown(&self) -> BalancesBreakdown {
+            BalancesBreakdown {
+                principal: self.principal,
+                prepaid: self.prepaid_total,
+                sponsorship: self.sponsorship_balance,
+                fees: self.fee_reserve.saturating_add(self.rewards_owed),
+                escrow: self.escrowed_total,
+                insurance: self.insurance,
+                dust: self.dust,
+                surplus: self.unclaimed_surplus,
+            }
+        }
+
+        /// Checks the internal consistency of the contract storage.
+        ///
+        /// The `verify_invariants` function is a read-only health check for monitoring. It verifies that:
+        /// - The sum of the `balance` vector equals `total_supply`.
+        /// - The number of members in the `balance` vector equals `contributors_count`.
+        /// - No account appears more than once in the `balance` vector.
+        /// - The contract's available balance is at least the native balance it accounts for, `held_balance`.
+        /// - The sub-balances of `get_balances_breakdown` add up to the accounted native balance.
+        ///
+        /// Returns a vector of every `InvariantViolation` found, which is empty when the storage is consistent.
+        #[ink(message)]
+        pub fn verify_invariants(&self) -> Vec<InvariantViolation> {
+            let mut violations = Vec::new();
+
+            let balances_sum: Balance = self.balance.iter().map(|(_, balance)| *balance).sum();
+            if balances_sum != self.total_supply {
+                violations.push(InvariantViolation::TotalSupplyMismatch {
+                    balances_sum,
+                    total_supply: self.total_supply,
+                });
+            }
+
+            let mut members: Vec<AccountId> = Vec::new();
+            for (account_id, _) in &self.balance {
+                if members.contains(account_id) {
+                    violations.push(InvariantViolation::DuplicateBalanceEntry(*account_id));
+                } else {
+                    members.push(*account_id);
+                }
+            }
+
+            if members.len() as u128 != self.contributors_count {
+                violations.push(InvariantViolation::ContributorsCountMismatch {
+                    members: members.len() as u128,
+                    contributors_count: self.contributors_count,
+                });
+            }
+
+            let contract_balance = self.get_available_balance();
+            if contract_balance < self.held_balance {
+                violations.push(InvariantViolation::ContractUnderfunded {
+                    contract_balance,
+                    held_balance: self.held_balance,
+                });
+            }
+
+            let breakdown_total = self.get_balances_breakdown().total();
+            if breakdown_total != self.held_balance {
+                violations.push(InvariantViolation::BreakdownMismatch {
+                    breakdown_total,
+                    held_balance: self.held_balance,
+                });
+            }
+            violations
+        }
+
+        /// Sets the final `status` of a request and records when it was decided.
+        fn close_request(&mut self, request_id: u128, status: RequestStatus) {
+            if let Some(mut info) = self.request_details.get(request_id) {
+                info.status = status;
+                info.decided_at = Some(self.env().block_timestamp());
+                self.request_details.insert(request_id, &info);
+            }
+        }
+
+        /// Restarts the request timeout of the next requester.
+        fn start_turn(&mut self) {
+            self.turn_started_at = self.env().block_timestamp();
+        }
+
+        /// Cancels and drops every pending request of `account`.
+        fn cancel_requests_of(&mut self, account: AccountId) {
+            for (_, _, request_id) in self.requests.clone().into_iter().filter(|(requester, _, _)| requester == &account) {
+                self.close_request(request_id, RequestStatus::Cancelled);
+            }
+            self.requests.retain(|(requester, _, _)| requester != &account);
+        }
+
+        /// Returns whether the caller may see the amounts contributed by `account` under the current privacy settings.
+        fn can_view(&self, account: AccountId) -> bool {
+            if !self.config.private_mode {
+                return true;
+            }
+            let caller = self.env().caller();
+            caller == account || self.has_role(Role::Admin, caller)
+        }
+
+        /// Returns an error unless `account` holds `role`.
+        ///
+        /// A missing `Admin` role is reported as `NotContractOwner`, any other missing role as `Unauthorized`.
+        fn ensure_role(&self, account: AccountId, role: Role) -> Result<()> {
+            if self.has_role(role, account) {
+                return Ok(());
+            }
+            match role {
+                Role::Admin => Err(Error::NotContractOwner),
+                _ => Err(Error::Unauthorized),
+            }
+        }
+
+        /// Returns whether `operator` was delegated the automation `permission`.
+        fn is_automated(&self, operator: AccountId, permission: u8) -> bool {
+            self.get_automation(operator) & permission != 0
+        }
+
+        /// Cancels the open impeachment proposal, if any, when the contribution cycle ends. The members it counted leave the pool,
+        /// so its electorate no longer exists.
+        fn cancel_impeachment(&mut self) {
+            let mut proposal = match self.impeachment {
+                Some(open) if Self::is_impeachment_open(&open, self.env().block_timestamp()) => open,
+                _ => return,
+            };
+            proposal.cancelled = true;
+            self.impeachment = Some(proposal);
+            self.env().emit_event(ImpeachmentCancelled {
+                id: proposal.id,
+                new_owner: proposal.new_owner,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+        }
+
+        /// Checks if `proposal` still accepts votes at `now`.
+        fn is_impeachment_open(proposal: &Impeachment, now: Timestamp) -> bool {
+            !proposal.executed && !proposal.cancelled && now <= proposal.ends_at
+        }
+
+        /// Returns an `UnknownPool` error if `pool` was not deployed with `create_pool` or `clone_pool_with_members`.
+        fn ensure_deployed_pool(&self, pool: AccountId) -> Result<()> {
+            if !self.created_pools.contains(&pool) && !self.successors.contains(&pool) {
+                return Err(Error::UnknownPool);
+            }
+            Ok(())
+        }
+
+        /// Replaces the statistics `pool` reported last with `stats` in the global statistics.
+        fn record_pool_stats(&mut self, pool: AccountId, stats: PoolStats) -> Result<()> {
+            let previous = self.reported_stats.get(pool).unwrap_or_default();
+            let replace = |total: u128, old: u128, new: u128| total.saturating_sub(old).checked_add(new).ok_or(Error::ArithmeticOverflow);
+            let mut global = self.global_stats;
+            global.total_value_locked = replace(global.total_value_locked, previous.total_value_locked, stats.total_value_locked)?;
+            global.total_payouts = replace(global.total_payouts, previous.total_payouts, stats.total_payouts)?;
+            global.total_paid_out = replace(global.total_paid_out, previous.total_paid_out, stats.total_paid_out)?;
+            global.members = replace(global.members, previous.members, stats.members)?;
+            global.active_pools = global.active_pools.saturating_sub(previous.active as u32).saturating_add(stats.active as u32);
+            self.global_stats = global;
+            self.reported_stats.insert(pool, &stats);
+            Ok(())
+        }
+
+        /// Adds `pool` to the pools of `member` if they joined it, or removes it if they left.
+        fn record_membership(&mut self, pool: AccountId, member: AccountId, joined: bool) {
+            let mut pools = self.member_pools.get(member).unwrap_or_default();
+            if joined && !pools.contains(&pool) {
+                pools.push(pool);
+            } else if !joined {
+                pools.retain(|other| *other != pool);
+            }
+            if pools.is_empty() {
+                self.member_pools.remove(member);
+            } else {
+                self.member_pools.insert(member, &pools);
+            }
+        }
+
+        /// Reports the statistics of the pool to its registry, if it has one.
+        ///
+        /// Reports are best effort: a registry that rejects the report or cannot be called does not block the pool.
+        fn notify_registry_stats(&self) {
+            if let Some(registry) = self.registry {
+                let _ = build_call::<crate::RaiserEnvironment>()
+                    .call(registry)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("PoolRegistry::report_pool_stats")))
+                            .push_arg(self.get_pool_stats()),
+                    )
+                    .returns::<Result<()>>()
+                    .try_invoke();
+            }
+        }
+
+        /// Reports to the registry of the pool, if it has one, that `member` joined or left. Like `notify_registry_stats`, this is best effort.
+        fn notify_registry_membership(&self, member: AccountId, joined: bool) {
+            if let Some(registry) = self.registry {
+                let _ = build_call::<crate::RaiserEnvironment>()
+                    .call(registry)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("PoolRegistry::report_membership")))
+                            .push_arg(member)
+                            .push_arg(joined),
+                    )
+                    .returns::<Result<()>>()
+                    .try_invoke();
+            }
+        }
+
+        /// Replaces the permissions of the automation `operator`, or revokes them all if `permissions` is 0, and emits an `AutomationSet` event.
+        fn set_automation_permissions(&mut self, operator: AccountId, permissions: u8) {
+            if permissions == 0 {
+                self.automation.remove(operator);
+                self.automation_operators.retain(|account| *account != operator);
+            } else {
+                self.automation.insert(operator, &permissions);
+                if !self.automation_operators.contains(&operator) {
+                    self.automation_operators.push(operator);
+                }
+            }
+            self.env().emit_event(AutomationSet {
+                operator,
+                permissions,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+        }
+
+        /// Grants or revokes `role` for `account` and emits the matching event.
+        fn set_role(&mut self, role: Role, account: AccountId, granted: bool, sender: AccountId) {
+            if granted {
+                self.roles.insert((role, account), &true);
+                if !self.role_holders.contains(&(role, account)) {
+                    self.role_holders.push((role, account));
+                }
+                self.env().emit_event(RoleGranted {
+                    role,
+                    account,
+                    sender,
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            } else {
+                self.roles.remove((role, account));
+                self.role_holders.retain(|holder| *holder != (role, account));
+                self.env().emit_event(RoleRevoked {
+                    role,
+                    account,
+                    sender,
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            }
+        }
+
+        /// Returns the need score of `account`. See `get_next_requester_score`.
+        fn need_score(&self, account: AccountId) -> u128 {
+            let rounds_waiting = self.current_round.saturating_sub(self.last_paid_round.get(account).unwrap_or(0));
+            let payouts = self.payouts_received.get(account).unwrap_or(0) as u128;
+            rounds_waiting.saturating_mul(NEED_SCORE_SCALE) / (payouts + 1)
+        }
+
+        /// Returns the index in the payout queue of the next requester's payout slot, and their need score.
+        fn next_requester_slot(&self) -> Option<(u32, u128)> {
+            if self.config.payout_order == PayoutOrder::JoinOrder {
+                return self.queue_get(0).map(|account_id| (0, self.need_score(account_id)));
+            }
+
+            // The best slot is ranked by (not deferred this round, score, time-weighted credit if enabled); ties go to the earliest slot.
+            let mut best: Option<(u32, bool, u128, u128)> = None;
+            for (index, account_id) in (0..).zip(self.queue_accounts()) {
+                let available = self.deferred_round.get(account_id) != Some(self.current_round);
+                let score = self.need_score(account_id);
+                let credit = if self.config.time_weighted_credit {
+                    self.get_time_credit(account_id)
+                } else {
+                    0
+                };
+                let better = match best {
+                    None => true,
+                    Some((_, best_available, best_score, best_credit)) => {
+                        (available, score, credit) > (best_available, best_score, best_credit)
+                    }
+                };
+                if better {
+                    best = Some((index, available, score, credit));
+                }
+            }
+            best.map(|(index, _, score, _)| (index, score))
+        }
+
+        /// Moves the payout slot of the next requester `member` to the back of the queue
+        /// and excludes them from the need score selection for the current round.
+        fn defer_next_slot(&mut self, member: AccountId) {
+            if let Some(slot) = self.next_requester_slot().and_then(|(index, _)| self.queue_remove(index)) {
+                self.queue_push(slot);
+            }
+            self.deferred_round.insert(member, &self.current_round);
+            self.start_turn();
+        }
+
+        /// Adds `amount` to the payouts following `distribution` and to the accounted balance.
+        ///
+        /// Returns the targeted round, if any, or an `InvalidRound` error if that round has already been paid out.
+        fn add_sponsorship(&mut self, distribution: SponsorDistribution, amount: Balance) -> Result<Option<u128>> {
+            let round = match distribution {
+                SponsorDistribution::SplitEvenly => {
+                    self.unallocated_sponsorship = self.unallocated_sponsorship.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                    None
+                }
+                SponsorDistribution::Round(round) => {
+                    if round < self.current_round {
+                        return Err(Error::InvalidRound);
+                    }
+                    let funded = self.get_round_sponsorship(round);
+                    let funded = funded.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                    self.round_sponsorship.insert(round, &funded);
+                    Some(round)
+                }
+            };
+            self.held_balance = self.held_balance.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.sponsorship_balance = self.sponsorship_balance.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            Ok(round)
+        }
+
+        /// Returns whether `account` holds the token required by the membership gate, querying the token contract.
+        ///
+        /// A failed call to the token contract counts as not holding the token.
+        fn passes_membership_gate(&self, account: AccountId) -> bool {
+            match self.membership_gate {
+                None => true,
+                Some(MembershipGate::Psp22 { token, min_balance }) => {
+                    let result = build_call::<crate::RaiserEnvironment>()
+                        .call(token)
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::balance_of")))
+                                .push_arg(account),
+                        )
+                        .returns::<Balance>()
+                        .try_invoke();
+                    matches!(result, Ok(Ok(balance)) if balance >= min_balance)
+                }
+                Some(MembershipGate::Psp34 { collection }) => {
+                    let result = build_call::<crate::RaiserEnvironment>()
+                        .call(collection)
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP34::balance_of")))
+                                .push_arg(account),
+                        )
+                        .returns::<u32>()
+                        .try_invoke();
+                    matches!(result, Ok(Ok(owned)) if owned > 0)
+                }
+            }
+        }
+
+        /// Settles a payment of `value` by `member` against their `installment`, following the overpayment setting.
+        ///
+        /// Any prepayment of the member is used first. Returns the amount to credit to the member,
+        /// a `TransferError` if the excess could not be refunded, or an `ArithmeticOverflow` error if a held balance would overflow.
+        fn settle_installment(&mut self, member: AccountId, value: Balance, installment: Balance) -> Result<Balance> {
+            let previous_prepaid = self.get_prepaid(member);
+            let available = value.checked_add(previous_prepaid).ok_or(Error::ArithmeticOverflow)?;
+            let excess = available.saturating_sub(installment);
+
+            let (credited, change, prepaid) = match self.config.overpayment {
+                Overpayment::Absorb => (available, 0, 0),
+                Overpayment::Refund => (available - excess, excess, 0),
+                Overpayment::Prepay => (available - excess, 0, excess),
+            };
+            let held_balance = self
+                .held_balance
+                .checked_add(value)
+                .and_then(|held| held.checked_sub(change))
+                .ok_or(Error::ArithmeticOverflow)?;
+            let principal = self.principal.checked_add(credited).ok_or(Error::ArithmeticOverflow)?;
+            let prepaid_total = self
+                .prepaid_total
+                .checked_add(prepaid)
+                .and_then(|total| total.checked_sub(previous_prepaid))
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            if change > 0 {
+                self.check_existential_deposit(change)?;
+            }
+            if change > 0 && self.env().transfer(member, change).is_err() {
+                return Err(Error::TransferError);
+            }
+            if prepaid > 0 {
+                self.prepaid.insert(member, &prepaid);
+            } else {
+                self.prepaid.remove(member);
+            }
+            self.held_balance = held_balance;
+            self.principal = principal;
+            self.prepaid_total = prepaid_total;
+            Ok(credited)
+        }
+
+        /// Pays the installment of `caller` for the current round with `value`, as described in `contribute_round`.
+        fn pay_installment(&mut self, caller: AccountId, value: Balance) -> Result<()> {
+            self.ensure_active()?;
+
+            if self.wind_down_at.is_some() {
+                return Err(Error::WindingDown);
+            }
+
+            if !self.is_contribution_window_open() {
+                return Err(Error::OutsideContributionWindow);
+            }
+
+            if !self.contributed.contains(caller) {
+                return Err(Error::NotContributor);
+            }
+
+            if self.has_contributed_this_round(caller) {
+                return Err(self.already_paid(caller));
+            }
+
+            let installment = self.installment_for(self.weight_of(caller))?;
+            if value.saturating_add(self.get_prepaid(caller)) < installment {
+                return Err(Error::LowAmount);
+            }
+            if value > 0 {
+                self.check_existential_deposit(value)?;
+                self.check_tvl_cap(value)?;
+            }
+
+            let credited = self.settle_installment(caller, value, installment)?;
+            self.credit(caller, credited)?;
+            if self.config.contribution_period == 0 || self.env().block_timestamp() <= self.get_contribution_deadline() {
+                self.record_on_time(caller);
+            }
+
+            Self::env().emit_event(
+                Transfer {
+                from: None,
+                to: Some(caller),
+                value: self.total_supply,
+            });
+            Ok(())
+        }
+
+        /// Credits the referral bonus of every member paid out in the finished cycle to their referrer.
+        ///
+        /// A referral is rewarded once. When the fee reserve runs out, the remaining referrals wait for the next cycle.
+        /// Returns an `ArithmeticOverflow` error if the rewards owed would overflow.
+        fn reward_referrers(&mut self) -> Result<()> {
+            let bonus = self.config.referral_bonus;
+            if bonus == 0 {
+                return Ok(());
+            }
+
+            for (member, _) in self.payout_history.clone() {
+                let referrer = match self.referrers.get(member) {
+                    Some(referrer) => referrer,
+                    None => continue,
+                };
+                if self.fee_reserve < bonus {
+                    return Ok(());
+                }
+
+                self.fee_reserve -= bonus;
+                self.rewards_owed = self.rewards_owed.checked_add(bonus).ok_or(Error::ArithmeticOverflow)?;
+                let rewards = self.get_referral_rewards(referrer).checked_add(bonus).ok_or(Error::ArithmeticOverflow)?;
+                self.referral_rewards.insert(referrer, &rewards);
+                self.referrers.remove(member);
+                self.env().emit_event(ReferralRewarded {
+                    referrer,
+                    member,
+                    amount: bonus,
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            }
+            Ok(())
+        }
+
+        /// Returns the current time on the clock of the contribution schedule.
+        fn schedule_clock(&self) -> u64 {
+            match self.config.period_unit {
+                PeriodUnit::Milliseconds => self.env().block_timestamp(),
+                PeriodUnit::Blocks => self.env().block_number() as u64,
+            }
+        }
+
+        /// Returns the installment of a member with membership tier `weight`, or an `ArithmeticOverflow` error if it would overflow.
+        fn installment_for(&self, weight: u8) -> Result<Balance> {
+            self.get_min_amount().checked_mul(weight as u128).ok_or(Error::ArithmeticOverflow)
+        }
+
+        /// Checks if the current round is overdue, i.e. a contribution period is configured and its deadline plus the grace period has passed.
+        fn is_round_overdue(&self) -> bool {
+            let deadline = self.get_contribution_deadline().saturating_add(self.config.grace_period);
+            self.config.contribution_period > 0 && self.env().block_timestamp() > deadline
+        }
+
+        /// Covers the missing installments of the current round from the insurance reserve.
+        ///
+        /// Members default once the contribution deadline and the grace period of the round have passed. Returns the covered amount,
+        /// or a `RoundIncomplete` error if the round is not overdue yet, no contribution period is configured or the reserve is too small.
+        fn cover_defaults(&mut self) -> Result<Balance> {
+            if !self.is_round_overdue() {
+                return Err(Error::RoundIncomplete);
+            }
+
+            let defaults = self
+                .balance
+                .iter()
+                .filter(|(member, _)| !self.has_contributed_this_round(*member))
+                .map(|(member, _)| Ok((*member, self.installment_for(self.weight_of(*member))?)))
+                .collect::<Result<Vec<(AccountId, Balance)>>>()?;
+            let covered = Self::checked_sum(defaults.iter().map(|(_, installment)| *installment)).ok_or(Error::ArithmeticOverflow)?;
+            if covered > self.insurance {
+                return Err(Error::RoundIncomplete);
+            }
+
+            self.insurance -= covered;
+            for (member, installment) in defaults {
+                self.round_paid.insert(member, &self.current_round);
+                self.paid_rounds.insert((member, self.current_round), &true);
+                let covered_defaults = self.covered_defaults_of(member).checked_add(installment).ok_or(Error::ArithmeticOverflow)?;
+                self.covered_defaults.insert(member, &covered_defaults);
+                self.cycle_defaults = self.cycle_defaults.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+                self.env().emit_event(DefaultCovered {
+                    member,
+                    amount: installment,
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            }
+            Ok(covered)
+        }
+
+        /// Pulls `amount` of `asset` from `member` and swaps it into the native token through `converter`.
+        ///
+        /// Returns the native amount the contract actually received, or a `TransferError` if any of the calls fails.
+        fn convert_to_native(&mut self, member: AccountId, converter: AccountId, asset: AccountId, amount: Balance) -> Result<Balance> {
+            let pulled = build_call::<crate::RaiserEnvironment>()
+                .call(asset)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                        .push_arg(member)
+                        .push_arg(self.env().account_id())
+                        .push_arg(amount)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<core::result::Result<(), PSP22Error>>()
+                .try_invoke();
+            if !matches!(pulled, Ok(Ok(Ok(())))) {
+                return Err(Error::TransferError);
+            }
+
+            let approved = build_call::<crate::RaiserEnvironment>()
+                .call(asset)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::approve")))
+                        .push_arg(converter)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<(), PSP22Error>>()
+                .try_invoke();
+            if !matches!(approved, Ok(Ok(Ok(())))) {
+                return Err(Error::TransferError);
+            }
+
+            let before = self.env().balance();
+            let swapped = build_call::<crate::RaiserEnvironment>()
+                .call(converter)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("AssetConverter::swap_to_native")))
+                        .push_arg(asset)
+                        .push_arg(amount),
+                )
+                .returns::<core::result::Result<Balance, PSP22Error>>()
+                .try_invoke();
+            if !matches!(swapped, Ok(Ok(Ok(_)))) {
+                return Err(Error::TransferError);
+            }
+
+            // Trust the balance change over the value the converter reports
+            Ok(self.env().balance().saturating_sub(before))
+        }
+
+        /// Pays the installment of `member` with the native amount `received` from a pull, and uses up one round of their authorization.
+        fn settle_pull(&mut self, member: AccountId, mut intent: RecurringIntent, received: Balance) -> Result<()> {
+            self.pay_installment(member, received)?;
+            intent.remaining_rounds = intent.remaining_rounds.saturating_sub(1);
+            self.recurring.insert(member, &intent);
+
+            self.env().emit_event(ContributionPulled {
+                member,
+                amount: intent.amount,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns a `PoolArchived` error if the pool is archived.
+        fn ensure_not_archived(&self) -> Result<()> {
+            if self.status == PoolStatus::Archived {
+                return Err(Error::PoolArchived);
+            }
+            Ok(())
+        }
+
+        /// Returns a `PoolNotActive` error unless the pool is active.
+        fn ensure_active(&self) -> Result<()> {
+            if self.status != PoolStatus::Active {
+                return Err(Error::PoolNotActive);
+            }
+            Ok(())
+        }
+
+        /// Sets the lifecycle status and emits a `StatusChanged` event.
+        fn change_status(&mut self, status: PoolStatus) {
+            let previous = self.status;
+            self.status = status;
+            self.env().emit_event(StatusChanged {
+                previous,
+                status,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+        }
+
+        /// Returns an `AccountTooNew` or `NotVerifiedPerson` error if `account` fails the anti-sybil constraints.
+        fn check_join_constraints(&self, account: AccountId) -> Result<()> {
+            let JoinConstraints { min_account_age, personhood } = self.join_constraints;
+
+            if min_account_age > 0 {
+                match self.first_seen.get(account) {
+                    Some(block) if self.env().block_number().saturating_sub(block) >= min_account_age => {}
+                    _ => return Err(Error::AccountTooNew),
+                }
+            }
+
+            if let Some(personhood) = personhood {
+                let result = build_call::<crate::RaiserEnvironment>()
+                    .call(personhood)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("Personhood::is_verified")))
+                            .push_arg(account),
+                    )
+                    .returns::<bool>()
+                    .try_invoke();
+                if !matches!(result, Ok(Ok(true))) {
+                    return Err(Error::NotVerifiedPerson);
+                }
+            }
+
+            if let Some(registry) = self.attestation_registry {
+                let result = build_call::<crate::RaiserEnvironment>()
+                    .call(registry)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("AttestationRegistry::is_attested")))
+                            .push_arg(account),
+                    )
+                    .returns::<bool>()
+                    .try_invoke();
+                if !matches!(result, Ok(Ok(true))) {
+                    return Err(Error::NotAttested);
+                }
+            }
+            Ok(())
+        }
+
+        /// Takes the remainder of the even sponsorship split out of the unallocated sponsorship, unless it goes to the final payout.
+        ///
+        /// This keeps every remaining payout slot at exactly the same share.
+        fn collect_sponsorship_dust(&mut self) -> Result<()> {
+            let remaining_slots = self.queue_len() as u128;
+            if remaining_slots == 0 || self.config.dust_policy == DustPolicy::FinalPayout {
+                return Ok(());
+            }
+
+            let dust = self.unallocated_sponsorship % remaining_slots;
+            if dust > 0 {
+                self.unallocated_sponsorship -= dust;
+                self.sponsorship_balance = self.sponsorship_balance.saturating_sub(dust);
+                self.collect_dust(dust)?;
+            }
+            Ok(())
+        }
+
+        /// Adds `dust` to the fee reserve or the dust balance, following the dust policy, and emits a `DustCollected` event.
+        /// Returns an `ArithmeticOverflow` error if the balance would overflow.
+        fn collect_dust(&mut self, dust: Balance) -> Result<()> {
+            match self.config.dust_policy {
+                DustPolicy::Fees => self.fee_reserve = self.fee_reserve.checked_add(dust).ok_or(Error::ArithmeticOverflow)?,
+                _ => self.dust = self.dust.checked_add(dust).ok_or(Error::ArithmeticOverflow)?,
+            }
+            self.env().emit_event(DustCollected {
+                amount: dust,
+                policy: self.config.dust_policy,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the payout slot `account` acts for: the beneficiary of their team, or `account` itself.
+        fn slot_of(&self, account: AccountId) -> AccountId {
+            self.team_of.get(account).unwrap_or(account)
+        }
+
+        /// Returns the number of payout slots left in the queue.
+        fn queue_len(&self) -> u32 {
+            self.queue_tail - self.queue_head
+        }
+
+        /// Returns the account in the payout slot at `index`, counted from the front of the queue.
+        fn queue_get(&self, index: u32) -> Option<AccountId> {
+            if index >= self.queue_len() {
+                return None;
+            }
+            self.queue.get(self.queue_head + index)
+        }
+
+        /// Returns the accounts in the payout queue, front first. It reads every slot, so messages that change state only call it
+        /// where they have to visit every slot anyway.
+        fn queue_accounts(&self) -> Vec<AccountId> {
+            (self.queue_head..self.queue_tail).filter_map(|index| self.queue.get(index)).collect()
+        }
+
+        /// Checks if `account` has a payout slot left in the queue, without reading the queue.
+        fn queue_contains(&self, account: AccountId) -> bool {
+            self.queued_slots.get(account).unwrap_or(0) > 0
+        }
+
+        /// Returns the index of the first payout slot of `account`, counted from the front of the queue.
+        fn queue_position(&self, account: AccountId) -> Option<u32> {
+            if !self.queue_contains(account) {
+                return None;
+            }
+            (0..self.queue_len()).find(|index| self.queue_get(*index) == Some(account))
+        }
+
+        /// Adds a payout slot for `account` at the back of the queue.
+        fn queue_push(&mut self, account: AccountId) {
+            self.queue.insert(self.queue_tail, &account);
+            self.queue_tail += 1;
+            self.queued_slots.insert(account, &(self.queued_slots.get(account).unwrap_or(0) + 1));
+        }
+
+        /// Removes the payout slot at `index` and returns its account.
+        ///
+        /// Removing the front slot, as a payout in join order does, only moves the head of the queue.
+        /// Removing another slot moves the slots behind it forward by one, so that the queue keeps its order.
+        fn queue_remove(&mut self, index: u32) -> Option<AccountId> {
+            let account = self.queue_get(index)?;
+            if index == 0 {
+                self.queue.remove(self.queue_head);
+                self.queue_head += 1;
+            } else {
+                for position in self.queue_head + index..self.queue_tail - 1 {
+                    if let Some(next) = self.queue.get(position + 1) {
+                        self.queue.insert(position, &next);
+                    }
+                }
+                self.queue_tail -= 1;
+                self.queue.remove(self.queue_tail);
+            }
+            let slots = self.queued_slots.get(account).unwrap_or(0);
+            if slots > 1 {
+                self.queued_slots.insert(account, &(slots - 1));
+            } else {
+                self.queued_slots.remove(account);
+            }
+            if self.queue_head == self.queue_tail {
+                self.queue_head = 0;
+                self.queue_tail = 0;
+            }
+            Some(account)
+        }
+
+        /// Removes every payout slot of `account`, keeping the order of the other slots.
+        fn queue_remove_account(&mut self, account: AccountId) {
+            if !self.queue_contains(account) {
+                return;
+            }
+            let mut next = self.queue_head;
+            for position in self.queue_head..self.queue_tail {
+                match self.queue.get(position) {
+                    Some(slot) if slot != account => {
+                        if next != position {
+                            self.queue.insert(next, &slot);
+                        }
+                        next += 1;
+                    }
+                    _ => {}
+                }
+            }
+            for position in next..self.queue_tail {
+                self.queue.remove(position);
+            }
+            self.queue_tail = next;
+            self.queued_slots.remove(account);
+            if self.queue_head == self.queue_tail {
+                self.queue_head = 0;
+                self.queue_tail = 0;
+            }
+        }
+
+        /// Swaps every payout slot of `a` with those of `b`.
+        fn queue_swap(&mut self, a: AccountId, b: AccountId) {
+            for position in self.queue_head..self.queue_tail {
+                let slot = self.queue.get(position);
+                if slot == Some(a) {
+                    self.queue.insert(position, &b);
+                } else if slot == Some(b) {
+                    self.queue.insert(position, &a);
+                }
+            }
+            let slots_a = self.queued_slots.get(a).unwrap_or(0);
+            let slots_b = self.queued_slots.get(b).unwrap_or(0);
+            self.queued_slots.insert(a, &slots_b);
+            self.queued_slots.insert(b, &slots_a);
+        }
+
+        /// Adds `account` to the payout queue with `weight` slots and to the member ledger.
+        fn admit(&mut self, account: AccountId, weight: u8) -> Result<()> {
+            self.contributors_count = self.contributors_count.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            for _ in 0..weight {
+                self.queue_push(account);
+            }
+            self.record_enrollment(account);
+            self.contributed.insert(account, &true);
+            self.weights.insert(account, &weight);
+            self.total_weight = self.total_weight.checked_add(weight as u128).ok_or(Error::ArithmeticOverflow)?;
+            if !self.balance.iter().any(|(member, _)| member == &account) {
+                self.balance.push((account, 0));
+            }
+            self.commit_member(account);
+            self.start_turn();
+            self.notify_registry_membership(account, true);
+            self.notify_registry_stats();
+            Ok(())
+        }
+
+        /// Admits the accounts enrolled in the next cycle as members of the current one, crediting their held installments.
+        ///
+        /// The admission gates are checked again, since the settings or the account may have changed since it enrolled.
+        /// An account that fails them is not admitted and its installment is returned, or credited to its pending withdrawals
+        /// if the transfer fails, so that the new cycle can start either way.
+        fn admit_enrollments(&mut self) -> Result<()> {
+            for (member, amount) in core::mem::take(&mut self.next_cycle_enrollments) {
+                if self.validate_admission(member, Some(amount), 1).is_err() {
+                    self.refund_enrollment(member, amount)?;
+                    continue;
+                }
+                self.admit(member, 1)?;
+                self.credit(member, amount)?;
+                self.prepaid_total = self.prepaid_total.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.principal = self.principal.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            }
+            Ok(())
+        }
+
+        /// Refunds the installment `account` enrolled with, after it failed the admission gates, and emits an `EnrollmentRefunded` event.
+        fn refund_enrollment(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            self.prepaid_total = self.prepaid_total.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            if self.env().transfer(account, amount).is_ok() {
+                self.held_balance = self.held_balance.checked_sub(amount).ok_or(Error::ArithmeticOverflow)?;
+            } else {
+                let withdrawal = self.withdrawals.get(account).unwrap_or(0).checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self.withdrawals.insert(account, &withdrawal);
+                self.escrowed_total = self.escrowed_total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            }
+            self.env().emit_event(EnrollmentRefunded {
+                member: account,
+                amount,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+            Ok(())
+        }
+
+        /// Returns the `AlreadyMember` error for `account`, with the last round they paid and the amount they have funded.
+        fn already_member(&self, account: AccountId) -> Error {
+            Error::AlreadyMember {
+                round: self.round_paid.get(account).unwrap_or(0),
+                amount: self.ledger_balance(account),
+            }
+        }
+
+        /// Returns the `AlreadyPaidThisRound` error for `account`, with the amount of their receipt for the current round.
+        fn already_paid(&self, account: AccountId) -> Error {
+            Error::AlreadyPaidThisRound {
+                round: self.current_round,
+                amount: self.round_payment(account),
+            }
+        }
+
+        /// Returns the amount of the receipt of `account` for the current round, or 0 if they have not paid it.
+        fn round_payment(&self, account: AccountId) -> Balance {
+            self.receipt_count
+                .get(account)
+                .and_then(|count| count.checked_sub(1))
+                .and_then(|nonce| self.receipts.get((account, nonce)))
+                .filter(|receipt| receipt.round == self.current_round)
+                .map_or(0, |receipt| receipt.amount)
+        }
+
+        /// Returns whether the commit deadline of the current round has passed, so installments are only accepted from `reveal_contribution`.
+        fn is_reveal_only(&self) -> bool {
+            self.get_commit_deadline().is_some_and(|deadline| self.env().block_timestamp() >= deadline)
+        }
+
+        /// Checks that a non-zero `amount` could be transferred out of the pool again, i.e. that it is not below the existential deposit.
+        fn check_existential_deposit(&self, amount: Balance) -> Result<()> {
+            if amount < self.env().minimum_balance() {
+                return Err(Error::BelowExistentialDeposit);
+            }
+            Ok(())
+        }
+
+        /// Emits a `CycleStats` event summarizing the cycle that is about to end.
+        ///
+        /// The pot is the sum of the payouts of the cycle, and the average contribution is the pot divided by the number of members.
+        fn emit_cycle_stats(&self) {
+            let total_pot: Balance = self.payout_history.iter().map(|(_, amount)| amount).sum();
+            let members = self.contributors_count;
+            self.env().emit_event(CycleStats {
+                total_pot,
+                members,
+                avg_contribution: total_pot.checked_div(members).unwrap_or(0),
+                duration_ms: self.env().block_timestamp().saturating_sub(self.cycle_started_at),
+                defaults: self.cycle_defaults,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+        }
+
+        /// Applies `patch` to the current settings and validates the result, as described in `update_config`.
+        fn merge_config(&self, patch: ConfigPatch) -> Result<Config> {
+            let mut new = self.config.clone();
+            if let Some(min_amount) = patch.min_amount {
+                new.min_amount = min_amount;
+            }
+            if let Some(max_contributors) = patch.max_contributors {
+                new.max_contributors = max_contributors;
+            }
+            if let Some(private_mode) = patch.private_mode {
+                new.private_mode = private_mode;
+            }
+            if let Some(request_timeout) = patch.request_timeout {
+                new.request_timeout = request_timeout;
+            }
+            if let Some(payout_order) = patch.payout_order {
+                new.payout_order = payout_order;
+            }
+            if let Some(contribution_period) = patch.contribution_period {
+                new.contribution_period = contribution_period;
+            }
+            if let Some(grace_period) = patch.grace_period {
+                new.grace_period = grace_period;
+            }
+            if let Some(claim_window) = patch.claim_window {
+                new.claim_window = claim_window;
+            }
+            if let Some(overpayment) = patch.overpayment {
+                new.overpayment = overpayment;
+            }
+            if let Some(referral_bonus) = patch.referral_bonus {
+                new.referral_bonus = referral_bonus;
+            }
+            if let Some(min_contributors) = patch.min_contributors {
+                new.min_contributors = min_contributors;
+            }
+            if let Some(enrollment_deadline) = patch.enrollment_deadline {
+                new.enrollment_deadline = enrollment_deadline;
+            }
+            if let Some(period_unit) = patch.period_unit {
+                new.period_unit = period_unit;
+            }
+            if let Some(period_length) = patch.period_length {
+                new.period_length = period_length;
+            }
+            if let Some(contribution_window) = patch.contribution_window {
+                new.contribution_window = contribution_window;
+            }
+            if let Some(dust_policy) = patch.dust_policy {
+                new.dust_policy = dust_policy;
+            }
+            if let Some(payout_delivery) = patch.payout_delivery {
+                new.payout_delivery = payout_delivery;
+            }
+            if let Some(approval_timeout) = patch.approval_timeout {
+                new.approval_timeout = approval_timeout;
+            }
+            if let Some(approval_timeout_action) = patch.approval_timeout_action {
+                new.approval_timeout_action = approval_timeout_action;
+            }
+            if let Some(cover_interest_bps) = patch.cover_interest_bps {
+                new.cover_interest_bps = cover_interest_bps;
+            }
+            if let Some(payout_lockup) = patch.payout_lockup {
+                new.payout_lockup = payout_lockup;
+            }
+            if let Some(time_weighted_credit) = patch.time_weighted_credit {
+                new.time_weighted_credit = time_weighted_credit;
+            }
+            if let Some(commit_period) = patch.commit_period {
+                new.commit_period = commit_period;
+            }
+
+            self.validate_config(&new)?;
+            Ok(new)
+        }
+
+        /// Validates pool settings, as described in `update_config`.
+        ///
+        /// The maximum number of contributors is only checked against the current members if it changes,
+        /// so a pool that has members but no maximum yet can still change its other settings.
+        fn validate_config(&self, config: &Config) -> Result<()> {
+            Self::validate_settings(config)?;
+            if config.max_contributors != self.config.max_contributors {
+                self.validate_max_contributors(config.max_contributors)?;
+            }
+            Ok(())
+        }
+
+        /// Validates the pool settings that do not depend on the current members, for this pool or a new one.
+        fn validate_settings(config: &Config) -> Result<()> {
+            if config.min_amount == 0
+                || config.min_contributors > config.max_contributors
+                || config.contribution_window > config.period_length
+                || config.cover_interest_bps > 10_000
+                || config.winners_per_round == 0
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if config.max_contributors > MAX_POOL_SIZE {
+                return Err(Error::InvalidMaxContributors);
+            }
+            Ok(())
+        }
+
+        /// Replaces the settings with `new` and emits a `ConfigUpdated` event.
+        fn set_config(&mut self, new: Config) {
+            let old = core::mem::replace(&mut self.config, new.clone());
+            self.start_turn();
+            self.env().emit_event(ConfigUpdated {
+                old,
+                new,
+                pool_id: self.env().account_id(),
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+            });
+        }
+
+        /// Returns the total `member` owes for installments other members covered with `cover_for`.
+        fn cover_debt_of(&self, member: AccountId) -> Balance {
+            self.cover_debts.get(member).unwrap_or_default().iter().fold(0, |total: Balance, (_, owed)| total.saturating_add(*owed))
+        }
+
+        /// Repays `amount` of the cover debts of `member` to their creditors' pending withdrawals, oldest debt first.
+        /// Returns an `ArithmeticOverflow` error if a pending withdrawal would overflow.
+        fn repay_cover_debts(&mut self, member: AccountId, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Ok(());
+            }
+
+            let mut remaining = amount;
+            let mut debts = self.cover_debts.get(member).unwrap_or_default();
+            for (creditor, owed) in debts.iter_mut() {
+                let repaid = remaining.min(*owed);
+                if repaid == 0 {
+                    break;
+                }
+                *owed -= repaid;
+                remaining -= repaid;
+                let withdrawal = self.withdrawals.get(*creditor).unwrap_or(0).checked_add(repaid).ok_or(Error::ArithmeticOverflow)?;
+                self.withdrawals.insert(*creditor, &withdrawal);
+                self.env().emit_event(CoverRepaid {
+                    member,
+                    creditor: *creditor,
+                    amount: repaid,
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            }
+            debts.retain(|(_, owed)| *owed > 0);
+            if debts.is_empty() {
+                self.cover_debts.remove(member);
+            } else {
+                self.cover_debts.insert(member, &debts);
+            }
+            self.escrowed_total = self.escrowed_total.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Pays the installment of the current round from the payout lock-up of every member who has one and has not paid yet.
+        fn apply_lockups(&mut self) -> Result<()> {
+            for (member, _) in self.balance.clone() {
+                let locked = self.get_lockup(member);
+                if locked == 0 || self.has_contributed_this_round(member) {
+                    continue;
+                }
+
+                let installment = self.installment_for(self.weight_of(member))?.min(locked);
+                self.credit(member, installment)?;
+                self.principal = self.principal.checked_add(installment).ok_or(Error::ArithmeticOverflow)?;
+                self.escrowed_total = self.escrowed_total.checked_sub(installment).ok_or(Error::ArithmeticOverflow)?;
+                if locked == installment {
+                    self.lockups.remove(member);
+                } else {
+                    self.lockups.insert(member, &(locked - installment));
+                }
+                self.env().emit_event(LockupApplied {
+                    member,
+                    amount: installment,
+                    pool_id: self.env().account_id(),
+                    cycle: self.contribution_cycle,
+                    round: self.current_round,
+                });
+            }
+            Ok(())
+        }
+
+        /// Checks that receiving `amount` keeps the accounted balance of the pool within its TVL cap, if one is set.
+        fn check_tvl_cap(&self, amount: Balance) -> Result<()> {
+            match self.tvl_cap {
+                Some(cap) if self.held_balance.saturating_add(amount) > cap => Err(Error::CapExceeded),
+                _ => Ok(()),
+            }
+        }
+
+        /// Transfers a failed payout to `dest` and removes it from the queue.
+        fn deliver_failed_payout(&mut self, request_id: u128, failed: FailedPayout, dest: AccountId) -> Result<()> {
+            let delivered = if dest == failed.recipient {
+                self.push_payout(dest, failed.amount)
+            } else {
+                self.env().transfer(dest, failed.amount).is_ok()
+            };
+            if !delivered {
+                return Err(Error::TransferError);
+            }
+            self.failed_payouts.remove(request_id);
+            self.failed_payout_ids.retain(|id| *id != request_id);
+            self.escrowed_total = self.escrowed_total.checked_sub(failed.amount).ok_or(Error::ArithmeticOverflow)?;
+            self.held_balance = self.held_balance.saturating_sub(failed.amount);
+
+            self.env().emit_event(Transfer {
+                from: Some(self.owner),
+                to: Some(dest),
+                value: failed.amount,
+            });
+            Ok(())
+        }
+
+        /// Records the current block timestamp as the last activity of `account`.
+        fn record_activity(&mut self, account: AccountId) {
+            self.last_active_at.insert(account, &self.env().block_timestamp());
+        }
+
+        /// Adds an on-time installment to the score of `member` and moves them up the leaderboard.
+        ///
+        /// Members reaching the score of others rank below them, so the earlier achiever stays ahead.
+        fn record_on_time(&mut self, member: AccountId) {
+            let score = self.on_time_scores.get(member).unwrap_or(0).saturating_add(1);
+            self.on_time_scores.insert(member, &score);
+
+            if let Some(index) = self.leaderboard.iter().position(|(account, _)| account == &member) {
+                self.leaderboard.remove(index);
+            }
+            let index = self
+                .leaderboard
+                .iter()
+                .position(|(_, other)| *other < score)
+                .unwrap_or(self.leaderboard.len());
+            if index < LEADERBOARD_SIZE {
+                self.leaderboard.insert(index, (member, score));
+                self.leaderboard.truncate(LEADERBOARD_SIZE);
+            }
+        }
+
+        /// Pushes a payout to `recipient`, through the XCM gateway if they registered a remote beneficiary.
+        ///
+        /// Returns whether the payout left the pool.
+        fn push_payout(&mut self, recipient: AccountId, amount: Balance) -> bool {
+            match (self.xcm_gateway, self.remote_beneficiaries.get(recipient)) {
+                (Some(gateway), Some(beneficiary)) => {
+                    let result = build_call::<crate::RaiserEnvironment>()
+                        .call(gateway)
+                        .transferred_value(amount)
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(ink::selector_bytes!("XcmGateway::transfer_to_parachain")))
+                                .push_arg(beneficiary.para_id)
+                                .push_arg(beneficiary.account),
+                        )
+                        .returns::<bool>()
+                        .try_invoke();
+                    let routed = matches!(result, Ok(Ok(true)));
+                    if routed {
+                        self.env().emit_event(PayoutRouted {
+                            recipient,
+                            amount,
+                            para_id: beneficiary.para_id,
+                            account: beneficiary.account,
+                            pool_id: self.env().account_id(),
+                            cycle: self.contribution_cycle,
+                            round: self.current_round,
+                        });
+                    }
+                    routed
+                }
+                _ => self.env().transfer(recipient, amount).is_ok(),
+            }
+        }
+
+        /// Adds `account` to the member tree, in a free leaf if there is one. Members already in the tree are skipped.
+        fn commit_member(&mut self, account: AccountId) {
+            if self.member_leaf.contains(account) {
+                return;
+            }
+            let leaf = match self.free_member_leaves.pop() {
+                Some(leaf) => leaf,
+                None => {
+                    self.next_member_leaf += 1;
+                    self.next_member_leaf - 1
+                }
+            };
+            self.member_leaf.insert(account, &leaf);
+            let hash = self.member_leaf_hash(account);
+            self.set_member_leaf(leaf, hash);
+        }
+
+        /// Sets leaf `index` of the member tree to `hash` and recomputes the nodes up to the root.
+        fn set_member_leaf(&mut self, index: u32, hash: Hash) {
+            let zeros = self.empty_member_nodes();
+            let mut index = index;
+            let mut node = hash;
+            self.member_tree.insert((0, index), &node);
+            for level in 0..MEMBER_TREE_DEPTH {
+                let sibling = self.member_tree.get((level, index ^ 1)).unwrap_or(zeros[level as usize]);
+                node = if index.is_multiple_of(2) {
+                    self.hash_member_nodes(node, sibling)
+                } else {
+                    self.hash_member_nodes(sibling, node)
+                };
+                index /= 2;
+                self.member_tree.insert((level + 1, index), &node);
+            }
+        }
+
+        /// Returns the hash of an empty subtree of the member tree at each level, from the leaves to the root.
+        fn empty_member_nodes(&self) -> Vec<Hash> {
+            let mut zeros = Vec::with_capacity(MEMBER_TREE_DEPTH as usize + 1);
+            zeros.push(Hash::default());
+            for level in 0..MEMBER_TREE_DEPTH as usize {
+                zeros.push(self.hash_member_nodes(zeros[level], zeros[level]));
+            }
+            zeros
+        }
+
+        /// Returns the leaf of `account` in the member tree: the BLAKE2b-256 hash of the SCALE-encoded account.
+        fn member_leaf_hash(&self, account: AccountId) -> Hash {
+            Hash::from(self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&account))
+        }
+
+        /// Returns the parent of two member tree nodes: the BLAKE2b-256 hash of `left` followed by `right`.
+        fn hash_member_nodes(&self, left: Hash, right: Hash) -> Hash {
+            Hash::from(self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(left, right)))
+        }
+
+        /// Removes every payout slot and the membership state of `account`, after `refund` was transferred to them.
+        ///
+        /// `prepaid` is the part of the refund taken from their prepaid installments, the rest is taken from the principal.
+        fn remove_member(&mut self, account: AccountId, refund: Balance, prepaid: Balance) {
+            let weight = self.weight_of(account);
+            self.queue_remove_account(account);
+            self.start_turn();
+            self.contributors_count = self.contributors_count.saturating_sub(1);
+            self.total_weight = self.total_weight.saturating_sub(weight as u128);
+            self.weights.remove(account);
+            self.round_paid.remove(account);
+            self.recurring.remove(account);
+            self.missed_turns.remove(account);
+            self.deferred_round.remove(account);
+            self.prepaid.remove(account);
+            self.contributed.remove(account);
+            self.address_to_amount_funded.remove(account);
+            self.balance.retain(|(member, _)| member != &account);
+            self.leaderboard.retain(|(member, _)| member != &account);
+            if let Some(leaf) = self.member_leaf.take(account) {
+                self.set_member_leaf(leaf, Hash::default());
+                self.free_member_leaves.push(leaf);
+            }
+            self.release_credit(account);
+            self.cancel_requests_of(account);
+            self.round_pot = self.round_pot.saturating_sub(self.round_payment(account).min(refund));
+            self.total_supply = self.total_supply.saturating_sub(refund);
+            self.held_balance = self.held_balance.saturating_sub(refund);
+            self.principal = self.principal.saturating_sub(refund - prepaid);
+            self.prepaid_total = self.prepaid_total.saturating_sub(prepaid);
+            self.notify_registry_membership(account, false);
+            self.notify_registry_stats();
+        }
+
+        /// Returns the state of `member` exported by `export_state`.
+        fn member_snapshot(&self, member: AccountId) -> MemberSnapshot {
+            let first_round = self.current_round.saturating_sub(self.total_weight);
+            MemberSnapshot {
+                account: member,
+                weight: self.weight_of(member),
+                round_paid: self.round_paid.get(member).unwrap_or(0),
+                paid_rounds: (first_round..=self.current_round).filter(|round| self.paid_rounds.contains((member, *round))).collect(),
+                received: self.received.get(member).unwrap_or(0),
+                last_paid_round: self.last_paid_round.get(member),
+                payouts_received: self.payouts_received.get(member).unwrap_or(0),
+                prepaid: self.get_prepaid(member),
+                time_credit: self.time_credits.get(member).unwrap_or_default(),
+                referrer: self.referrers.get(member),
+                covered_defaults: self.covered_defaults_of(member),
+                cover_debts: self.get_cover_debts(member),
+                escrow: self.escrow.get(member),
+                lockup: self.get_lockup(member),
+                withdrawal: self.withdrawals.get(member).unwrap_or(0),
+                pending_payout: self.pending_payouts.get(member),
+                surplus_claim: self.surplus_claims.get(member).unwrap_or(0),
+                referral_rewards: self.referral_rewards.get(member).unwrap_or(0),
+            }
+        }
+
+        /// Returns the member `caller` acts for if it is an unexpired session key, or `caller` itself otherwise.
+        fn on_behalf_of(&self, caller: AccountId) -> AccountId {
+            match self.session_keys.get(caller) {
+                Some((member, expires_at)) if self.env().block_timestamp() < expires_at => member,
+                _ => caller,
+            }
+        }
+
+        /// Returns the latest price of the price oracle, in native token units per fiat unit,
+        /// or `None` if no oracle is set, the price is older than `max_age` or zero, or the oracle cannot be queried.
+        fn oracle_price(&self) -> Option<Balance> {
+            let PriceOracle { oracle, max_age, .. } = self.price_oracle?;
+
+            let result = build_call::<crate::RaiserEnvironment>()
+                .call(oracle)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("PriceOracle::get_price"))))
+                .returns::<(Balance, Timestamp)>()
+                .try_invoke();
+
+            match result {
+                Ok(Ok((price, updated_at)))
+                    if price > 0 && self.env().block_timestamp().saturating_sub(updated_at) <= max_age =>
+                {
+                    Some(price)
+                }
+                _ => None,
+            }
+        }
+
+        /// Appends `account` to the enrollment order of the current cycle and records when it joined.
+        fn record_enrollment(&mut self, account: AccountId) {
+            self.enrollment_order.push(account);
+            self.joined_at.insert(account, &self.env().block_timestamp());
+        }
+
+        /// Brings the time-weighted credit of `account` and of the pool up to the current block timestamp, then adds `value` to their locked amounts.
+        fn lock_credit(&mut self, account: AccountId, value: Balance) {
+            let now = self.env().block_timestamp();
+            let member = self.time_credits.get(account).unwrap_or_default();
+            self.time_credits.insert(account, &TimeCredit {
+                locked: member.locked.saturating_add(value),
+                credit: member.accrued(now),
+                updated_at: now,
+            });
+            let total = self.total_time_credit;
+            self.total_time_credit = TimeCredit {
+                locked: total.locked.saturating_add(value),
+                credit: total.accrued(now),
+                updated_at: now,
+            };
+        }
+
+        /// Removes the time-weighted credit of `account` from the pool.
+        fn release_credit(&mut self, account: AccountId) {
+            let now = self.env().block_timestamp();
+            let member = match self.time_credits.take(account) {
+                Some(member) => member,
+                None => return,
+            };
+            let total = self.total_time_credit;
+            self.total_time_credit = TimeCredit {
+                locked: total.locked.saturating_sub(member.locked),
+                credit: total.accrued(now).saturating_sub(member.accrued(now)),
+                updated_at: now,
+            };
+        }
+
+        /// Returns the sum of `amounts`, or `None` if it overflows.
+        fn checked_sum(amounts: impl IntoIterator<Item = Balance>) -> Option<Balance> {
+            amounts.into_iter().try_fold(0 as Balance, |total, amount| total.checked_add(amount))
+        }
+
+        /// Returns `amount * part / whole`. `part` and `whole` are first scaled down to 64 bits, so the product
+        /// cannot overflow for amounts below 2^64 however large the time-weighted credits grow.
+        fn pro_rata(amount: Balance, part: u128, whole: u128) -> Balance {
+            let shift = (128 - whole.leading_zeros()).saturating_sub(64);
+            amount.saturating_mul(part >> shift) / (whole >> shift)
+        }
+
+        /// Ends the memberships of the finished cycle: removes the funded amount, round payments, tier, payouts received and member tree leaf
+        /// of every member, and resets the member ledger, the total supply and the round's pot.
+        ///
+        /// Assigning a new `Mapping` does not clear its storage, so every member's entries are removed one by one.
+        fn clear_cycle_ledger(&mut self) {
+            for (member, _) in core::mem::take(&mut self.balance) {
+                self.address_to_amount_funded.remove(member);
+                self.contributed.remove(member);
+                self.round_paid.remove(member);
+                self.weights.remove(member);
+                self.received.remove(member);
+                if let Some(leaf) = self.member_leaf.take(member) {
+                    self.set_member_leaf(leaf, Hash::default());
+                    self.free_member_leaves.push(leaf);
+                }
+                self.notify_registry_membership(member, false);
+            }
+            self.total_supply = 0;
+            self.round_pot = 0;
+        }
+
+        /// Adds `value` to the funded amount and balance of `account`, to the total supply and to the round's pot,
+        /// records it as the account's installment for the current round, and stores a receipt.
+        ///
+        /// Returns an `ArithmeticOverflow` error if the funded amount, the total supply or the pot would overflow.
+        fn credit(&mut self, account: AccountId, value: Balance) -> Result<()> {
+            let funded_amount = self.ledger_balance(account).checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            let total_supply = self.total_supply.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            let round_pot = self.round_pot.checked_add(value).ok_or(Error::ArithmeticOverflow)?;
+            self.address_to_amount_funded.insert(account, &(funded_amount, true));
+            match self.balance.iter_mut().find(|(member, _)| member == &account) {
+                Some(entry) => entry.1 = funded_amount,
+                None => self.balance.push((account, funded_amount)),
+            }
+            self.round_paid.insert(account, &self.current_round);
+            self.paid_rounds.insert((account, self.current_round), &true);
+            self.total_supply = total_supply;
+            self.round_pot = round_pot;
+            self.lock_credit(account, value);
+
+            let fiat_value = self.oracle_price().map(|price| value / price);
+            let mut report = self.get_cycle_value_report(self.contribution_cycle);
+            report.contributed += value;
+            match fiat_value {
+                Some(fiat_value) => report.fiat_value += fiat_value,
+                None => report.unpriced += value,
+            }
+            self.cycle_values.insert(self.contribution_cycle, &report);
+
+            let nonce = self.receipt_count.get(account).unwrap_or(0);
+            self.receipts.insert((account, nonce), &Receipt {
+                nonce,
+                cycle: self.contribution_cycle,
+                round: self.current_round,
+                amount: value,
+                block_number: self.env().block_number(),
+                fiat_value,
+            });
+            self.receipt_count.insert(account, &nonce.checked_add(1).ok_or(Error::ArithmeticOverflow)?);
+            self.notify_registry_stats();
+            Ok(())
+        }
+
+        /// Returns the even share of the unallocated sponsorship for one of the remaining payout slots.
+        fn even_sponsorship_share(&self) -> Balance {
+            let remaining_slots = self.queue_len() as u128;
+            if remaining_slots == 0 {
+                return 0;
+            }
+            self.unallocated_sponsorship / remaining_slots
+        }
+
+        /// Returns whether `account` has paid their installment for the current round.
+        fn has_contributed_this_round(&self, account: AccountId) -> bool {
+            self.round_paid.get(account) == Some(self.current_round)
+        }
+
+        /// Returns an `InvalidMaxContributors` error unless `new_max` is between the current number of contributors and `MAX_POOL_SIZE`.
+        fn validate_max_contributors(&self, new_max: u128) -> Result<()> {
+            if new_max < self.contributors_count || new_max > MAX_POOL_SIZE {
+                return Err(Error::InvalidMaxContributors);
+            }
+            Ok(())
+        }
+
+        /// Runs the validation shared by `contribute_with_weight`, `join` and `can_contribute`.
+        ///
+        /// `join` passes no `amount`, which skips the minimum amount check.
+        /// The admission gates are checked by `validate_admission`, which `enroll_next_cycle` shares.
+        fn validate_contribution(&self, account: AccountId, amount: Option<Balance>, weight: u8) -> Result<()> {
+            self.ensure_active()?;
+
+            if self.wind_down_at.is_some() {
+                return Err(Error::WindingDown);
+            }
+
+            if self.contributors_count < self.config.max_contributors && self.is_payment_phase() {
+                return Err(Error::EnrollmentClosed);
+            }
+
+            if !self.is_contribution_window_open() {
+                return Err(Error::OutsideContributionWindow);
+            }
+
+            if self.contributed.contains(account) {
+                return Err(self.already_member(account));
+            }
+
+            let max = self.config.max_contributors;
+            if self.contributors_count >= MAX_POOL_SIZE || (max > 0 && self.contributors_count >= max) {
+                return Err(Error::PoolFull);
+            }
+
+            if let Some(amount) = amount {
+                self.check_tvl_cap(amount)?;
+            }
+
+            self.validate_admission(account, amount, weight)
+        }
+
+        /// Checks the gates an account must pass to become a member, whichever way it joins: the owner policy, the membership tier,
+        /// the minimum amount and existential deposit of `amount` if any, the membership gate, the account age, personhood and attestation.
+        ///
+        /// Unlike `validate_contribution`, it does not depend on the phase of the current cycle, so `enroll_next_cycle` checks it
+        /// when an account enrolls, and `admit_enrollments` checks it again when the enrolled account joins.
+        fn validate_admission(&self, account: AccountId, amount: Option<Balance>, weight: u8) -> Result<()> {
+            if account == self.owner && self.owner_policy == OwnerPolicy::Blocked {
+                return Err(Error::OwnerCannotJoin);
+            }
+
+            if weight == 0 || weight > MAX_WEIGHT {
+                return Err(Error::InvalidWeight);
+            }
+
+            let installment = self.installment_for(weight)?;
+            if matches!(amount, Some(amount) if amount < installment) {
+                return Err(Error::LowAmount);
+            }
+
+            if let Some(amount) = amount {
+                self.check_existential_deposit(amount)?;
+            }
+
+            if !self.passes_membership_gate(account) {
+                return Err(Error::NotTokenHolder);
+            }
+
+            self.check_join_constraints(account)
+        }
+    }
+
+    /// Implements the cross-contract interface by delegating to the messages of the same name.
+    impl RaiserPool for Raiser {
+        #[ink(message, payable)]
+        fn contribute(&mut self) -> Result<()> {
+            Raiser::contribute(self)
+        }
+
+        #[ink(message)]
+        fn request_token(&mut self) -> Result<()> {
+            Raiser::request_token(self)
+        }
+
+        #[ink(message)]
+        fn approve_request(&mut self, request_id: u128) -> Result<()> {
+            Raiser::approve_request(self, request_id)
+        }
+
+        #[ink(message)]
+        fn is_payment_phase(&self) -> bool {
+            Raiser::is_payment_phase(self)
+        }
+
+        #[ink(message)]
+        fn get_current_round(&self) -> u128 {
+            Raiser::get_current_round(self)
+        }
+
+        #[ink(message)]
+        fn get_next_requester(&self) -> Option<AccountId> {
+            Raiser::get_next_requester(self)
+        }
+
+        #[ink(message)]
+        fn get_total_supply(&self) -> Balance {
+            Raiser::get_total_supply(self)
+        }
+
+        #[ink(message)]
+        fn total_contributors(&self) -> u128 {
+            Raiser::total_contributors(self)
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            Raiser::balance_of(self, owner)
+        }
+
+        #[ink(message)]
+        fn claim(&mut self) -> Result<()> {
+            Raiser::claim(self)
+        }
+    }
+
+    /// Implements the registry interface by delegating to the messages of the same name.
+    impl PoolRegistry for Raiser {
+        #[ink(message)]
+        fn report_pool_stats(&mut self, stats: PoolStats) -> Result<()> {
+            Raiser::report_pool_stats(self, stats)
+        }
+
+        #[ink(message)]
+        fn report_membership(&mut self, member: AccountId, joined: bool) -> Result<()> {
+            Raiser::report_membership(self, member, joined)
+        }
+    }
+
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// The unit of the amounts in the tests: a cent of the environment, the unit of the default amounts.
+        pub(super) const UNIT: Balance = crate::RaiserEnvironment::CENT;
+
+        /// Deploys the pool at an account of its own that holds only the existential deposit, as after instantiation,
+        /// and gives every test account enough balance to pay its installments.
+        ///
+        /// Messages that receive value are called with `pay`, so payouts can only be paid from what the members contributed.
+        fn deploy_pool() {
+            let pool = AccountId::from([0x42; 32]);
+            ink::env::test::set_callee::<crate::RaiserEnvironment>(pool);
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(pool, minimum_balance);
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            for account in [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank] {
+                ink::env::test::set_account_balance::<crate::RaiserEnvironment>(account, 1_000 * UNIT);
+            }
+        }
+
+        /// Calls a payable message the way a transaction would: the value set with `set_value_transferred` moves from the caller
+        /// to the pool before the call, and back to the caller if the call fails.
+        fn pay<T>(call: impl FnOnce() -> Result<T>) -> Result<T> {
+            let value = ink::env::transferred_value::<crate::RaiserEnvironment>();
+            let caller = ink::env::caller::<crate::RaiserEnvironment>();
+            let pool = ink::env::account_id::<crate::RaiserEnvironment>();
+            move_balance(caller, pool, value);
+            let result = call();
+            if result.is_err() {
+                move_balance(pool, caller, value);
+            }
+            result
+        }
+
+        /// Moves `value` from the test account `from` to `to`.
+        fn move_balance(from: AccountId, to: AccountId, value: Balance) {
+            let balance = |account| ink::env::test::get_account_balance::<crate::RaiserEnvironment>(account).unwrap_or(0);
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(from, balance(from) - value);
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(to, balance(to) + value);
+        }
+        /// Test case for the initialization of the `Raiser` contract.
+        ///
+        /// This test creates a new instance of the `Raiser` contract and checks if it is initialized with the correct default values.
+        /// It asserts that the total supply of tokens, the total number of contributors, the number of completed payouts, 
+        /// the maximum number of contributors, and the length of the payout history are all zero.
+        #[ink::test]
+        fn it_works() {
+            let  contract = Raiser::default();
+            assert_eq!(contract.get_total_supply(), 0);
+            assert_eq!(contract.total_contributors(), 0);
+            assert_eq!(contract.get_completed_payouts(), 0);
+            assert_eq!(contract.get_max_contributors(), 0);
+            assert_eq!(contract.get_payout_history().len(), 0);
+        }
+
+        /// Test case for the `set_max_contributors` function of the `Raiser` contract.
+        ///
+        /// This test simulates the owner's call to `set_max_contributors` function.
+        /// It first sets up the testing environment by getting the default accounts and setting the callee and caller.
+        /// The callee is set to the contract's account ID and the caller is set to Alice's account (the owner).
+        ///
+        /// Then it creates a new instance of the `Raiser` contract and asserts that the initial maximum number of contributors is zero.
+        ///
+        /// It then calls `set_max_contributors` to set the maximum number of contributors to 10 and asserts that the function returns `Ok(())`.
+        ///
+        /// Finally, it asserts that the maximum number of contributors is now 10.
+        #[ink::test]
+        fn set_max_contributors_works() {
+            let accounts =
+            ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let contract = ink::env::account_id::<crate::RaiserEnvironment>();
+            ink::env::test::set_callee::<crate::RaiserEnvironment>(contract);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+
+            let mut contract: Raiser = Raiser::new();
+            assert_eq!(contract.get_max_contributors(), 0);
+            assert_eq!(contract.set_max_contributors(10), Ok(()));
+            assert_eq!(contract.get_max_contributors(), 10);
+        }
+
+        /// This test function verifies the functionality of the `contribute` function in the `Raiser` contract.
+        /// It first creates a new instance of the `Raiser` contract and asserts that the total supply of tokens is initially zero.
+        /// Then, it simulates a user (Alice) contributing to the contract by setting the transferred value to 100.
+        /// Finally, it asserts that the total supply of tokens has increased by the contributed amount (100 in this case).
+        #[ink::test]
+        fn contribute_works() {
+            let mut contract = Raiser::new();
+            assert_eq!(contract.get_total_supply(), 0);
+            let accounts =
+            ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.get_total_supply(), 100 * UNIT);
+
+             // Try to contribute again
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(200 * UNIT);
+            // Check if the contribution fails as expected
+            assert_eq!(contract.contribute(), Err(Error::AlreadyMember { round: 1, amount: 100 * UNIT }));
+            // Check if the total supply is still the same
+            assert_eq!(contract.get_total_supply(), 100 * UNIT);
+
+            // Check if Alice is in the list of contributors
+            let contributors = contract.get_contributors();
+            assert_eq!(contributors.len(), 1);
+            assert_eq!(contributors[0].0, accounts.alice);
+            assert_eq!(contributors[0].1, 100 * UNIT);
+         }
+
+        /// This test function verifies the functionality of the `request_token` function in the `Raiser` contract.
+        /// It first creates a new instance of the `Raiser` contract and sets the maximum number of contributors to 1.
+        /// Then, it simulates a user (Alice) contributing to the contract.
+        /// After the contribution, Alice requests a token.
+        /// Finally, it asserts that Alice's request has been added to the requests list.
+        #[ink::test]
+        fn request_token_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            let requests = contract.requests;
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].0, accounts.alice);
+        }
+
+
+        #[ink::test]
+        fn approve_request_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            
+            // Simulate a contribution from Alice
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            contract.request_token().unwrap();
+             
+            // Try to approve the request as the owner
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+          
+        }
+
+        // This test checks the functionality of the `get_next_requester` function.
+        // It simulates contributions and token requests from two accounts, Alice and Bob.
+        // The test verifies that `get_next_requester` correctly returns the account that should be the next to receive tokens.
+        // Even after Bob makes a request, Alice is returned as the next requester because her request came first.
+        #[ink::test]
+        fn get_next_requester_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Initially, there should be no next requester
+            assert_eq!(contract.get_next_requester(), None);
+
+            // Simulate a contribution and token request from Alice
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Now, Alice should be the next requester
+            assert_eq!(contract.get_next_requester(), Some(accounts.alice));
+
+            // Simulate a contribution and token request from Bob
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Alice should still be the next requester, because Bob's request comes after Alice's
+            assert_eq!(contract.get_next_requester(), Some(accounts.alice));
+        }
+
+        // This test checks the functionality of the `get_completed_payouts` function.
+        // It simulates a contribution and token request from Alice, and then approves the request.
+        // The test verifies that `get_completed_payouts` correctly returns the number of completed payouts.
+        // After the approval of Alice's request, the number of completed payouts should increase by one.
+
+        #[ink::test]
+        fn get_completed_payouts_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Initially, there should be no completed payouts
+            assert_eq!(contract.get_completed_payouts(), 0);
+
+            // Simulate a contribution and token request from Alice
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Simulate a contribution from bob
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+           
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+
+            // Approve the request
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+
+            // Now, there should be one completed payout
+             assert_eq!(contract.get_completed_payouts(), 1);
+            
+            
+             assert_eq!(contract.get_payout_history().len(),1);
+
+        }
+
+        // This test checks the functionality of the `next_contribution_cycle` function.
+        // It calls `next_contribution_cycle` twice and checks that the cycle number increases each time.
+        // The test verifies that `next_contribution_cycle` correctly increments the cycle counter.
+        #[ink::test]
+        fn next_contribution_cycle_works() {
+            let mut contract = Raiser::new();
+
+            // Initially, we should be in the first contribution cycle
+            assert_eq!(contract.contribution_cycle, 1);
+
+            // Move to the next contribution cycle
+            assert_eq!(contract.next_contribution_cycle(), Ok(CycleAdvanceOutcome::Advanced));
+
+            // Now, we should be in the second contribution cycle
+            assert_eq!(contract.contribution_cycle, 2);
+
+            // A member who has not paid keeps the cycle open
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.join(1), Ok(()));
+            assert_eq!(contract.next_contribution_cycle(), Ok(CycleAdvanceOutcome::NotAllPaid));
+
+            // Once paid, the cycle still waits for the member's payout
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute_round(), Ok(()));
+            assert_eq!(contract.next_contribution_cycle(), Ok(CycleAdvanceOutcome::HistoryMismatch));
+            assert_eq!(contract.contribution_cycle, 2);
+        }
+        // This test checks the `can_contribute` and `can_request` simulation helpers.
+        // It verifies that they report the same errors as `contribute` and `request_token` without changing state.
+        #[ink::test]
+        fn simulation_helpers_work() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+
+            assert_eq!(contract.can_contribute(accounts.alice, 10 * UNIT), Err(Error::LowAmount));
+            assert_eq!(contract.can_contribute(accounts.alice, 100 * UNIT), Ok(()));
+            assert_eq!(contract.can_request(accounts.alice), Err(Error::NotPaymentPhase));
+            assert_eq!(contract.get_total_supply(), 0);
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            assert_eq!(contract.can_contribute(accounts.alice, 100 * UNIT), Err(Error::AlreadyMember { round: 1, amount: 100 * UNIT }));
+            assert_eq!(contract.can_request(accounts.bob), Err(Error::NotNextContributor));
+            assert_eq!(contract.can_request(accounts.alice), Ok(()));
+            assert_eq!(contract.requests.len(), 0);
+        }
+
+        // This test checks the functionality of the `remove_contributor` function.
+        // It verifies that only the owner can remove a member and that the member disappears from every storage structure.
+        #[ink::test]
+        fn remove_contributor_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Simulate contributions from Alice and Bob
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Bob is not the owner
+            assert_eq!(contract.remove_contributor(accounts.alice), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.remove_contributor(accounts.charlie), Err(Error::NotContributor));
+            assert_eq!(contract.remove_contributor(accounts.bob), Ok(()));
+
+            assert_eq!(contract.total_contributors(), 1);
+            assert_eq!(contract.get_total_supply(), 100 * UNIT);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.get_contributors(), vec![(accounts.alice, 100 * UNIT)]);
+            assert_eq!(contract.contributed.get(accounts.bob), None);
+            assert_eq!(contract.address_to_amount_funded.get(accounts.bob), None);
+        }
+
+        // This test checks weighted membership tiers.
+        // It verifies that a 2x member must contribute twice the minimum and occupies two payout slots.
+        #[ink::test]
+        fn contribute_with_weight_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute_with_weight(0), Err(Error::InvalidWeight));
+            assert_eq!(contract.contribute_with_weight(MAX_WEIGHT + 1), Err(Error::InvalidWeight));
+            assert_eq!(contract.contribute_with_weight(3), Err(Error::LowAmount));
+            assert_eq!(contract.contribute_with_weight(2), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            assert_eq!(contract.weight_of(accounts.alice), 2);
+            assert_eq!(contract.weight_of(accounts.bob), 1);
+            assert_eq!(contract.total_contributors(), 2);
+            assert_eq!(contract.total_weight, 3);
+            assert_eq!(contract.queue_accounts(), vec![accounts.alice, accounts.alice, accounts.bob]);
+            assert_eq!(contract.get_contributors().len(), 2);
+        }
+
+        // This test checks the `verify_invariants` health check.
+        // It verifies that a consistent pool reports no violations and that a corrupted ledger is detected.
+        #[ink::test]
+        fn verify_invariants_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            assert_eq!(contract.verify_invariants(), vec![]);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.verify_invariants(), vec![]);
+
+            // Corrupt the ledger with a duplicate entry
+            contract.balance.push((accounts.alice, 100 * UNIT));
+            let violations = contract.verify_invariants();
+            assert!(violations.contains(&InvariantViolation::DuplicateBalanceEntry(accounts.alice)));
+            assert!(violations.contains(&InvariantViolation::TotalSupplyMismatch {
+                balances_sum: 200 * UNIT,
+                total_supply: 100 * UNIT,
+            }));
+        }
+
+        // This test checks round tracking.
+        // It verifies that a payout is rejected until every member has paid the current round,
+        // and that the round advances after each payout.
+        #[ink::test]
+        fn contribute_round_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Alice and Bob join in round 1
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::AlreadyPaidThisRound { round: 1, amount: 100 * UNIT }));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert!(contract.all_contributed_this_round());
+
+            // Charlie is not a member
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::NotContributor));
+
+            // Alice receives the round 1 payout
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_current_round(), 2);
+            assert!(!contract.all_contributed_this_round());
+
+            // Bob cannot be paid until both members have contributed to round 2
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::RoundIncomplete));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(10 * UNIT);
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::LowAmount));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+
+            assert!(contract.all_contributed_this_round());
+            assert_eq!(contract.balance_of(accounts.alice), 200 * UNIT);
+            assert_eq!(contract.get_total_supply(), 400 * UNIT);
+        }
+
+        // This test checks the `sponsor_pool` function.
+        // It verifies that split sponsorships are shared across the remaining payouts and that
+        // round sponsorships are only added to their round.
+        #[ink::test]
+        fn sponsor_pool_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Charlie sponsors the pool without becoming a member
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(0);
+            assert_eq!(pay(|| contract.sponsor_pool(SponsorDistribution::SplitEvenly)), Err(Error::LowAmount));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(60 * UNIT);
+            assert_eq!(pay(|| contract.sponsor_pool(SponsorDistribution::SplitEvenly)), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(40 * UNIT);
+            assert_eq!(pay(|| contract.sponsor_pool(SponsorDistribution::Round(1))), Ok(()));
+            assert_eq!(contract.get_sponsored_amount(), 100 * UNIT);
+            assert_eq!(contract.total_contributors(), 2);
+
+            // Alice's payout includes half of the split sponsorship and the round 1 sponsorship
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_payout_history(), vec![(accounts.alice, (200 + 30 + 40) * UNIT)]);
+            assert_eq!(contract.unallocated_sponsorship, 30 * UNIT);
+            assert_eq!(contract.get_round_sponsorship(1), 0);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(pay(|| contract.sponsor_pool(SponsorDistribution::Round(1))), Err(Error::InvalidRound));
+        }
+
+        // This test checks the treasurer role.
+        // It verifies that only the owner can appoint a treasurer and that the treasurer can approve
+        // requests but cannot change the pool settings.
+        #[ink::test]
+        fn treasurer_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::Unauthorized));
+            assert_eq!(contract.set_treasurer(Some(accounts.bob)), Err(Error::NotContractOwner));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_treasurer(Some(accounts.bob)), Ok(()));
+            assert_eq!(contract.get_treasurer(), Some(accounts.bob));
+
+            // The treasurer cannot change the pool settings
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_max_contributors(5), Err(Error::NotContractOwner));
+
+            // Only the treasurer itself approves, other accounts cannot act in its place
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.eve);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::Unauthorized));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_current_round(), 2);
+        }
+
+        // This test checks request IDs and the `get_request_details` function.
+        // It verifies that requests get increasing IDs and that their status and timestamps follow the approval.
+        #[ink::test]
+        fn get_request_details_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_request_details(1), None);
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.requests[0].2, 1);
+            assert_eq!(contract.requests[1].2, 2);
+
+            let pending = contract.get_request_details(1).unwrap();
+            assert_eq!(pending.requester, accounts.alice);
+            assert_eq!(pending.amount, 100 * UNIT);
+            assert_eq!(pending.status, RequestStatus::Pending);
+            assert_eq!(pending.created_at, 1_000);
+            assert_eq!(pending.decided_at, None);
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(2_000);
+            assert_eq!(contract.approve_request(1), Ok(()));
+
+            let approved = contract.get_request_details(1).unwrap();
+            assert_eq!(approved.status, RequestStatus::Approved);
+            assert_eq!(approved.decided_at, Some(2_000));
+            assert_eq!(contract.get_request_details(2).unwrap().status, RequestStatus::Cancelled);
+        }
+
+        // This test checks private mode.
+        // It verifies that balances are hidden from other accounts but remain visible to the account itself and the owner.
+        #[ink::test]
+        fn private_mode_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.set_private_mode(true), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_private_mode(true), Ok(()));
+            assert!(contract.is_private_mode());
+
+            // The owner and Bob can see Bob's balance
+            assert_eq!(contract.balance_of(accounts.bob), 100 * UNIT);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.balance_of(accounts.bob), 100 * UNIT);
+
+            // Charlie only sees the account IDs
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.get_contributors(), vec![(accounts.bob, 0)]);
+            assert_eq!(contract.get_total_supply(), 100 * UNIT);
+        }
+
+        // This test checks the `authorize_recurring` function and the validation of `pull_contribution`.
+        // The PSP22 call itself needs a deployed token and is not exercised by the off-chain environment.
+        #[ink::test]
+        fn authorize_recurring_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.authorize_recurring(100 * UNIT, 3), Err(Error::NotContributor));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.authorize_recurring(10 * UNIT, 3), Err(Error::LowAmount));
+            assert_eq!(contract.authorize_recurring(100 * UNIT, 3), Ok(()));
+            assert_eq!(contract.get_recurring(accounts.bob), Some(RecurringIntent {
+                amount: 100 * UNIT,
+                remaining_rounds: 3,
+            }));
+
+            // Bob has already paid the current round when joining
+            assert_eq!(contract.pull_contribution(accounts.bob), Err(Error::AlreadyPaidThisRound { round: 1, amount: 100 * UNIT }));
+            assert_eq!(contract.pull_contribution(accounts.charlie), Err(Error::NoRecurringAuthorization));
+
+            // Move to the next round without a configured token
+            contract.current_round += 1;
+            assert_eq!(contract.pull_contribution(accounts.bob), Err(Error::PaymentTokenNotSet));
+
+            assert_eq!(contract.authorize_recurring(100 * UNIT, 0), Ok(()));
+            assert_eq!(contract.get_recurring(accounts.bob), None);
+        }
+
+        // This test checks how `pull_contribution` settles a pulled installment once the swap has delivered native funds.
+        // The pull and the swap need deployed token and router contracts, so the received amount is moved to the pool directly.
+        #[ink::test]
+        fn settle_pull_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let pool = ink::env::account_id::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.authorize_recurring(100 * UNIT, 2), Ok(()));
+
+            // Without a converter, the token cannot be turned into native funds
+            contract.current_round += 1;
+            contract.payment_token = Some(accounts.django);
+            assert_eq!(contract.pull_contribution(accounts.bob), Err(Error::ConverterNotSet));
+            contract.current_round -= 1;
+
+            // Alice receives the round 1 payout and pays round 2
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+
+            // A swap that delivers less than the installment is rejected
+            let intent = contract.get_recurring(accounts.bob).unwrap();
+            move_balance(accounts.django, pool, 10 * UNIT);
+            assert_eq!(contract.settle_pull(accounts.bob, intent, 10 * UNIT), Err(Error::LowAmount));
+            move_balance(pool, accounts.django, 10 * UNIT);
+
+            move_balance(accounts.django, pool, 100 * UNIT);
+            assert_eq!(contract.settle_pull(accounts.bob, intent, 100 * UNIT), Ok(()));
+            assert_eq!(contract.get_recurring(accounts.bob).map(|intent| intent.remaining_rounds), Some(1));
+            assert!(contract.all_contributed_this_round());
+            assert_eq!(contract.verify_invariants(), vec![]);
+
+            // The pulled installment funds Bob's payout
+            let bob_before = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(accounts.bob).unwrap();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            let bob_after = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_after - bob_before, 200 * UNIT);
+            assert_eq!(contract.verify_invariants(), vec![]);
+        }
+
+        // This test checks the `get_config` and `update_config` functions.
+        // It verifies that only the set fields of the patch are applied and that invalid settings are rejected.
+        #[ink::test]
+        fn update_config_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_config(), Config::default());
+
+            let patch = ConfigPatch {
+                min_amount: Some(75 * UNIT),
+                max_contributors: Some(4),
+                ..Default::default()
+            };
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.update_config(patch.clone()), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.update_config(patch), Ok(()));
+            assert_eq!(contract.get_config(), Config {
+                min_amount: 75 * UNIT,
+                max_contributors: 4,
+                private_mode: false,
+                request_timeout: 0,
+                payout_order: PayoutOrder::JoinOrder,
+                contribution_period: 0,
+                grace_period: 0,
+                claim_window: 0,
+                overpayment: Overpayment::Absorb,
+                referral_bonus: 0,
+                min_contributors: 0,
+                enrollment_deadline: 0,
+                period_unit: PeriodUnit::Milliseconds,
+                period_length: 0,
+                contribution_window: 0,
+                dust_policy: DustPolicy::FinalPayout,
+                payout_delivery: PayoutDelivery::Push,
+                approval_timeout: 0,
+                approval_timeout_action: ApprovalTimeoutAction::ForceApprove,
+                cover_interest_bps: 0,
+                payout_lockup: false,
+                winners_per_round: 1,
+                winner_split: WinnerSplit::Even,
+                time_weighted_credit: false,
+                commit_period: 0,
+            });
+            assert_eq!(contract.get_max_contributors(), 4);
+
+            let invalid = ConfigPatch {
+                min_amount: Some(0),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(invalid), Err(Error::InvalidConfig));
+            assert_eq!(contract.get_config().min_amount, 75 * UNIT);
+        }
+
+        // This test checks the `defer_turn` function.
+        // It verifies that only the next requester can defer, that their pending request is cancelled,
+        // and that the following member becomes eligible.
+        #[ink::test]
+        fn defer_turn_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            assert_eq!(contract.defer_turn(), Err(Error::NotNextContributor));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.defer_turn(), Ok(()));
+
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+            assert_eq!(contract.requests.len(), 0);
+            assert_eq!(contract.get_request_details(1).unwrap().status, RequestStatus::Cancelled);
+            assert_eq!(contract.can_request(accounts.bob), Ok(()));
+        }
+
+        // This test checks the `skip_inactive` function.
+        // It verifies that the next requester can only be skipped once the request timeout has passed
+        // and while they have no pending request.
+        #[ink::test]
+        fn skip_inactive_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(contract.skip_inactive(), Err(Error::NotPaymentPhase));
+            assert_eq!(contract.update_config(ConfigPatch {
+                request_timeout: Some(1_000),
+                ..Default::default()
+            }), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(500);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // The timeout starts when the pool is complete
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            assert_eq!(contract.skip_inactive(), Err(Error::TimeoutNotReached));
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_500);
+            assert_eq!(contract.skip_inactive(), Ok(()));
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+            assert_eq!(contract.missed_turns_of(accounts.alice), 1);
+
+            // Bob requests in time and cannot be skipped
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(3_000);
+            assert_eq!(contract.skip_inactive(), Err(Error::RequestPending));
+        }
+
+        // This test checks the `get_available_balance` function and the balance check of `approve_request`.
+        // It verifies that a payout is rejected when it would dip into the existential deposit.
+        #[ink::test]
+        fn get_available_balance_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let contract_id = ink::env::account_id::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance + 50 * UNIT);
+            assert_eq!(contract.get_available_balance(), 50 * UNIT);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::InsufficientContractBalance));
+
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance + 100 * UNIT);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+        }
+
+        // This test checks the `export_state` function and the `new_from_snapshot` constructor.
+        // It verifies that a pool rebuilt from the exported chunks has the same state.
+        #[ink::test]
+        fn export_state_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                overpayment: Some(Overpayment::Prepay),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(pay(|| contract.contribute_with_weight(2)), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(80 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.export_state(0), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            let export = |contract: &Raiser| {
+                let mut data = Vec::new();
+                for chunk in 0..SNAPSHOT_CHUNKS {
+                    data.extend(contract.export_state(chunk).unwrap());
+                }
+                data
+            };
+            let data = export(&contract);
+            assert_eq!(contract.export_state(SNAPSHOT_CHUNKS), Ok(Vec::new()));
+
+            // The migrated pool shares the test account of the original, so it is already funded
+            let migrated = Raiser::new_from_snapshot(data.clone()).unwrap();
+            assert_eq!(migrated.get_config(), contract.get_config());
+            assert_eq!(migrated.get_total_supply(), 150 * UNIT);
+            assert_eq!(migrated.total_contributors(), 2);
+            assert_eq!(migrated.get_contributors(), contract.get_contributors());
+            assert_eq!(migrated.weight_of(accounts.alice), 2);
+            assert_eq!(migrated.get_prepaid(accounts.bob), 30 * UNIT);
+            assert_eq!(migrated.get_enrollment_order(), contract.get_enrollment_order());
+            assert_eq!(migrated.get_pending_requests(0, 10), contract.get_pending_requests(0, 10));
+            assert_eq!(migrated.get_balances_breakdown(), contract.get_balances_breakdown());
+            assert_eq!(migrated.round_pot, 150 * UNIT);
+            assert_eq!(migrated.get_untracked_balance(), 0);
+            assert_eq!(migrated.can_contribute(accounts.bob, 100 * UNIT), Err(Error::AlreadyMember { round: 1, amount: 50 * UNIT }));
+            assert!(migrated.all_contributed_this_round());
+            assert!(migrated.has_paid(accounts.alice, 1));
+            assert_eq!(migrated.verify_invariants(), vec![]);
+
+            // A snapshot whose totals do not match its members is rejected
+            contract.total_weight += 1;
+            assert_eq!(Raiser::new_from_snapshot(export(&contract)).err(), Some(Error::InvalidSnapshot));
+            contract.total_weight -= 1;
+            contract.prepaid_total += 1;
+            contract.held_balance += 1;
+            assert_eq!(Raiser::new_from_snapshot(export(&contract)).err(), Some(Error::InvalidSnapshot));
+            contract.prepaid_total -= 1;
+            contract.held_balance -= 1;
+            contract.config.min_amount = 0;
+            assert_eq!(Raiser::new_from_snapshot(export(&contract)).err(), Some(Error::InvalidSnapshot));
+
+            assert_eq!(Raiser::new_from_snapshot(vec![1, 2, 3]).err(), Some(Error::InvalidSnapshot));
+        }
+
+        // This test checks the `get_receipts` function.
+        // It verifies that every contribution stores a receipt and that receipts can be paginated.
+        #[ink::test]
+        fn get_receipts_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Move to the next round and pay the installment in a later block
+            contract.current_round += 1;
+            ink::env::test::advance_block::<crate::RaiserEnvironment>();
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(60 * UNIT);
+            assert_eq!(contract.contribute_round(), Ok(()));
+
+            let receipts = contract.get_receipts(accounts.alice, 0, 10);
+            assert_eq!(receipts.len(), 2);
+            assert_eq!(receipts[0], Receipt {
+                nonce: 0,
+                cycle: 1,
+                round: 1,
+                amount: 100 * UNIT,
+                block_number: 0,
+                fiat_value: None,
+            });
+            assert_eq!(receipts[1].round, 2);
+            assert_eq!(receipts[1].amount, 60 * UNIT);
+            assert_eq!(receipts[1].block_number, 1);
+
+            assert_eq!(contract.get_receipts(accounts.alice, 1, 1), vec![receipts[1]]);
+            assert_eq!(contract.get_receipts(accounts.alice, 2, 10), vec![]);
+            assert_eq!(contract.get_receipts(accounts.bob, 0, 10), vec![]);
+        }
+
+        // This test checks the `request_token_with_memo` function.
+        // It verifies that the memo hash is stored with the request and surfaced in `get_request_details`.
+        #[ink::test]
+        fn request_token_with_memo_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let memo_hash = Hash::from([7; 32]);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+
+            assert_eq!(contract.request_token_with_memo(Some(memo_hash)), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.get_request_details(1).unwrap().memo_hash, Some(memo_hash));
+            assert_eq!(contract.get_request_details(2).unwrap().memo_hash, None);
+        }
+
+        // This test checks the bounds enforced by `set_max_contributors`.
+        // It verifies that the maximum cannot drop below the current member count or exceed `MAX_POOL_SIZE`,
+        // and that setting it to the member count starts the payment phase.
+        #[ink::test]
+        fn set_max_contributors_bounds_work() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(MAX_POOL_SIZE), Ok(()));
+            assert_eq!(contract.set_max_contributors(MAX_POOL_SIZE + 1), Err(Error::InvalidMaxContributors));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Err(Error::InvalidMaxContributors));
+            assert_eq!(contract.update_config(ConfigPatch {
+                max_contributors: Some(0),
+                ..Default::default()
+            }), Err(Error::InvalidMaxContributors));
+            assert_eq!(contract.can_request(accounts.alice), Err(Error::NotPaymentPhase));
+
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(contract.can_request(accounts.alice), Ok(()));
+
+            // The hard cap also bounds the number of members
+            contract.contributors_count = MAX_POOL_SIZE;
+            assert_eq!(contract.can_contribute(accounts.charlie, 100 * UNIT), Err(Error::PoolFull));
+        }
+
+        // This test checks that the root storage cell of a full pool fits the 16 KiB buffer of the contract.
+        // It fills every member vector for `MAX_POOL_SIZE` members, with the longest metadata URI, and encodes the contract.
+        #[ink::test]
+        fn max_pool_fits_root_cell_works() {
+            let mut contract = Raiser::new();
+            for i in 0..MAX_POOL_SIZE as u32 {
+                let member = AccountId::from([i as u8 + 1; 32]);
+                contract.balance.push((member, Balance::MAX));
+                contract.requests.push((member, Balance::MAX, u128::MAX));
+                contract.payout_history.push((member, Balance::MAX));
+                contract.next_cycle_enrollments.push((member, Balance::MAX));
+                contract.leaderboard.push((member, Score::MAX));
+                contract.enrollment_order.push(member);
+                contract.free_member_leaves.push(i);
+            }
+            contract.metadata_uri = "x".repeat(MAX_METADATA_URI_LEN);
+
+            let mut root = Vec::new();
+            ink::storage::traits::Storable::encode(&contract, &mut root);
+            assert!(root.len() <= 16 * 1024, "root storage cell takes {} bytes", root.len());
+        }
+
+        // This test checks that a full pool turns away new members.
+        // It verifies that an outsider cannot join past `max_contributors` and push the pool out of the payment phase.
+        #[ink::test]
+        fn full_pool_rejects_members_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(contract.contribute(), Ok(()));
+            }
+            assert!(contract.is_payment_phase());
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.can_contribute(accounts.charlie, 100 * UNIT), Err(Error::PoolFull));
+            assert_eq!(contract.contribute(), Err(Error::PoolFull));
+            assert_eq!(contract.join(1), Err(Error::PoolFull));
+            assert_eq!(contract.total_contributors(), 2);
+            assert!(contract.is_payment_phase());
+
+            // The next member in line can still request the payout
+            let next = contract.get_next_requester().unwrap();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(next);
+            assert_eq!(contract.request_token(), Ok(()));
+        }
+
+        // This test checks the role-based access control.
+        // It verifies that only admins manage roles and that granted roles unlock the matching privileged messages.
+        #[ink::test]
+        fn roles_work() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // The deployer holds every role
+            assert!(contract.has_role(Role::Admin, accounts.alice));
+            assert!(contract.has_role(Role::Upgrader, accounts.alice));
+            assert!(!contract.has_role(Role::Admin, accounts.bob));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.grant_role(Role::Admin, accounts.bob), Err(Error::NotContractOwner));
+            assert_eq!(contract.set_max_contributors(3), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.grant_role(Role::Admin, accounts.bob), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_max_contributors(3), Ok(()));
+            assert_eq!(contract.revoke_role(Role::Approver, accounts.alice), Ok(()));
+            assert!(!contract.has_role(Role::Approver, accounts.alice));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(1), Err(Error::Unauthorized));
+
+            // The treasurer's approver role follows the treasurer
+            assert_eq!(contract.set_treasurer(Some(accounts.charlie)), Ok(()));
+            assert!(contract.has_role(Role::Approver, accounts.charlie));
+            assert_eq!(contract.set_treasurer(None), Ok(()));
+            assert!(!contract.has_role(Role::Approver, accounts.charlie));
+        }
+
+        // This test checks the need score payout order.
+        // It verifies that the member who waited longest without a payout is picked, and that deferring
+        // excludes a member from the selection for the rest of the round.
+        #[ink::test]
+        fn need_score_payout_order_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.update_config(ConfigPatch {
+                max_contributors: Some(3),
+                payout_order: Some(PayoutOrder::NeedScore),
+                ..Default::default()
+            }), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Alice already received a payout in an earlier round, so Alice's score is lower
+            contract.current_round = 3;
+            contract.last_paid_round.insert(accounts.alice, &2);
+            contract.payouts_received.insert(accounts.alice, &1);
+            assert_eq!(contract.get_next_requester_score(), Some((accounts.bob, 3 * NEED_SCORE_SCALE)));
+            assert_eq!(contract.can_request(accounts.alice), Err(Error::NotNextContributor));
+
+            // Bob defers, so Charlie is next despite the tie
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.defer_turn(), Ok(()));
+            assert_eq!(contract.get_next_requester(), Some(accounts.charlie));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.can_request(accounts.charlie), Ok(()));
+        }
+
+        // This test checks the `get_untracked_balance` and `allocate_donations` functions.
+        // It verifies that funds sent outside of the contract messages are detected and can be added to the payouts.
+        #[ink::test]
+        fn allocate_donations_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let contract_id = ink::env::account_id::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+
+            // The contract holds exactly Alice's contribution
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance + 100 * UNIT);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.get_untracked_balance(), 0);
+            assert_eq!(contract.allocate_donations(SponsorDistribution::SplitEvenly), Err(Error::NoUntrackedBalance));
+
+            // Someone sends 30 with a plain balance transfer
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance + 130 * UNIT);
+            assert_eq!(contract.get_untracked_balance(), 30 * UNIT);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.allocate_donations(SponsorDistribution::SplitEvenly), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.allocate_donations(SponsorDistribution::Round(1)), Ok(()));
+            assert_eq!(contract.get_donations(), 30 * UNIT);
+            assert_eq!(contract.get_round_sponsorship(1), 30 * UNIT);
+            assert_eq!(contract.get_untracked_balance(), 0);
+        }
+
+        // This test checks the `get_pool_stats` function.
+        // It verifies that the value locked and the lifetime payouts follow contributions and payouts, across a cycle reset.
+        #[ink::test]
+        fn get_pool_stats_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            assert_eq!(contract.get_pool_stats(), PoolStats {
+                total_value_locked: 0,
+                total_payouts: 0,
+                total_paid_out: 0,
+                members: 0,
+                active: false,
+            });
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_pool_stats().total_value_locked, 100 * UNIT);
+            assert!(contract.get_pool_stats().active);
+
+            // The payout completes the cycle, which resets the member count but not the lifetime figures
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            let stats = contract.get_pool_stats();
+            assert_eq!(stats.total_value_locked, 0);
+            assert_eq!(stats.total_payouts, 1);
+            assert_eq!(stats.total_paid_out, 100 * UNIT);
+            assert_eq!(stats.members, 0);
+            assert!(!stats.active);
+        }
+
+        // This test checks the `report_pool_stats`, `report_membership`, `pools_of_member` and `global_stats` functions.
+        // It verifies that only deployed pools can report, and that a new report replaces the previous figures of the pool.
+        #[ink::test]
+        fn pool_registry_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let report = |total_value_locked: Balance, total_payouts: u128, total_paid_out: Balance, members: u128| PoolStats {
+                total_value_locked,
+                total_payouts,
+                total_paid_out,
+                members,
+                active: members > 0,
+            };
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.report_pool_stats(report(100 * UNIT, 0, 0, 2)), Err(Error::UnknownPool));
+            assert_eq!(contract.report_membership(accounts.bob, true), Err(Error::UnknownPool));
+
+            contract.created_pools.push(accounts.django);
+            contract.successors.push(accounts.eve);
+            assert_eq!(contract.report_pool_stats(report(100 * UNIT, 0, 0, 2)), Ok(()));
+            assert_eq!(contract.report_membership(accounts.bob, true), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.eve);
+            assert_eq!(contract.report_pool_stats(report(30 * UNIT, 1, 50 * UNIT, 1)), Ok(()));
+            assert_eq!(contract.report_membership(accounts.bob, true), Ok(()));
+            assert_eq!(contract.pools_of_member(accounts.bob), vec![accounts.django, accounts.eve]);
+            let global = contract.global_stats();
+            assert_eq!(global.total_value_locked, 130 * UNIT);
+            assert_eq!(global.total_payouts, 1);
+            assert_eq!(global.total_paid_out, 50 * UNIT);
+            assert_eq!(global.members, 3);
+            assert_eq!(global.active_pools, 2);
+
+            // Django's cycle ends, so its new report replaces its previous figures and Bob leaves it
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.report_pool_stats(report(0, 2, 200 * UNIT, 0)), Ok(()));
+            assert_eq!(contract.report_membership(accounts.bob, false), Ok(()));
+            assert_eq!(contract.pools_of_member(accounts.bob), vec![accounts.eve]);
+            assert_eq!(contract.pools_of_member(accounts.charlie), vec![]);
+            let global = contract.global_stats();
+            assert_eq!(global.total_value_locked, 30 * UNIT);
+            assert_eq!(global.total_payouts, 3);
+            assert_eq!(global.total_paid_out, 250 * UNIT);
+            assert_eq!(global.members, 1);
+            assert_eq!(global.active_pools, 1);
+            assert_eq!(global.pools, 0);
+        }
+
+        // This test checks the `tick` function.
+        // It verifies that reminders are emitted once per round and overdue notices only after the grace period.
+        #[ink::test]
+        fn tick_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            assert_eq!(contract.tick(), Err(Error::RemindersDisabled));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                contribution_period: Some(1_000),
+                grace_period: Some(500),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            assert_eq!(contract.get_contribution_deadline(), 1_000);
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            contract.current_round += 1;
+
+            // Alice has not paid for round 2, so a reminder is due
+            let emitted = ink::env::test::recorded_events().count();
+            assert_eq!(contract.tick(), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), emitted + 1);
+
+            // A second tick in the same round does not repeat it
+            assert_eq!(contract.tick(), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), emitted + 1);
+
+            // Once the deadline and the grace period have passed, Alice is reported as overdue
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_501);
+            assert_eq!(contract.tick(), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), emitted + 2);
+            assert_eq!(contract.tick(), Ok(()));
+            assert_eq!(ink::env::test::recorded_events().count(), emitted + 2);
+        }
+
+        // This test checks the `claim` and `expire_escrow` functions.
+        // It verifies that escrowed payouts can be claimed within the window and otherwise return to the pool.
+        #[ink::test]
+        fn escrowed_payout_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(2),
+                claim_window: Some(1_000),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Alice's payout is held in escrow instead of being transferred
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_escrow(accounts.alice), Some((200 * UNIT, 1_000)));
+            assert_eq!(contract.expire_escrow(accounts.alice), Err(Error::ClaimWindowOpen));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.claim(), Err(Error::NothingToClaim));
+
+            // Alice lets the window lapse, so the payout goes back to the pool
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_001);
+            assert_eq!(contract.claim(), Err(Error::ClaimWindowClosed));
+            assert_eq!(contract.expire_escrow(accounts.alice), Ok(()));
+            assert_eq!(contract.get_escrow(accounts.alice), None);
+            assert_eq!(contract.unallocated_sponsorship, 200 * UNIT);
+        }
+
+        // This test checks the `set_membership_gate` function.
+        // It verifies that only an admin can gate the pool and that an ungated pool accepts anyone.
+        // The token queries themselves need a deployed token contract and are not available off-chain.
+        #[ink::test]
+        fn set_membership_gate_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let gate = MembershipGate::Psp22 {
+                token: accounts.django,
+                min_balance: 10,
+            };
+
+            assert_eq!(contract.get_membership_gate(), None);
+            assert_eq!(contract.can_contribute(accounts.bob, 100 * UNIT), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_membership_gate(Some(gate)), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_membership_gate(Some(gate)), Ok(()));
+            assert_eq!(contract.get_membership_gate(), Some(gate));
+            assert_eq!(contract.set_membership_gate(None), Ok(()));
+            assert_eq!(contract.get_membership_gate(), None);
+        }
+
+        // This test checks the `get_pool_snapshot` function.
+        // It verifies that the overview follows the pool from the contribution phase to a pending request.
+        #[ink::test]
+        fn get_pool_snapshot_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            let overview = contract.get_pool_snapshot();
+            assert_eq!(overview.phase, PoolPhase::Contribution);
+            assert_eq!(overview.total_supply, 100 * UNIT);
+            assert_eq!(overview.members, 1);
+            assert_eq!(overview.round, 1);
+            assert_eq!(overview.pending_request, None);
+            assert_eq!(overview.config.max_contributors, 2);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+
+            let overview = contract.get_pool_snapshot();
+            assert_eq!(overview.phase, PoolPhase::Payment);
+            assert_eq!(overview.next_requester, Some(accounts.alice));
+            assert_eq!(overview.pending_request.map(|request| request.amount), Some(200 * UNIT));
+        }
+
+        // This test checks the overpayment settings of `contribute_round`.
+        // It verifies that the excess of an installment is refunded or credited toward the next rounds.
+        #[ink::test]
+        fn overpayment_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                overpayment: Some(Overpayment::Prepay),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+
+            // Alice joins with 120, so 70 is kept for later
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(120 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 50 * UNIT);
+            assert_eq!(contract.get_prepaid(accounts.alice), 70 * UNIT);
+
+            // The prepayment covers the next round without sending anything
+            contract.current_round += 1;
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(0);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 100 * UNIT);
+            assert_eq!(contract.get_prepaid(accounts.alice), 20 * UNIT);
+
+            // With refunds, the leftover prepayment and the excess are sent back
+            let patch = ConfigPatch {
+                overpayment: Some(Overpayment::Refund),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            contract.current_round += 1;
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(60 * UNIT);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 150 * UNIT);
+            assert_eq!(contract.get_prepaid(accounts.alice), 0);
+            assert_eq!(contract.held_balance, 150 * UNIT);
+        }
+
+        // This test checks the `prune_history` function.
+        // It verifies that the receipts and requests of a finished cycle are deleted and the current cycle is kept.
+        #[ink::test]
+        fn prune_history_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Alice completes cycle 1 alone
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.contribution_cycle, 2);
+
+            // Alice joins cycle 2 again
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_receipts(accounts.alice, 0, 10).len(), 2);
+
+            assert_eq!(contract.prune_history(3), Err(Error::InvalidCycle));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.prune_history(2), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.prune_history(2), Ok(()));
+            assert_eq!(contract.get_request_details(1), None);
+            let receipts = contract.get_receipts(accounts.alice, 0, 10);
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].cycle, 2);
+        }
+
+        // This test checks the `commit_contribution` and `reveal_contribution` functions.
+        // It verifies that an installment is only accepted when it matches the commitment of the current round.
+        #[ink::test]
+        fn commit_reveal_contribution_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let salt = [7u8; 32];
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.commit_contribution(Hash::from([0u8; 32])), Err(Error::NotContributor));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            contract.current_round += 1;
+
+            assert_eq!(contract.reveal_contribution(salt), Err(Error::NoCommitment));
+            let commitment = contract.get_commitment_hash(80 * UNIT, salt);
+            assert_eq!(contract.commit_contribution(commitment), Ok(()));
+
+            // Revealing another amount than the committed one is rejected
+            assert_eq!(contract.reveal_contribution(salt), Err(Error::CommitmentMismatch));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(80 * UNIT);
+            assert_eq!(contract.reveal_contribution(salt), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), 180 * UNIT);
+            assert_eq!(contract.reveal_contribution(salt), Err(Error::NoCommitment));
+        }
+
+        // This test checks the commit deadline of `commit_period`.
+        // It verifies that commitments close at the deadline, that reveals only open after it, and that the rest of the round only accepts reveals.
+        #[ink::test]
+        fn commit_deadline_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let salt = [7u8; 32];
+            assert_eq!(contract.get_commit_deadline(), None);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(3),
+                commit_period: Some(1_000),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+            contract.current_round += 1;
+            contract.round_opened_at = 10_000;
+            assert_eq!(contract.get_commit_deadline(), Some(11_000));
+
+            // Before the deadline, members commit and nothing can be revealed yet
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(10_500);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.commit_contribution(contract.get_commitment_hash(100 * UNIT, salt)), Ok(()));
+            assert_eq!(pay(|| contract.reveal_contribution(salt)), Err(Error::RevealNotOpen));
+
+            // After it, Bob can neither commit nor pay without a commitment, while Alice reveals
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(11_000);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.commit_contribution(contract.get_commitment_hash(100 * UNIT, salt)), Err(Error::CommitPhaseClosed));
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::RevealOnly));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(pay(|| contract.reveal_contribution(salt)), Ok(()));
+            assert!(contract.has_contributed_this_round(accounts.alice));
+            assert!(!contract.has_contributed_this_round(accounts.bob));
+        }
+
+        // This test checks the `contribute_with_referrer` function.
+        // It verifies that the referrer earns the bonus from the fee reserve once the referred member completes a cycle.
+        #[ink::test]
+        fn contribute_with_referrer_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(1),
+                referral_bonus: Some(10 * UNIT),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(25 * UNIT);
+            assert_eq!(pay(|| contract.fund_fee_reserve()), Ok(()));
+
+            // Bob joins on Charlie's invitation
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute_with_referrer(accounts.bob)), Err(Error::InvalidReferrer));
+            assert_eq!(pay(|| contract.contribute_with_referrer(accounts.charlie)), Ok(()));
+            assert_eq!(contract.get_referrals(accounts.charlie), vec![accounts.bob]);
+            assert_eq!(contract.get_referral_rewards(accounts.charlie), 0);
+
+            // Bob's payout completes the cycle
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_referral_rewards(accounts.charlie), 10 * UNIT);
+            assert_eq!(contract.fee_reserve, 15 * UNIT);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.withdraw_referral_rewards(), Ok(()));
+            assert_eq!(contract.get_referral_rewards(accounts.charlie), 0);
+        }
+
+        // This test checks the `wind_down` and `refund_members` functions.
+        // It verifies that contributions stop, refunds wait for the timelock and each member gets their net position back.
+        #[ink::test]
+        fn wind_down_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.terminate(), Err(Error::NotWoundDown));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.wind_down(), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.wind_down(), Ok(()));
+            assert_eq!(contract.wind_down(), Err(Error::WindingDown));
+
+            // New members are turned away
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Err(Error::WindingDown));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.refund_members(), Err(Error::NotWoundDown));
+
+            let alice_before = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(accounts.alice).unwrap();
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(WIND_DOWN_DELAY);
+            assert_eq!(contract.refund_members(), Ok(()));
+            let alice_after = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(accounts.alice).unwrap();
+            assert_eq!(alice_after - alice_before, 100 * UNIT);
+            assert_eq!(contract.refund_members(), Err(Error::NotWoundDown));
+        }
+
+        // This test checks what `refund_members` returns to each account and when `terminate` can run.
+        // It verifies that prepayments and enrollments are refunded, funds owed to others block the termination,
+        // and the remaining balance goes to the members rather than the caller.
+        #[ink::test]
+        fn refund_members_and_terminate_work() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let balance = |account| ink::env::test::get_account_balance::<crate::RaiserEnvironment>(account).unwrap();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                overpayment: Some(Overpayment::Prepay),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+
+            // Alice prepays 70, Bob pays the minimum and Charlie funds the insurance reserve
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(120 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(30 * UNIT);
+            assert_eq!(pay(|| contract.fund_insurance()), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.wind_down(), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(WIND_DOWN_DELAY);
+            assert_eq!(contract.terminate(), Err(Error::NotWoundDown));
+
+            let (alice_before, bob_before) = (balance(accounts.alice), balance(accounts.bob));
+            assert_eq!(contract.refund_members(), Ok(()));
+            assert_eq!(balance(accounts.alice) - alice_before, 120 * UNIT);
+            assert_eq!(balance(accounts.bob) - bob_before, 50 * UNIT);
+            assert_eq!(contract.get_prepaid(accounts.alice), 0);
+            assert_eq!(contract.held_balance, 30 * UNIT);
+            assert_eq!(contract.verify_invariants(), vec![]);
+
+            // Funds owed to others must be paid out first
+            contract.rewards_owed = 10 * UNIT;
+            assert_eq!(contract.terminate(), Err(Error::ClaimsOutstanding));
+            contract.rewards_owed = 0;
+
+            // The insurance left over is shared by Alice and Bob, the last member also receiving the existential deposit
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+            let alice_before = balance(accounts.alice);
+            let should_terminate = move || {
+                let _ = contract.terminate();
+            };
+            ink::env::test::assert_contract_termination::<crate::RaiserEnvironment, _>(should_terminate, accounts.bob, minimum_balance + 15 * UNIT);
+            assert_eq!(balance(accounts.alice) - alice_before, 15 * UNIT);
+        }
+
+        // This test checks the quorum settings of the payment phase.
+        // It verifies that payouts start with fewer members than the maximum once the enrollment deadline has passed.
+        #[ink::test]
+        fn quorum_payment_phase_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let invalid = ConfigPatch {
+                max_contributors: Some(3),
+                min_contributors: Some(4),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(invalid), Err(Error::InvalidConfig));
+
+            let patch = ConfigPatch {
+                max_contributors: Some(3),
+                min_contributors: Some(2),
+                enrollment_deadline: Some(1_000),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert!(!contract.is_payment_phase());
+            assert_eq!(contract.can_request(accounts.alice), Err(Error::NotPaymentPhase));
+
+            // After the deadline, two members are enough and enrollment closes
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            assert!(contract.is_payment_phase());
+            assert_eq!(contract.can_request(accounts.alice), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.contribute(), Err(Error::EnrollmentClosed));
+        }
+
+        // This test checks the `round_status` and `has_paid` functions.
+        // It verifies that the paid status is reported per round and kept after the round is over.
+        #[ink::test]
+        fn round_status_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.round_status(), vec![(accounts.alice, true), (accounts.bob, true)]);
+
+            // In round 2, only Bob has paid so far
+            contract.current_round += 1;
+            assert_eq!(contract.contribute_round(), Ok(()));
+            assert_eq!(contract.round_status(), vec![(accounts.alice, false), (accounts.bob, true)]);
+            assert!(contract.has_paid(accounts.alice, 1));
+            assert!(!contract.has_paid(accounts.alice, 2));
+            assert!(contract.has_paid(accounts.bob, 2));
+        }
+
+        // This test checks the `RaiserPool` trait implementation.
+        // It verifies that calls through the trait reach the same state as the inherent messages.
+        #[ink::test]
+        fn raiser_pool_trait_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(RaiserPool::contribute(&mut contract), Ok(()));
+
+            assert_eq!(RaiserPool::total_contributors(&contract), 1);
+            assert_eq!(RaiserPool::get_total_supply(&contract), 100 * UNIT);
+            assert_eq!(RaiserPool::balance_of(&contract, accounts.alice), 100 * UNIT);
+            assert_eq!(RaiserPool::get_current_round(&contract), 1);
+            assert_eq!(RaiserPool::get_next_requester(&contract), Some(accounts.alice));
+            assert!(!RaiserPool::is_payment_phase(&contract));
+        }
+
+        // This test checks the `get_balances_breakdown` function.
+        // It verifies that every native inflow and payout lands in its sub-balance and the sub-balances stay consistent.
+        #[ink::test]
+        fn get_balances_breakdown_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(1),
+                overpayment: Some(Overpayment::Prepay),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(120 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(30 * UNIT);
+            assert_eq!(pay(|| contract.sponsor_pool(SponsorDistribution::SplitEvenly)), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(5 * UNIT);
+            assert_eq!(pay(|| contract.fund_fee_reserve()), Ok(()));
+
+            assert_eq!(contract.get_balances_breakdown(), BalancesBreakdown {
+                principal: 50 * UNIT,
+                prepaid: 70 * UNIT,
+                sponsorship: 30 * UNIT,
+                fees: 5 * UNIT,
+                escrow: 0,
+                insurance: 0,
+                dust: 0,
+                surplus: 0,
+            });
+            assert_eq!(contract.verify_invariants(), vec![]);
+
+            // The payout takes the principal and the sponsorship
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            let breakdown = contract.get_balances_breakdown();
+            assert_eq!(breakdown.principal, 0);
+            assert_eq!(breakdown.sponsorship, 0);
+            assert_eq!(breakdown.total(), 75 * UNIT);
+            assert_eq!(contract.held_balance, 75 * UNIT);
+        }
+
+        // This test checks the `new_with_owner_policy` constructor.
+        // It verifies that a blocked owner cannot join and that a co-signed owner payout needs a second approver.
+        #[ink::test]
+        fn owner_policy_works() {
+            deploy_pool();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+
+            let mut blocked = Raiser::new_with_owner_policy(OwnerPolicy::Blocked);
+            assert_eq!(blocked.contribute(), Err(Error::OwnerCannotJoin));
+
+            let mut contract = Raiser::new_with_owner_policy(OwnerPolicy::CoSigned);
+            assert_eq!(contract.get_owner_policy(), OwnerPolicy::CoSigned);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::CoSignatureRequired));
+            assert_eq!(contract.cosign_request(1), Err(Error::CoSignatureRequired));
+
+            // Bob co-signs as a second approver
+            assert_eq!(contract.grant_role(Role::Approver, accounts.bob), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.cosign_request(2), Err(Error::NotContributor));
+            assert_eq!(contract.cosign_request(1), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+        }
+
+        // This test checks the contribution schedule.
+        // It verifies that contributions are only accepted in the window at the start of each period.
+        #[ink::test]
+        fn contribution_window_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let invalid = ConfigPatch {
+                period_length: Some(1_000),
+                contribution_window: Some(1_001),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(invalid), Err(Error::InvalidConfig));
+
+            let patch = ConfigPatch {
+                period_length: Some(1_000),
+                contribution_window: Some(200),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(2_500);
+            assert_eq!(contract.current_period(), 2);
+            assert!(!contract.is_contribution_window_open());
+            assert_eq!(contract.contribute(), Err(Error::OutsideContributionWindow));
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(3_100);
+            assert_eq!(contract.current_period(), 3);
+            assert_eq!(contract.contribute(), Ok(()));
+        }
+
+        // This test checks the `contribute_with_preference` and `claim_installment` functions.
+        // It verifies that a split payout is claimed one installment per round.
+        #[ink::test]
+        fn split_payout_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute_with_preference(PayoutPreference::Split)), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_payout_preference(accounts.alice), PayoutPreference::Split);
+            assert_eq!(contract.get_payout_preference(accounts.bob), PayoutPreference::LumpSum);
+
+            // Alice's payout of 200 is split over the two rounds of the cycle
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.claim_installment(), Err(Error::NothingToClaim));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_pending_payout(accounts.alice), Some(PendingPayout {
+                remaining: 200 * UNIT,
+                installments_left: 2,
+                claimed_until: 1,
+            }));
+
+            assert_eq!(contract.claim_installment(), Ok(()));
+            assert_eq!(contract.get_pending_payout(accounts.alice).map(|pending| pending.remaining), Some(100 * UNIT));
+            assert_eq!(contract.claim_installment(), Err(Error::NothingToClaim));
+
+            // The next round releases the last installment
+            contract.current_round += 1;
+            assert_eq!(contract.claim_installment(), Ok(()));
+            assert_eq!(contract.get_pending_payout(accounts.alice), None);
+            assert_eq!(contract.escrowed_total, 0);
+        }
+
+        // This test checks the common fields of the pool events.
+        // It verifies that an event carries the pool, cycle and round, and that the round is indexed as a topic.
+        #[ink::test]
+        fn event_context_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+
+            let event = ink::env::test::recorded_events().last().unwrap();
+            let created = <RequestCreated as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+            assert_eq!(created.pool_id, ink::env::account_id::<crate::RaiserEnvironment>());
+            assert_eq!(created.cycle, 1);
+            assert_eq!(created.round, 1);
+
+            let mut round_topic = [0u8; 32];
+            round_topic[..16].copy_from_slice(&1u128.to_le_bytes());
+            assert_eq!(event.topics.last().unwrap(), &round_topic.to_vec());
+        }
+
+        // This test checks the `fund_insurance` function and the coverage of defaults in `approve_request`.
+        // It verifies that an overdue installment is paid from the reserve so the payout is not reduced.
+        #[ink::test]
+        fn insurance_covers_defaults_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(2),
+                contribution_period: Some(1_000),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Alice receives the round 1 payout
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+
+            // In round 2, only Bob pays the installment
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::RoundIncomplete));
+
+            // Alice is overdue, but the reserve is empty
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_001);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::RoundIncomplete));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(80 * UNIT);
+            assert_eq!(pay(|| contract.fund_insurance()), Ok(()));
+            assert_eq!(contract.insurance_balance(), 80 * UNIT);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.insurance_balance(), 30 * UNIT);
+            assert_eq!(contract.covered_defaults_of(accounts.alice), 50 * UNIT);
+            assert_eq!(contract.covered_defaults_of(accounts.bob), 0);
+        }
+
+        // This test checks the converter settings and `contribute_round_with_asset`.
+        // The swap itself needs deployed token and router contracts and is not available off-chain.
+        #[ink::test]
+        fn contribute_round_with_asset_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.contribute_round_with_asset(accounts.django, 100, 50), Err(Error::ConverterNotSet));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_converter(Some(accounts.eve)), Err(Error::NotContractOwner));
+            assert_eq!(contract.set_accepted_asset(accounts.django, true), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_converter(Some(accounts.eve)), Ok(()));
+            assert_eq!(contract.get_converter(), Some(accounts.eve));
+            assert_eq!(contract.contribute_round_with_asset(accounts.django, 100, 50), Err(Error::AssetNotAccepted));
+
+            assert_eq!(contract.set_accepted_asset(accounts.django, true), Ok(()));
+            assert!(contract.is_accepted_asset(accounts.django));
+            assert_eq!(contract.set_accepted_asset(accounts.django, false), Ok(()));
+            assert!(!contract.is_accepted_asset(accounts.django));
+        }
+
+        // This test checks the `set_status` function and the archival of a pool.
+        // It verifies the allowed transitions and that an archived pool rejects mutating messages but stays readable.
+        #[ink::test]
+        fn set_status_works() {
+            deploy_pool();
+            let mut contract = Raiser::new_draft();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // A draft pool does not accept members
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.get_status(), PoolStatus::Draft);
+            assert_eq!(pay(|| contract.contribute()), Err(Error::PoolNotActive));
+            assert_eq!(contract.set_status(PoolStatus::Active), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_status(PoolStatus::Completed), Err(Error::InvalidStatusTransition));
+            assert_eq!(contract.set_status(PoolStatus::Active), Ok(()));
+            assert_eq!(contract.set_status(PoolStatus::Suspended), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Err(Error::PoolNotActive));
+            assert_eq!(contract.set_status(PoolStatus::Active), Ok(()));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Winding down completes the pool, which can then be archived
+            assert_eq!(contract.wind_down(), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(WIND_DOWN_DELAY);
+            assert_eq!(contract.refund_members(), Ok(()));
+            assert_eq!(contract.get_status(), PoolStatus::Completed);
+            assert_eq!(contract.set_status(PoolStatus::Archived), Ok(()));
+
+            assert_eq!(contract.set_max_contributors(5), Err(Error::PoolArchived));
+            assert_eq!(contract.terminate(), Err(Error::PoolArchived));
+            assert_eq!(contract.get_status(), PoolStatus::Archived);
+            assert_eq!(contract.total_contributors(), 1);
+        }
+
+        // This test checks the `set_price_oracle` function.
+        // It verifies that only an admin can set the oracle and that the fixed minimum applies without one.
+        // The price query itself needs a deployed oracle contract and is not available off-chain.
+        #[ink::test]
+        fn set_price_oracle_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let price_oracle = PriceOracle {
+                oracle: accounts.django,
+                fiat_amount: 1_000,
+                max_age: 60_000,
+            };
+
+            assert_eq!(contract.get_min_amount(), 50 * UNIT);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_price_oracle(Some(price_oracle)), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let free = PriceOracle { fiat_amount: 0, ..price_oracle };
+            assert_eq!(contract.set_price_oracle(Some(free)), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_price_oracle(Some(price_oracle)), Ok(()));
+            assert_eq!(contract.get_price_oracle(), Some(price_oracle));
+            assert_eq!(contract.set_price_oracle(None), Ok(()));
+            assert_eq!(contract.get_min_amount(), 50 * UNIT);
+        }
+
+        // This test checks the `check_in` function and the minimum account age.
+        // It verifies that a new member must have checked in enough blocks before joining.
+        #[ink::test]
+        fn join_constraints_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            let constraints = JoinConstraints {
+                min_account_age: 2,
+                personhood: None,
+            };
+            assert_eq!(contract.set_join_constraints(constraints), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_join_constraints(constraints), Ok(()));
+            assert_eq!(contract.get_join_constraints(), constraints);
+
+            // Bob has never checked in
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Err(Error::AccountTooNew));
+
+            assert_eq!(contract.check_in(), Ok(0));
+            ink::env::test::advance_block::<crate::RaiserEnvironment>();
+            assert_eq!(pay(|| contract.contribute()), Err(Error::AccountTooNew));
+
+            // Checking in again keeps the first block
+            assert_eq!(contract.check_in(), Ok(0));
+            ink::env::test::advance_block::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_first_seen(accounts.bob), Some(0));
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+        }
+
+        // This test checks the `Accumulate` dust policy.
+        // It verifies that the remainder of an even sponsorship split is kept in the dust balance and the accounting still adds up.
+        #[ink::test]
+        fn dust_policy_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(2),
+                dust_policy: Some(DustPolicy::Accumulate),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(10 * UNIT + 1);
+            assert_eq!(pay(|| contract.sponsor_pool(SponsorDistribution::SplitEvenly)), Ok(()));
+
+            // The extra base unit does not split evenly over 2 payouts, so it goes to the dust balance
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.dust_balance(), 1);
+            assert_eq!(contract.get_payout_history(), vec![(accounts.alice, 205 * UNIT)]);
+            assert_eq!(contract.get_balances_breakdown().dust, 1);
+            assert_eq!(contract.verify_invariants(), vec![]);
+        }
+
+        // This test checks the `create_team` function.
+        // It verifies that a team member pays the slot's installment and requests its payout, which goes to the beneficiary.
+        #[ink::test]
+        fn create_team_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Charlie is not a member, and Alice cannot add a pool member to a team
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.create_team(vec![accounts.django]), Err(Error::NotContributor));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.create_team(vec![accounts.bob]), Err(Error::InvalidTeamMember));
+            assert_eq!(contract.create_team(vec![accounts.charlie, accounts.charlie]), Err(Error::InvalidTeamMember));
+            assert_eq!(contract.create_team(vec![accounts.charlie, accounts.django]), Ok(()));
+            assert_eq!(contract.get_team(accounts.alice), vec![accounts.charlie, accounts.django]);
+            assert_eq!(contract.team_of(accounts.django), Some(accounts.alice));
+
+            // Charlie requests the payout of Alice's slot
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_payout_history(), vec![(accounts.alice, 200 * UNIT)]);
+
+            // Django pays Alice's installment for round 2
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::AlreadyPaidThisRound { round: 2, amount: 100 * UNIT }));
+            assert!(contract.has_paid(accounts.alice, 2));
+        }
+
+        // This test checks the `get_pending_requests` function.
+        // It verifies that the queue lists a pending request with its creation time and that pages past the end are empty.
+        #[ink::test]
+        fn get_pending_requests_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_pending_requests(0, 10), vec![]);
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(500);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.get_pending_requests(0, 10), vec![(1, accounts.alice, 100 * UNIT, 500)]);
+            assert_eq!(contract.get_pending_requests(1, 10), vec![]);
+            assert_eq!(contract.get_pending_requests(0, 0), vec![]);
+
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_pending_requests(0, 10), vec![]);
+        }
+
+        // This test checks that the counters and balances refuse to overflow.
+        // It verifies that a contribution pushing the total supply past `Balance::MAX` fails with an `ArithmeticOverflow` error,
+        // and that the fee reserve and the payout counters cannot overflow either.
+        #[ink::test]
+        fn arithmetic_overflow_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            contract.total_supply = Balance::MAX - 10;
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Err(Error::ArithmeticOverflow));
+
+            // A failed message reverts on-chain, but not off-chain, so the next check uses a fresh account
+            contract.total_supply = 0;
+            contract.total_weight = u128::MAX;
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute_with_weight(1), Err(Error::ArithmeticOverflow));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+
+            // Funding a full fee reserve fails instead of wrapping around
+            contract.fee_reserve = Balance::MAX;
+            assert_eq!(contract.fund_fee_reserve(), Err(Error::ArithmeticOverflow));
+
+            // So does a payout that would push the amount a winner received or the total paid out past `Balance::MAX`
+            contract.config.payout_delivery = PayoutDelivery::Pull;
+            contract.received.insert(accounts.bob, &Balance::MAX);
+            assert_eq!(contract.pay_winner(accounts.bob, 1, 100, 0).err(), Some(Error::ArithmeticOverflow));
+            contract.received.remove(accounts.bob);
+            contract.total_paid_out = Balance::MAX;
+            assert_eq!(contract.pay_winner(accounts.bob, 1, 100, 0).err(), Some(Error::ArithmeticOverflow));
+        }
+
+        // This test checks the `enroll_next_cycle` function.
+        // It verifies that an account enrolled during the payouts joins the next cycle as soon as the current one ends.
+        #[ink::test]
+        fn enroll_next_cycle_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+
+            // Bob cannot enroll before the current cycle is paying out
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.enroll_next_cycle()), Err(Error::NotPaymentPhase));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.enroll_next_cycle()), Ok(()));
+            assert_eq!(pay(|| contract.enroll_next_cycle()), Err(Error::AlreadyMember { round: 1, amount: 100 * UNIT }));
+            assert_eq!(contract.get_next_cycle_enrollments(), vec![(accounts.bob, 100 * UNIT)]);
+
+            // Alice's payout ends the cycle, and Bob is the first member of the next one
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.contribution_cycle, 2);
+            assert_eq!(contract.get_next_cycle_enrollments(), vec![]);
+            assert_eq!(contract.total_contributors(), 1);
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+        }
+
+        // This test checks that `enroll_next_cycle` and `admit_enrollments` enforce the admission gates of `contribute`.
+        // It verifies that a gated pool cannot be joined through the next cycle, and that an account failing the gates at the cycle boundary is refunded.
+        #[ink::test]
+        fn enroll_next_cycle_gates_work() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.set_join_constraints(JoinConstraints { min_account_age: 5, personhood: None }), Ok(()));
+
+            // Bob has not checked in, so he cannot enroll
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.enroll_next_cycle()), Err(Error::AccountTooNew));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(10 * UNIT);
+            assert_eq!(pay(|| contract.enroll_next_cycle()), Err(Error::LowAmount));
+
+            assert!(contract.check_in().is_ok());
+            for _ in 0..5 {
+                ink::env::test::advance_block::<crate::RaiserEnvironment>();
+            }
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.enroll_next_cycle()), Ok(()));
+            let bob_balance = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(accounts.bob).unwrap();
+
+            // The constraints tighten before the cycle ends, so Bob is refunded instead of admitted
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_join_constraints(JoinConstraints { min_account_age: 1_000, personhood: None }), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.contribution_cycle, 2);
+            assert_eq!(contract.get_next_cycle_enrollments(), vec![]);
+            assert_eq!(contract.total_contributors(), 0);
+            assert_eq!(
+                ink::env::test::get_account_balance::<crate::RaiserEnvironment>(accounts.bob),
+                Ok(bob_balance + 100 * UNIT)
+            );
+            assert_eq!(contract.get_pending_withdrawal(accounts.bob), 0);
+            assert_eq!(contract.verify_invariants(), vec![]);
+        }
+
+        // This test checks the `join` function.
+        // It verifies that the queue follows the enrollment order, not the payment order, and that payouts wait for every installment.
+        #[ink::test]
+        fn join_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+
+            // Bob joins first but pays last
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.join(1), Ok(()));
+            assert_eq!(contract.join(1), Err(Error::AlreadyMember { round: 0, amount: 0 }));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::RoundIncomplete));
+
+            // The payout is re-validated against the pot, which now includes Bob's installment
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_payout_history(), vec![(accounts.bob, 200 * UNIT)]);
+            assert_eq!(contract.get_request_details(1).unwrap().amount, 200 * UNIT);
+            assert_eq!(contract.verify_invariants(), vec![]);
+        }
+
+        // This test checks the `propose_queue_swap` and `confirm_swap` functions.
+        // It verifies that the positions only change once both members confirm.
+        #[ink::test]
+        fn queue_swap_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Charlie cannot propose a swap for others, and is not in the queue
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.propose_queue_swap(accounts.alice, accounts.bob), Err(Error::Unauthorized));
+            assert_eq!(contract.propose_queue_swap(accounts.alice, accounts.charlie), Err(Error::NotContributor));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.propose_queue_swap(accounts.alice, accounts.bob), Ok(1));
+            assert_eq!(contract.confirm_swap(1), Ok(()));
+            assert_eq!(contract.get_next_requester(), Some(accounts.alice));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.confirm_swap(1), Err(Error::Unauthorized));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.confirm_swap(1), Ok(()));
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+            assert_eq!(contract.get_queue_swap(1), None);
+            assert_eq!(contract.confirm_swap(1), Err(Error::SwapNotFound));
+        }
+
+        // This test checks the `new_successor` constructor used by `clone_pool_with_members`.
+        // It verifies that the members are pre-registered in order and that the owner, not the deployer, holds the roles.
+        // The deployment itself needs the contract code on chain and is not available off-chain.
+        #[ink::test]
+        fn new_successor_works() {
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let config = Config {
+                max_contributors: 2,
+                ..Default::default()
+            };
+
+            // The old pool deploys the successor
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            let mut contract = Raiser::new_successor(accounts.alice, config.clone(), vec![(accounts.bob, 1), (accounts.charlie, 2)]).unwrap();
+            assert_eq!(contract.clone_pool_with_members(), Err(Error::NotContractOwner));
+
+            assert_eq!(contract.get_config(), config);
+            assert_eq!(contract.owner, accounts.alice);
+            assert!(contract.has_role(Role::Admin, accounts.alice));
+            assert!(!contract.has_role(Role::Admin, accounts.django));
+            assert_eq!(contract.total_contributors(), 2);
+            assert_eq!(contract.weight_of(accounts.charlie), 2);
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+            assert!(contract.is_payment_phase());
+
+            // Members pay their first installment like after `join`
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(contract.contribute_round(), Ok(()));
+            assert_eq!(contract.get_successors(), vec![]);
+
+            // The settings and the members are validated
+            let invalid = Config {
+                min_amount: 0,
+                ..config.clone()
+            };
+            assert_eq!(Raiser::new_successor(accounts.alice, invalid, vec![]).err(), Some(Error::InvalidConfig));
+            let members = vec![(accounts.bob, 1), (accounts.charlie, 1), (accounts.eve, 1)];
+            assert_eq!(Raiser::new_successor(accounts.alice, config.clone(), members).err(), Some(Error::InvalidMaxContributors));
+            let unbounded = Config {
+                max_contributors: 0,
+                ..config.clone()
+            };
+            let members = (0..=MAX_POOL_SIZE as u8).map(|index| (AccountId::from([index; 32]), 1)).collect();
+            assert_eq!(Raiser::new_successor(accounts.alice, unbounded, members).err(), Some(Error::InvalidMaxContributors));
+            for weight in [0, MAX_WEIGHT + 1] {
+                assert_eq!(Raiser::new_successor(accounts.alice, config.clone(), vec![(accounts.bob, weight)]).err(), Some(Error::InvalidWeight));
+            }
+            let members = vec![(accounts.frank, 1), (accounts.frank, 2)];
+            assert_eq!(
+                Raiser::new_successor(accounts.alice, config, members).err(),
+                Some(Error::AlreadyMember { round: 0, amount: 0 })
+            );
+        }
+
+        // This test checks the `withdraw_payout` function.
+        // It verifies that a pull-delivery pool credits the payout instead of transferring it, and that only the recipient can withdraw it.
+        #[ink::test]
+        fn withdraw_payout_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(2),
+                payout_delivery: Some(PayoutDelivery::Pull),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Alice's payout is credited as a pending withdrawal instead of being transferred
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_pending_withdrawal(accounts.alice), 200 * UNIT);
+            assert_eq!(contract.escrowed_total, 200 * UNIT);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.withdraw_payout(), Err(Error::NothingToClaim));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.withdraw_payout(), Ok(()));
+            assert_eq!(contract.get_pending_withdrawal(accounts.alice), 0);
+            assert_eq!(contract.escrowed_total, 0);
+            assert_eq!(contract.withdraw_payout(), Err(Error::NothingToClaim));
+        }
+
+        // This test checks the existential deposit checks of `contribute` and `contribute_round`.
+        // It verifies that contributions, and excesses to refund, below the existential deposit of the chain are rejected.
+        #[ink::test]
+        fn existential_deposit_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_existential_deposit(), minimum_balance);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                min_amount: Some(minimum_balance / 2),
+                overpayment: Some(Overpayment::Refund),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+
+            // A contribution above the minimum amount but below the existential deposit is rejected
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(minimum_balance - 1);
+            assert_eq!(pay(|| contract.contribute()), Err(Error::BelowExistentialDeposit));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(2 * minimum_balance);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.balance_of(accounts.alice), minimum_balance / 2);
+
+            // An excess of half the existential deposit would be refunded below it
+            contract.current_round += 1;
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(minimum_balance);
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::BelowExistentialDeposit));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(3 * minimum_balance / 2);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+        }
+
+        // This test checks the existential deposit of split payouts.
+        // It verifies that a split payout has no more installments than keep each one at or above the existential deposit.
+        #[ink::test]
+        fn split_payout_existential_deposit_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                min_amount: Some(minimum_balance),
+                max_contributors: Some(3),
+                payout_lockup: Some(true),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(minimum_balance);
+            assert_eq!(pay(|| contract.contribute_with_preference(PayoutPreference::Split)), Ok(()));
+            for member in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+
+            // Two installments are locked up, so the single existential deposit left is paid in one installment instead of three
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_pending_payout(accounts.alice), Some(PendingPayout {
+                remaining: minimum_balance,
+                installments_left: 1,
+                claimed_until: 1,
+            }));
+        }
+
+        // This test checks the existential deposit of co-winners.
+        // It verifies that a co-winner whose share would be below the existential deposit is not paid and keeps their slot.
+        #[ink::test]
+        fn co_winner_existential_deposit_works() {
+            deploy_pool();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+            let config = Config {
+                min_amount: minimum_balance,
+                max_contributors: 3,
+                winners_per_round: 2,
+                ..Default::default()
+            };
+            let mut contract = Raiser::new_with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(minimum_balance);
+            for member in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+
+            // With a pot of a single existential deposit, half of it would be below the existential deposit, so Bob is not paid with Alice
+            contract.round_pot = minimum_balance;
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_payout_history(), vec![(accounts.alice, minimum_balance)]);
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+        }
+
+        // This test checks the `CycleStats` event emitted when a cycle ends.
+        // It verifies that the pot, the member count and the duration of the finished cycle are reported.
+        #[ink::test]
+        fn cycle_stats_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Alice completes a cycle alone, five seconds after it started
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(5_000);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.contribution_cycle, 2);
+
+            let signature = <CycleStats as ink::env::Event>::SIGNATURE_TOPIC.unwrap();
+            let event = ink::env::test::recorded_events()
+                .find(|event| event.topics.first() == Some(&signature.to_vec()))
+                .unwrap();
+            let stats = <CycleStats as scale::Decode>::decode(&mut &event.data[..]).unwrap();
+            assert_eq!(stats.total_pot, 100 * UNIT);
+            assert_eq!(stats.members, 1);
+            assert_eq!(stats.avg_contribution, 100 * UNIT);
+            assert_eq!(stats.duration_ms, 5_000);
+            assert_eq!(stats.defaults, 0);
+            assert_eq!(stats.cycle, 1);
+        }
+
+        // This test checks the `force_approve` function.
+        // It verifies that a request left pending past the approval timeout can be approved, or expired, by anyone.
+        #[ink::test]
+        fn force_approve_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(2),
+                approval_timeout: Some(1_000),
+                approval_timeout_action: Some(ApprovalTimeoutAction::Expire),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Bob cannot resolve Alice's request before the timeout
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.force_approve(1), Err(Error::TimeoutNotReached));
+            assert_eq!(contract.force_approve(2), Err(Error::NotContributor));
+
+            // After the timeout, the request expires
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            assert_eq!(contract.force_approve(1), Ok(()));
+            assert_eq!(contract.get_request_details(1).unwrap().status, RequestStatus::Expired);
+            assert_eq!(contract.requests.len(), 0);
+
+            // With the `ForceApprove` action, Alice's new request is paid without an approver
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                approval_timeout_action: Some(ApprovalTimeoutAction::ForceApprove),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(2_000);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.force_approve(2), Ok(()));
+            assert_eq!(contract.get_request_details(2).unwrap().status, RequestStatus::Approved);
+            assert_eq!(contract.completed_payouts, 1);
+        }
+
+        // This test checks the `set_attestation_registry` function.
+        // It verifies that only an admin can set the registry and that a pool without one accepts anyone.
+        // The registry query itself needs a deployed registry contract and is not available off-chain.
+        #[ink::test]
+        fn set_attestation_registry_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_attestation_registry(), None);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_attestation_registry(Some(accounts.django)), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_attestation_registry(Some(accounts.django)), Ok(()));
+            assert_eq!(contract.get_attestation_registry(), Some(accounts.django));
+            assert_eq!(contract.set_attestation_registry(None), Ok(()));
+            assert_eq!(contract.can_contribute(accounts.bob, 100 * UNIT), Ok(()));
+        }
+
+        // This test checks the `get_payout_history_from` and `get_contribution_history` functions.
+        // It verifies that a client can fetch entries incrementally and resume from the returned cursor after new entries are appended.
+        #[ink::test]
+        fn history_cursors_work() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_payout_history_from(0, 10), (vec![], 0));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            let (payouts, cursor) = contract.get_payout_history_from(0, 10);
+            assert_eq!(payouts, vec![(accounts.alice, 200 * UNIT)]);
+            assert_eq!(cursor, 1);
+            assert_eq!(contract.get_payout_history_from(cursor, 10), (vec![], 1));
+
+            // Alice's receipts are fetched one at a time, then resumed after a new installment
+            let (receipts, cursor) = contract.get_contribution_history(accounts.alice, 0, 1);
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].amount, 100 * UNIT);
+            assert_eq!(cursor, 1);
+            assert_eq!(contract.get_contribution_history(accounts.alice, cursor, 10), (vec![], 1));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            let (receipts, cursor) = contract.get_contribution_history(accounts.alice, cursor, 10);
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].round, 2);
+            assert_eq!(cursor, 2);
+        }
+
+        // This test checks that `update_config` queues economic changes while a cycle is underway.
+        // It verifies that the minimum amount only changes at the next cycle boundary, while other settings apply at once.
+        #[ink::test]
+        fn pending_config_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(1), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            let patch = ConfigPatch {
+                min_amount: Some(75 * UNIT),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch.clone()), Ok(()));
+            assert_eq!(contract.get_config().min_amount, 50 * UNIT);
+            assert_eq!(contract.pending_config(), Some(patch));
+
+            // The claim window applies right away, the referral bonus joins the queued minimum amount
+            let patch = ConfigPatch {
+                claim_window: Some(1_000),
+                referral_bonus: Some(5 * UNIT),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            assert_eq!(contract.get_config().claim_window, 1_000);
+            assert_eq!(contract.get_config().referral_bonus, 0);
+            let queued = ConfigPatch {
+                min_amount: Some(75 * UNIT),
+                referral_bonus: Some(5 * UNIT),
+                ..Default::default()
+            };
+            assert_eq!(contract.pending_config(), Some(queued));
+
+            // The queued change applies once the cycle ends
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.contribution_cycle, 2);
+            assert_eq!(contract.get_config().min_amount, 75 * UNIT);
+            assert_eq!(contract.get_config().referral_bonus, 5 * UNIT);
+            assert_eq!(contract.get_config().claim_window, 1_000);
+            assert_eq!(contract.pending_config(), None);
+
+            // A queued change that is no longer valid is dropped at the end of the cycle
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            contract.pending_config = Some(ConfigPatch {
+                min_amount: Some(0),
+                ..Default::default()
+            });
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.contribution_cycle, 3);
+            assert_eq!(contract.get_config().min_amount, 75 * UNIT);
+            assert_eq!(contract.pending_config(), None);
+        }
+
+        // This test checks the `set_metadata_uri` function.
+        // It verifies that only an admin can set the metadata and that overly long URIs are rejected.
+        #[ink::test]
+        fn set_metadata_uri_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let uri = String::from("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+            let hash = Hash::from([9; 32]);
+            assert_eq!(contract.get_metadata(), (String::new(), None));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_metadata_uri(uri.clone(), hash), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_metadata_uri(uri.clone(), hash), Ok(()));
+            assert_eq!(contract.get_metadata(), (uri.clone(), Some(hash)));
+
+            let too_long = "a".repeat(MAX_METADATA_URI_LEN + 1);
+            assert_eq!(contract.set_metadata_uri(too_long, hash), Err(Error::MetadataUriTooLong));
+            assert_eq!(contract.get_metadata(), (uri, Some(hash)));
+        }
+
+        // This test checks the `set_automation` function.
+        // It verifies that an operator with the `AUTOMATE_APPROVE` permission can approve requests until it is revoked.
+        #[ink::test]
+        fn set_automation_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_automation(accounts.django, AUTOMATE_APPROVE), Err(Error::NotContractOwner));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_automation(accounts.django, 1 << 7), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_automation(accounts.django, AUTOMATE_APPROVE), Ok(()));
+            assert_eq!(contract.get_automation(accounts.django), AUTOMATE_APPROVE);
+
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+
+            // Eve holds neither the `Approver` role nor the permission
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.eve);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::Unauthorized));
+
+            // Django approves without holding the `Approver` role
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.completed_payouts, 1);
+
+            // Once revoked, Django is an ordinary account again
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_automation(accounts.django, 0), Ok(()));
+            assert_eq!(contract.get_automation(accounts.django), 0);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Err(Error::Unauthorized));
+        }
+
+        // This test checks the `cover_for` function.
+        // It verifies that only an overdue installment of another member can be covered,
+        // and that the installment plus interest is deducted from the payout of the covered member and credited to the coverer.
+        #[ink::test]
+        fn cover_for_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(2),
+                cover_interest_bps: Some(1_000),
+                contribution_period: Some(1_000),
+                grace_period: Some(500),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+
+            // Charlie is not a member, so only Alice can cover Bob's missed installment
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(pay(|| contract.cover_for(accounts.bob)), Err(Error::NotContributor));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(pay(|| contract.cover_for(accounts.alice)), Err(Error::CannotCoverSelf));
+
+            // Bob has not defaulted until the deadline plus the grace period has passed
+            let overdue = contract.get_contribution_deadline() + 500;
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(overdue);
+            assert_eq!(pay(|| contract.cover_for(accounts.bob)), Err(Error::RoundIncomplete));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(overdue + 1);
+            assert_eq!(pay(|| contract.cover_for(accounts.bob)), Ok(()));
+            assert_eq!(contract.get_cover_debts(accounts.bob), vec![(accounts.alice, 55 * UNIT)]);
+            assert!(contract.all_contributed_this_round());
+
+            // Bob's payout of the round 2 pot of 100 is reduced by the 55 Bob owes Alice
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_request_details(contract.next_request_id - 1).unwrap().amount, 100 * UNIT);
+            assert_eq!(contract.total_paid_out, (200 + 45) * UNIT);
+            assert_eq!(contract.get_cover_debts(accounts.bob), vec![]);
+            assert_eq!(contract.get_pending_withdrawal(accounts.alice), 55 * UNIT);
+        }
+
+        // This test checks the `estimated_payout_round` and `estimated_payout_timestamp` functions.
+        // It verifies that the estimates follow the payout queue and the configured round length.
+        #[ink::test]
+        fn estimated_payout_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(3), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            assert_eq!(contract.estimated_payout_round(accounts.alice), Some(1));
+            assert_eq!(contract.estimated_payout_round(accounts.charlie), Some(3));
+            assert_eq!(contract.estimated_payout_round(accounts.django), None);
+            assert_eq!(contract.estimated_payout_timestamp(accounts.charlie), None);
+
+            // With a one-day contribution period, Charlie is paid two days after the round opened
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                contribution_period: Some(86_400_000),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            assert_eq!(contract.estimated_payout_timestamp(accounts.alice), Some(0));
+            assert_eq!(contract.estimated_payout_timestamp(accounts.charlie), Some(172_800_000));
+        }
+
+        // This test checks the topics of an event about a member.
+        // It verifies that the member and the cycle are indexed before the round, so subscriptions can filter on them.
+        #[ink::test]
+        fn member_event_topics_work() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.join(1), Ok(()));
+
+            let event = ink::env::test::recorded_events().last().unwrap();
+            let mut cycle_topic = [0u8; 32];
+            cycle_topic[..16].copy_from_slice(&1u128.to_le_bytes());
+            let member_topic: &[u8] = accounts.bob.as_ref();
+            assert_eq!(event.topics.len(), 4);
+            assert_eq!(event.topics[1], member_topic.to_vec());
+            assert_eq!(event.topics[2], cycle_topic.to_vec());
+        }
+
+        // This test checks the owner impeachment process.
+        // It verifies that the owner is only replaced once a supermajority of the membership weight voted within the window.
+        #[ink::test]
+        fn impeachment_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(3), Ok(()));
+            assert_eq!(contract.set_treasurer(Some(accounts.eve)), Ok(()));
+            assert_eq!(contract.grant_role(Role::Admin, accounts.frank), Ok(()));
+            assert_eq!(contract.grant_role(Role::Upgrader, accounts.frank), Ok(()));
+            assert_eq!(contract.set_automation(accounts.frank, AUTOMATE_APPROVE), Ok(()));
+            assert_eq!(contract.set_automation(accounts.django, AUTOMATE_APPROVE), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            for member in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(contract.contribute(), Ok(()));
+            }
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.propose_impeachment(accounts.django), Err(Error::NotContributor));
+
+            // Bob proposes Django, but one vote out of three is not a supermajority
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.propose_impeachment(accounts.django), Ok(1));
+            assert_eq!(contract.propose_impeachment(accounts.django), Err(Error::ImpeachmentActive));
+            assert_eq!(contract.vote_impeachment(1), Err(Error::AlreadyVoted));
+            assert_eq!(contract.execute_impeachment(1), Err(Error::QuorumNotReached));
+
+            // Members leaving after the proposal opened do not lower the quorum below the electorate it was opened with
+            contract.total_weight = 1;
+            assert_eq!(contract.execute_impeachment(1), Err(Error::QuorumNotReached));
+            contract.total_weight = 3;
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.vote_impeachment(1), Ok(()));
+            assert_eq!(contract.execute_impeachment(1), Ok(()));
+            assert_eq!(contract.owner, accounts.django);
+            assert!(contract.has_role(Role::Admin, accounts.django));
+            assert!(!contract.has_role(Role::Admin, accounts.alice));
+            assert!(!contract.has_role(Role::Approver, accounts.eve));
+            assert_eq!(contract.get_treasurer(), None);
+
+            // The admin and the keeper appointed by Alice lose their powers, the new owner keeps theirs
+            assert!(!contract.has_role(Role::Admin, accounts.frank));
+            assert!(!contract.has_role(Role::Upgrader, accounts.frank));
+            assert_eq!(contract.get_automation(accounts.frank), 0);
+            assert_eq!(contract.get_automation(accounts.django), AUTOMATE_APPROVE);
+            assert!(contract.get_impeachment().unwrap().executed);
+            assert_eq!(contract.execute_impeachment(1), Err(Error::ImpeachmentNotFound));
+        }
+
+        // This test checks that an impeachment proposal does not outlive the contribution cycle it was opened in.
+        // It verifies that the proposal is cancelled when the cycle ends, instead of passing against an empty membership.
+        #[ink::test]
+        fn impeachment_cancelled_on_cycle_end_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+
+            // Bob's vote is one of two, so the proposal stays open
+            assert_eq!(contract.propose_impeachment(accounts.frank), Ok(1));
+            assert_eq!(contract.get_impeachment().unwrap().electorate, 2);
+            assert_eq!(contract.execute_impeachment(1), Err(Error::QuorumNotReached));
+
+            // Both payouts end the cycle, and no one enrolled in the next one
+            for round in 1..=2 {
+                let next = contract.get_next_requester().unwrap();
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(next);
+                assert_eq!(contract.request_token(), Ok(()));
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+                assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+                if round == 1 {
+                    for member in [accounts.alice, accounts.bob] {
+                        ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                        assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+                    }
+                }
+            }
+            assert_eq!(contract.contribution_cycle, 2);
+            assert_eq!(contract.total_weight, 0);
+
+            let proposal = contract.get_impeachment().unwrap();
+            assert!(proposal.cancelled);
+            assert!(!proposal.executed);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.frank);
+            assert_eq!(contract.execute_impeachment(1), Err(Error::ImpeachmentNotFound));
+            assert_eq!(contract.owner, accounts.alice);
+            assert!(!contract.has_role(Role::Upgrader, accounts.frank));
+
+            // Without the cancellation, an empty electorate would still never reach the quorum
+            contract.impeachment = Some(Impeachment { electorate: 0, cancelled: false, ..proposal });
+            assert_eq!(contract.execute_impeachment(1), Err(Error::QuorumNotReached));
+        }
+
+        // This test checks the `new_with_config` constructor.
+        // It verifies that a pool can be deployed with all of its settings and that invalid settings are rejected.
+        #[ink::test]
+        fn new_with_config_works() {
+            let config = Config {
+                min_amount: 75 * UNIT,
+                max_contributors: 4,
+                payout_order: PayoutOrder::NeedScore,
+                contribution_period: 86_400_000,
+                claim_window: 3_600_000,
+                ..Default::default()
+            };
+            let contract = Raiser::new_with_config(config.clone()).unwrap();
+            assert_eq!(contract.get_config(), config);
+            assert_eq!(contract.get_max_contributors(), 4);
+
+            let invalid = Config {
+                min_amount: 0,
+                ..Default::default()
+            };
+            assert_eq!(Raiser::new_with_config(invalid).err(), Some(Error::InvalidConfig));
+            let too_large = Config {
+                max_contributors: MAX_POOL_SIZE + 1,
+                ..Default::default()
+            };
+            assert_eq!(Raiser::new_with_config(too_large).err(), Some(Error::InvalidMaxContributors));
+        }
+
+        // This test checks how a rejected contribution is reported.
+        // It verifies that the returned error is the reason, that `can_contribute` predicts it and that no event is emitted.
+        #[ink::test]
+        fn contribution_rejected_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(10 * UNIT);
+            let emitted = ink::env::test::recorded_events().count();
+            assert_eq!(contract.can_contribute(accounts.bob, 10 * UNIT), Err(Error::LowAmount));
+            assert_eq!(contract.contribute(), Err(Error::LowAmount));
+            assert_eq!(ink::env::test::recorded_events().count(), emitted);
+        }
+
+        // This test checks the payout lock-up.
+        // It verifies that the installments a recipient still owes are held back from their payout and paid automatically in the next round.
+        #[ink::test]
+        fn payout_lockup_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                max_contributors: Some(3),
+                payout_lockup: Some(true),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            for member in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+
+            // Alice owes two more installments of 50, so 100 of the pot of 300 is held back
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.received.get(accounts.alice), Some(200 * UNIT));
+
+            // The installment of round 2 is already paid from the lock-up
+            assert!(contract.has_contributed_this_round(accounts.alice));
+            assert_eq!(contract.get_lockup(accounts.alice), 50 * UNIT);
+            assert_eq!(contract.escrowed_total, 50 * UNIT);
+            assert_eq!(pay(|| contract.contribute_round()), Err(Error::AlreadyPaidThisRound { round: 2, amount: 50 * UNIT }));
+        }
+
+        // This test checks the display names of members.
+        // It verifies that only members can register a bounded name and that it is listed with the contributors.
+        #[ink::test]
+        fn set_alias_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_alias(b"alice".to_vec()), Err(Error::NotContributor));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_alias(vec![b'a'; MAX_ALIAS_LEN + 1]), Err(Error::AliasTooLong));
+            assert_eq!(contract.set_alias(b"alice".to_vec()), Ok(()));
+            assert_eq!(contract.get_alias(accounts.alice), Some(b"alice".to_vec()));
+            assert_eq!(
+                contract.get_contributors_with_aliases(),
+                vec![(accounts.alice, 100 * UNIT, Some(b"alice".to_vec())), (accounts.bob, 100 * UNIT, None)]
+            );
+
+            // An empty name removes the display name
+            assert_eq!(contract.set_alias(Vec::new()), Ok(()));
+            assert_eq!(contract.get_alias(accounts.alice), None);
+        }
+
+        // This test checks the distribution of surplus funds.
+        // It verifies that the untracked funds are split among the members by weight and that each member claims their share once.
+        #[ink::test]
+        fn distribute_surplus_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let contract_id = ink::env::account_id::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance);
+            assert_eq!(contract.distribute_surplus(), Err(Error::NoUntrackedBalance));
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance + 100 * UNIT + 1);
+            assert_eq!(contract.distribute_surplus(), Err(Error::NoMembers));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // 100 and a base unit arrive on top of the 200 contributed, and the base unit left over stays untracked
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(contract_id, minimum_balance + 300 * UNIT + 1);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.distribute_surplus(), Ok(()));
+            assert_eq!(contract.get_surplus_claim(accounts.alice), 50 * UNIT);
+            assert_eq!(contract.get_surplus_claim(accounts.bob), 50 * UNIT);
+            assert_eq!(contract.get_unclaimed_surplus(), 100 * UNIT);
+            assert_eq!(contract.get_untracked_balance(), 1);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.claim_surplus(), Ok(()));
+            assert_eq!(contract.claim_surplus(), Err(Error::NothingToClaim));
+            assert_eq!(contract.get_unclaimed_surplus(), 50 * UNIT);
+        }
+
+        // This test checks the TVL cap.
+        // It verifies that only an admin can set it and that contributions above it are rejected.
+        #[ink::test]
+        fn set_tvl_cap_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_tvl_cap(Some(150 * UNIT)), Err(Error::NotContractOwner));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_tvl_cap(Some(150 * UNIT)), Ok(()));
+            assert_eq!(contract.get_tvl_cap(), Some(150 * UNIT));
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Err(Error::CapExceeded));
+
+            // Removing the cap lets the contribution through
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_tvl_cap(None), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+        }
+
+        // This test checks the failed-payout queue.
+        // It verifies that a queued payout is only redirected once both an admin and the recipient agree on the destination.
+        #[ink::test]
+        fn redirect_payout_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            contract.failed_payouts.insert(1, &FailedPayout {
+                recipient: accounts.bob,
+                amount: 100 * UNIT,
+                redirect_to: None,
+                owner_consent: false,
+                recipient_consent: false,
+            });
+            contract.failed_payout_ids.push(1);
+            contract.escrowed_total = 100 * UNIT;
+            contract.held_balance = 100 * UNIT;
+            move_balance(accounts.frank, ink::env::account_id::<crate::RaiserEnvironment>(), 100 * UNIT);
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.redirect_payout(1, accounts.charlie), Err(Error::Unauthorized));
+            assert_eq!(contract.retry_payout(2), Err(Error::FailedPayoutNotFound));
+
+            // The owner and Bob disagree at first, so the payout stays queued
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.redirect_payout(1, accounts.django), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.redirect_payout(1, accounts.eve), Ok(()));
+            assert!(contract.get_failed_payout(1).is_some());
+
+            // Any admin can consent for the pool
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.grant_role(Role::Admin, accounts.charlie), Ok(()));
+            let requests: Vec<(u128, RequestInfo)> = Vec::new();
+            let failed_payouts = vec![(1u128, contract.get_failed_payout(1).unwrap())];
+            assert_eq!(contract.export_state(6), Ok(scale::Encode::encode(&(requests, failed_payouts))));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.redirect_payout(1, accounts.eve), Ok(()));
+            assert_eq!(contract.get_failed_payout(1), None);
+            assert_eq!(contract.failed_payout_ids, vec![]);
+            assert_eq!(contract.escrowed_total, 0);
+        }
+
+        // This test checks the member activity heartbeat.
+        // It verifies that interactions refresh the last activity of a member and that idle members are reported as inactive.
+        #[ink::test]
+        fn inactive_members_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+            let joined_at = ink::env::block_timestamp::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_last_active_at(accounts.bob), Some(joined_at));
+            assert_eq!(contract.inactive_members(1_000), vec![]);
+
+            // Bob registers a name a while later, while Alice stays idle
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(joined_at + 5_000);
+            assert_eq!(contract.set_alias(b"bob".to_vec()), Ok(()));
+            assert_eq!(contract.inactive_members(1_000), vec![accounts.alice]);
+            assert_eq!(contract.inactive_members(10_000), vec![]);
+        }
+
+        // This test checks the integrity of the payout queue across swaps, deferrals and removals.
+        // It verifies that every remaining member keeps exactly one slot in the expected order and that the counters stay consistent.
+        #[ink::test]
+        fn queue_integrity_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+
+            // Bob and Django swap, then Alice defers to the back of the queue
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.propose_queue_swap(accounts.bob, accounts.django), Ok(1));
+            assert_eq!(contract.confirm_swap(1), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.confirm_swap(1), Ok(()));
+            assert_eq!(contract.queue_accounts(), vec![accounts.alice, accounts.django, accounts.charlie, accounts.bob]);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.defer_turn(), Ok(()));
+            assert_eq!(contract.queue_accounts(), vec![accounts.django, accounts.charlie, accounts.bob, accounts.alice]);
+
+            // Removing Charlie from the middle keeps the order of the others
+            assert_eq!(contract.remove_contributor(accounts.charlie), Ok(()));
+            assert_eq!(contract.queue_accounts(), vec![accounts.django, accounts.bob, accounts.alice]);
+            assert_eq!(contract.get_next_requester(), Some(accounts.django));
+            assert_eq!(contract.estimated_payout_round(accounts.alice), Some(contract.current_round + 2));
+            assert_eq!(contract.estimated_payout_round(accounts.charlie), None);
+            assert_eq!(contract.total_contributors(), 3);
+            assert_eq!(contract.queue_len() as u128 + contract.completed_payouts, contract.total_weight);
+            assert_eq!(contract.queued_slots.get(accounts.charlie), None);
+
+            // Paying out the front slot only moves the head of the queue and frees the slot
+            let head = contract.queue_head;
+            assert_eq!(contract.set_max_contributors(3), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.request_token(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.queue_head, head + 1);
+            assert_eq!(contract.queue.get(head), None);
+            assert_eq!(contract.queue_accounts(), vec![accounts.bob, accounts.alice]);
+            assert!(!contract.queue_contains(accounts.django));
+            assert_eq!(contract.queued_slots.get(accounts.bob), Some(1));
+        }
+
+        // This test checks the limits of `create_pool`.
+        // It verifies that only an admin sets them and that the fee, the per-account limit and the config are checked before deploying.
+        // The deployment itself needs the contract code on chain and is not available off-chain.
+        #[ink::test]
+        fn create_pool_limits_work() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_pool_creation_limits(), (0, 1));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_pool_creation_limits(10 * UNIT, 2), Err(Error::NotContractOwner));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_pool_creation_limits(10 * UNIT, 2), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(5 * UNIT);
+            assert_eq!(contract.create_pool(Config::default()), Err(Error::LowAmount));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(10 * UNIT);
+            let invalid = Config {
+                min_amount: 0,
+                ..Default::default()
+            };
+            assert_eq!(contract.create_pool(invalid), Err(Error::InvalidConfig));
+
+            contract.pools_created.insert(accounts.charlie, &2);
+            assert_eq!(contract.create_pool(Config::default()), Err(Error::PoolLimitReached));
+            assert_eq!(contract.get_created_pools(), vec![]);
+        }
+
+        // This test checks the enforcement of arbitration rulings.
+        // It verifies that a disputed payout is frozen and that only the arbitrator can split it between the recipient and the pool.
+        // Escalating the dispute calls the arbitration contract and is not available off-chain.
+        #[ink::test]
+        fn resolve_dispute_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_arbitrator(Some(accounts.django), 10 * UNIT), Err(Error::NotContractOwner));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(pay(|| contract.raise_dispute(accounts.bob)), Err(Error::ArbitratorNotSet));
+            assert_eq!(contract.set_arbitrator(Some(accounts.django), 10 * UNIT), Ok(()));
+            assert_eq!(pay(|| contract.raise_dispute(accounts.bob)), Err(Error::NothingToClaim));
+
+            // Alice disputes the escrowed payout of Bob, and the arbitrator opened the case
+            contract.escrow.insert(accounts.bob, &(100 * UNIT, 0));
+            contract.escrowed_total = 100 * UNIT;
+            contract.disputes.insert(1, &Dispute {
+                claimant: accounts.alice,
+                recipient: accounts.bob,
+                amount: 100 * UNIT,
+                ruling: None,
+            });
+            contract.disputed_escrow.insert(accounts.bob, &1);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.claim(), Err(Error::PayoutDisputed));
+            assert_eq!(contract.resolve_dispute(1, Ruling::Release), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.django);
+            assert_eq!(contract.resolve_dispute(1, Ruling::Split(10_001)), Err(Error::InvalidRuling));
+            assert_eq!(contract.resolve_dispute(1, Ruling::Split(7_500)), Ok(()));
+            assert_eq!(contract.resolve_dispute(1, Ruling::Refund), Err(Error::DisputeNotFound));
+            assert_eq!(contract.get_escrow(accounts.bob), Some((75 * UNIT, 0)));
+            assert_eq!(contract.unallocated_sponsorship, 25 * UNIT);
+            assert_eq!(contract.get_dispute(1).map(|dispute| dispute.ruling), Some(Some(Ruling::Split(7_500))));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.claim(), Ok(()));
+        }
+
+        // This test checks the on-time contributions leaderboard.
+        // It verifies that only installments paid by the deadline score and that the ranking follows the scores.
+        #[ink::test]
+        fn leaderboard_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let patch = ConfigPatch {
+                contribution_period: Some(1_000),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Bob pays round 2 on time, Alice pays late
+            contract.current_round = 2;
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(500);
+            assert_eq!(contract.contribute_round(), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(2_000);
+            assert_eq!(contract.contribute_round(), Ok(()));
+            assert_eq!(contract.leaderboard(10), vec![(accounts.bob, 1)]);
+
+            // Alice pays the next two rounds on time and overtakes Bob
+            for round in [3, 4] {
+                contract.current_round = round;
+                contract.round_opened_at = round as u64 * 1_000;
+                ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(round as u64 * 1_000 + 500);
+                assert_eq!(contract.contribute_round(), Ok(()));
+            }
+            assert_eq!(contract.leaderboard(10), vec![(accounts.alice, 2), (accounts.bob, 1)]);
+            assert_eq!(contract.leaderboard(1), vec![(accounts.alice, 2)]);
+        }
+
+        // This test checks the `debug_dump` function.
+        // It verifies that only the owner can read the storage sections and that each decodes to the documented type.
+        #[ink::test]
+        fn debug_dump_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            let members = contract.debug_dump(StorageSection::Members).unwrap();
+            let members = <Vec<(AccountId, Balance, u8, u128, Balance)> as scale::Decode>::decode(&mut &members[..]).unwrap();
+            assert_eq!(members, vec![(accounts.alice, contract.balance_of(accounts.alice), 1, 1, contract.get_prepaid(accounts.alice))]);
+            let queue = contract.debug_dump(StorageSection::Queue).unwrap();
+            assert_eq!(<Vec<AccountId> as scale::Decode>::decode(&mut &queue[..]).unwrap(), vec![accounts.alice]);
+            let config = contract.debug_dump(StorageSection::Config).unwrap();
+            let config = <(Config, Option<ConfigPatch>) as scale::Decode>::decode(&mut &config[..]).unwrap();
+            assert_eq!(config, (contract.get_config(), None));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.debug_dump(StorageSection::Requests), Err(Error::NotContractOwner));
+        }
+
+        // This test checks the deadline extension vote.
+        // It verifies that the extension is bounded, needs a simple majority of the membership weight and is limited to one per round.
+        #[ink::test]
+        fn vote_extension_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(contract.contribute(), Ok(()));
+            }
+            let deadline = contract.get_contribution_deadline();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.propose_extension(MAX_DEADLINE_EXTENSION + 1), Err(Error::InvalidExtension));
+            assert_eq!(contract.propose_extension(1_000), Ok(1));
+            assert_eq!(contract.get_extension().unwrap().electorate, 3);
+            assert_eq!(contract.vote_extension(), Err(Error::AlreadyVoted));
+            assert_eq!(contract.get_contribution_deadline(), deadline);
+
+            // Bob's vote makes two out of three
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.vote_extension(), Ok(()));
+            assert_eq!(contract.get_contribution_deadline(), deadline + 1_000);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.vote_extension(), Err(Error::ExtensionNotFound));
+            assert_eq!(contract.propose_extension(1_000), Err(Error::ExtensionActive));
+
+            // The extension only applies to its round
+            contract.current_round += 1;
+            assert_eq!(contract.get_contribution_deadline(), deadline);
+        }
+
+        // This test checks the remote-chain address book.
+        // It verifies that only members register a beneficiary and only an admin sets the XCM gateway.
+        // Routing a payout calls the gateway contract and is not available off-chain.
+        #[ink::test]
+        fn set_remote_beneficiary_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let beneficiary = RemoteBeneficiary {
+                para_id: 2_000,
+                account: [7; 32],
+            };
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_remote_beneficiary(Some(beneficiary)), Err(Error::NotContributor));
+            assert_eq!(contract.set_xcm_gateway(Some(accounts.django)), Err(Error::NotContractOwner));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.set_remote_beneficiary(Some(beneficiary)), Ok(()));
+            assert_eq!(contract.get_remote_beneficiary(accounts.bob), Some(beneficiary));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_xcm_gateway(Some(accounts.django)), Ok(()));
+            assert_eq!(contract.get_xcm_gateway(), Some(accounts.django));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.set_remote_beneficiary(None), Ok(()));
+            assert_eq!(contract.get_remote_beneficiary(accounts.bob), None);
+        }
+
+        // This test checks the Merkle commitment of the member set.
+        // It verifies that members can prove their membership against the root, and that removing a member invalidates their proof.
+        #[ink::test]
+        fn member_root_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let empty_root = contract.get_member_root();
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+            assert_ne!(contract.get_member_root(), empty_root);
+
+            let (index, siblings) = contract.get_member_proof(accounts.bob).unwrap();
+            assert_eq!(index, 1);
+            assert!(contract.verify_member_proof(accounts.bob, index, siblings.clone()));
+            assert!(!contract.verify_member_proof(accounts.charlie, index, siblings.clone()));
+            assert!(!contract.verify_member_proof(accounts.bob, 0, siblings.clone()));
+            assert_eq!(contract.get_member_proof(accounts.charlie), None);
+
+            // Bob's removal changes the root, and Charlie reuses Bob's leaf
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.remove_contributor(accounts.bob), Ok(()));
+            assert!(!contract.verify_member_proof(accounts.bob, index, siblings));
+            let (index, siblings) = contract.get_member_proof(accounts.alice).unwrap();
+            assert!(contract.verify_member_proof(accounts.alice, index, siblings));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.get_member_proof(accounts.charlie).map(|(index, _)| index), Some(1));
+        }
+
+        // This test checks the self-rescue of funds from a stuck pool.
+        // It verifies that members can only reclaim their net contribution once the pool has made no progress past the safety timeout.
+        #[ink::test]
+        fn rescue_withdraw_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+            assert!(!contract.is_stuck());
+            assert_eq!(contract.rescue_withdraw(), Err(Error::NotStuck));
+
+            let stalled_since = contract.get_contribution_deadline();
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(stalled_since + RESCUE_TIMEOUT + 1);
+            assert!(contract.is_stuck());
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            assert_eq!(contract.rescue_withdraw(), Err(Error::NotContributor));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.rescue_withdraw(), Ok(()));
+            assert_eq!(contract.total_contributors(), 1);
+            assert_eq!(contract.get_contributors(), vec![(accounts.alice, 50 * UNIT)]);
+            assert_eq!(contract.rescue_withdraw(), Err(Error::NotContributor));
+        }
+
+        // This test checks that `approve_request` approves a request by its ID.
+        // It verifies that unknown IDs and callers without the `Approver` role are rejected and that the requester's eligibility is checked again at approval time.
+        #[ink::test]
+        fn approve_request_by_id_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(2), Err(Error::NoPendingRequest));
+
+            // Eve holds no role and cannot approve the request
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.eve);
+            assert_eq!(contract.approve_request(1), Err(Error::Unauthorized));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+
+            // The pool left the payment phase after the request
+            assert_eq!(contract.set_max_contributors(3), Ok(()));
+            assert_eq!(contract.approve_request(1), Err(Error::NotPaymentPhase));
+
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(contract.approve_request(1), Ok(()));
+            assert_eq!(contract.get_request_details(1).unwrap().status, RequestStatus::Approved);
+            assert_eq!(contract.approve_request(1), Err(Error::NoPendingRequest));
+        }
+
+        // This test checks pools that pay out to several winners per round.
+        // It verifies that the pot is split between the requester and the next member in the queue, who both leave the queue in one round,
+        // and that the payout estimates count two members per round.
+        #[ink::test]
+        fn winners_per_round_works() {
+            deploy_pool();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let invalid = Config {
+                winners_per_round: 0,
+                ..Default::default()
+            };
+            assert_eq!(Raiser::new_with_config(invalid).err(), Some(Error::InvalidConfig));
+
+            let config = Config {
+                max_contributors: 4,
+                winners_per_round: 2,
+                ..Default::default()
+            };
+            let mut contract = Raiser::new_with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+            assert_eq!(contract.estimated_payout_round(accounts.bob), Some(1));
+            assert_eq!(contract.estimated_payout_round(accounts.charlie), Some(2));
+            assert_eq!(contract.estimated_payout_round(accounts.django), Some(2));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(1), Ok(()));
+            assert_eq!(contract.get_payout_history(), vec![(accounts.alice, 200 * UNIT), (accounts.bob, 200 * UNIT)]);
+            assert_eq!(contract.get_request_details(2).unwrap().requester, accounts.bob);
+            assert_eq!(contract.get_request_details(2).unwrap().status, RequestStatus::Approved);
+            assert_eq!(contract.queue_accounts(), vec![accounts.charlie, accounts.django]);
+            assert_eq!(contract.completed_payouts, 2);
+            assert_eq!(contract.current_round, 2);
+            assert_eq!(contract.estimated_payout_round(accounts.django), Some(2));
+        }
+
+        // This test checks session keys.
+        // It verifies that a registered key pays the member's installment until it expires or is revoked, and that invalid keys are rejected.
+        #[ink::test]
+        fn session_keys_work() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.register_session_key(accounts.eve, 1_000), Err(Error::NotContributor));
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+            assert_eq!(contract.register_session_key(accounts.bob, 1_000), Err(Error::InvalidSessionKey));
+            assert_eq!(contract.register_session_key(accounts.eve, 0), Err(Error::InvalidSessionKey));
+            assert_eq!(contract.register_session_key(accounts.eve, MAX_SESSION_KEY_DURATION + 1), Err(Error::InvalidSessionKey));
+            assert_eq!(contract.register_session_key(accounts.eve, 1_000), Ok(()));
+            assert_eq!(contract.get_session_key(accounts.eve), Some((accounts.bob, 1_000)));
+
+            // The key pays Bob's installment of the next round
+            contract.current_round += 1;
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.eve);
+            assert_eq!(contract.contribute_round(), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 200 * UNIT);
+            assert_eq!(contract.balance_of(accounts.eve), 0);
+            assert_eq!(contract.revoke_session_key(accounts.eve), Err(Error::InvalidSessionKey));
+            assert_eq!(contract.contribute_with_referrer(accounts.bob), Err(Error::InvalidReferrer));
+
+            // An expired key acts for itself
+            contract.current_round += 1;
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            assert_eq!(contract.contribute_round(), Err(Error::NotContributor));
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.revoke_session_key(accounts.eve), Ok(()));
+            assert_eq!(contract.get_session_key(accounts.eve), None);
+        }
+
+        // This test checks the `get_cycle_value_report` function.
+        // The oracle prices need a deployed oracle contract and are not available off-chain, so the contributions are reported as unpriced.
+        #[ink::test]
+        fn get_cycle_value_report_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            assert_eq!(contract.get_cycle_value_report(1), CycleValueReport::default());
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(contract.contribute(), Ok(()));
+            }
+
+            assert_eq!(contract.get_cycle_value_report(1), CycleValueReport {
+                contributed: 200 * UNIT,
+                fiat_value: 0,
+                unpriced: 200 * UNIT,
+            });
+            assert_eq!(contract.get_cycle_value_report(2), CycleValueReport::default());
+            assert_eq!(contract.get_receipts(accounts.bob, 0, 1)[0].fiat_value, None);
+        }
+
+        // This test checks the `ScenarioBuilder` fixtures.
+        // It verifies that a scenario of three members runs a full cycle and the first round of the next one, with every member paid once per cycle.
+        #[ink::test]
+        fn scenario_builder_works() {
+            use crate::testing::{member, ScenarioBuilder, Step};
+
+            let scenario = ScenarioBuilder::new(3, 100 * UNIT).rounds(4, 100 * UNIT);
+            assert_eq!(scenario.steps()[..4], [
+                Step::Join { member: 0, amount: 100 * UNIT },
+                Step::Join { member: 1, amount: 100 * UNIT },
+                Step::Join { member: 2, amount: 100 * UNIT },
+                Step::Payout { member: 0 },
+            ]);
+            let contract = scenario.build();
+
+            assert_eq!(contract.contribution_cycle, 2);
+            assert_eq!(contract.current_round, 5);
+            assert_eq!(contract.total_contributors(), 3);
+            assert_eq!(contract.payouts_received.get(member(0)), Some(2));
+            assert_eq!(contract.payouts_received.get(member(1)), Some(1));
+            assert_eq!(contract.payouts_received.get(member(2)), Some(1));
+            assert_eq!(contract.last_paid_round.get(member(2)), Some(3));
+            assert_eq!(contract.get_next_requester(), Some(member(1)));
+        }
+
+        // This test checks the `get_enrollment_order` function.
+        // It verifies that the join order and join times survive a swap of the payout queue and a removal.
+        #[ink::test]
+        fn enrollment_order_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            // Bob and Alice swap their payout positions
+            assert_eq!(contract.propose_queue_swap(accounts.alice, accounts.bob), Ok(1));
+            assert_eq!(contract.confirm_swap(1), Ok(()));
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.confirm_swap(1), Ok(()));
+            assert_eq!(contract.queue_accounts(), vec![accounts.bob, accounts.alice]);
+            assert_eq!(contract.get_enrollment_order(), vec![(accounts.alice, 0), (accounts.bob, 1_000)]);
+
+            assert_eq!(contract.remove_contributor(accounts.bob), Ok(()));
+            assert_eq!(contract.queue_accounts(), vec![accounts.alice]);
+            assert_eq!(contract.get_enrollment_order(), vec![(accounts.alice, 0), (accounts.bob, 1_000)]);
+        }
+
+        // This test checks the time-weighted contribution credit.
+        // It verifies that credit accrues with the amount and the time locked, and that it breaks ties in the need score order once enabled.
+        #[ink::test]
+        fn time_weighted_credit_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let patch = ConfigPatch {
+                payout_order: Some(PayoutOrder::NeedScore),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+
+            // Bob joins first with less, Alice later with more
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(50 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(1_000);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(500 * UNIT);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+
+            ink::env::test::set_block_timestamp::<crate::RaiserEnvironment>(2_000);
+            assert_eq!(contract.get_time_credit(accounts.bob), 50 * UNIT * 2_000);
+            assert_eq!(contract.get_time_credit(accounts.alice), 500 * UNIT * 1_000);
+            assert_eq!(contract.get_total_time_credit(), 600_000 * UNIT);
+
+            // Both have waited equally long, so the tie goes to the earliest slot, or to the most credit once enabled
+            assert_eq!(contract.get_next_requester(), Some(accounts.bob));
+            let patch = ConfigPatch {
+                time_weighted_credit: Some(true),
+                ..Default::default()
+            };
+            assert_eq!(contract.update_config(patch), Ok(()));
+            assert_eq!(contract.get_next_requester(), Some(accounts.alice));
+
+            // A removed member's credit leaves the pool total
+            assert_eq!(contract.remove_contributor(accounts.bob), Ok(()));
+            assert_eq!(contract.get_time_credit(accounts.bob), 0);
+            assert_eq!(contract.get_total_time_credit(), 500_000 * UNIT);
+        }
+
+        // This test checks the `simulate_cycle` function.
+        // It verifies the pots, payout schedule, lock-ups and fees of a pool of five members paying two winners per round.
+        #[ink::test]
+        fn simulate_cycle_works() {
+            let contract = Raiser::new();
+            let config = Config {
+                min_amount: 100 * UNIT,
+                referral_bonus: 10 * UNIT,
+                payout_lockup: true,
+                winners_per_round: 2,
+                ..Default::default()
+            };
+            assert_eq!(contract.simulate_cycle(config.clone(), 0), Err(Error::InvalidMaxContributors));
+            let invalid = Config {
+                min_amount: 0,
+                ..Default::default()
+            };
+            assert_eq!(contract.simulate_cycle(invalid, 5), Err(Error::InvalidConfig));
+
+            let result = contract.simulate_cycle(config, 5).unwrap();
+            assert_eq!(result.installment, 100 * UNIT);
+            assert_eq!(result.rounds.len(), 3);
+            assert_eq!(result.rounds[0].pot, 500 * UNIT);
+            assert_eq!(result.rounds[0].payouts, vec![
+                SimulatedPayout { position: 0, amount: 50 * UNIT, held_back: 200 * UNIT },
+                SimulatedPayout { position: 1, amount: 50 * UNIT, held_back: 200 * UNIT },
+            ]);
+            assert_eq!(result.rounds[2].payouts, vec![SimulatedPayout { position: 4, amount: 500 * UNIT, held_back: 0 }]);
+            assert_eq!(result.rounds[2].fees, 50 * UNIT);
+            assert_eq!(result.total_contributed, 1_500 * UNIT);
+            assert_eq!(result.total_fees, 50 * UNIT);
+            assert_eq!(result.creation_fee, 0);
+        }
+
+        // This test checks that each payout pays the pot of its own round.
+        // It verifies that a pool funded only by contributions pays every round, and that members can join again after the cycle ends.
+        #[ink::test]
+        fn round_pot_works() {
+            deploy_pool();
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute()), Ok(()));
+            }
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.get_payout_history(), vec![(accounts.alice, 200 * UNIT)]);
+            assert_eq!(contract.round_pot, 0);
+
+            // Round 2 pays its own pot, not the 400 contributed over the cycle
+            for member in [accounts.alice, accounts.bob] {
+                ink::env::test::set_caller::<crate::RaiserEnvironment>(member);
+                assert_eq!(pay(|| contract.contribute_round()), Ok(()));
+            }
+            assert_eq!(contract.get_total_supply(), 400 * UNIT);
+            assert_eq!(contract.round_pot, 200 * UNIT);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(contract.request_token(), Ok(()));
+            assert_eq!(contract.get_request_details(contract.next_request_id - 1).unwrap().amount, 200 * UNIT);
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            assert_eq!(contract.approve_request(contract.next_request_id - 1), Ok(()));
+            assert_eq!(contract.total_paid_out, 400 * UNIT);
+            assert_eq!(contract.get_available_balance(), 0);
+
+            // The cycle ended, so its ledger is cleared and Bob, who did not enroll again, is no longer a member
+            assert_eq!(contract.contribution_cycle, 2);
+            assert_eq!(contract.get_total_supply(), 0);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.verify_invariants(), vec![]);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert!(contract.all_contributed_this_round());
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            assert_eq!(pay(|| contract.contribute()), Ok(()));
+            assert_eq!(contract.total_contributors(), 2);
+        }
+
+        #[ink::test]
+        fn total_contributors_works() {
+            let mut contract = Raiser::new();
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+
+            // Initially, there should be no contributors
+            assert_eq!(contract.total_contributors(), 0);
+
+            // Simulate a contribution from Alice
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.set_max_contributors(2), Ok(()));
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Now, there should be one contributor
+            assert_eq!(contract.total_contributors(), 1);
+
+            // Simulate a contribution from Bob
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Ok(()));
+
+            // Now, there should be two contributors
+            assert_eq!(contract.total_contributors(), 2);
+
+            // The pool is full, so Charlie can't join
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(100 * UNIT);
+            assert_eq!(contract.contribute(), Err(Error::PoolFull));
+            assert_eq!(contract.total_contributors(), 2);
+        }
+
+    }
+
+    /// Property-based tests that drive random sequences of operations against the pool.
+    #[cfg(test)]
+    mod property_tests {
+        use super::*;
+        use super::tests::UNIT;
+        use proptest::prelude::*;
+
+        /// An operation of a random scenario. The index selects the acting account.
+        #[derive(Debug, Clone)]
+        enum Op {
+            Contribute(usize),
+            ContributeRound(usize),
+            Request(usize),
+            Approve,
+            Advance,
+        }
+
+        fn op() -> impl Strategy<Value = Op> {
+            prop_oneof![
+                (0..4usize).prop_map(Op::Contribute),
+                (0..4usize).prop_map(Op::ContributeRound),
+                (0..4usize).prop_map(Op::Request),
+                Just(Op::Approve),
+                Just(Op::Advance),
+            ]
+        }
+
+        /// Checks the cycle invariants, given the sum of the contributions the pool accepted in this cycle and over
+        /// all cycles.
+        fn check_invariants(contract: &Raiser, contributed: Balance, lifetime_contributed: Balance) {
+            // The pool never pays out more than its members have put in
+            assert!(contract.total_paid_out <= lifetime_contributed);
+
+            // Every accepted contribution is accounted for, and nothing else
+            assert_eq!(contract.get_total_supply(), contributed);
+
+            // Every payout slot is either still queued or paid out in this cycle
+            assert_eq!(contract.payout_history.len() as u128, contract.completed_payouts);
+            assert_eq!(contract.queue_len() as u128 + contract.completed_payouts, contract.total_weight);
+
+            for (member, amount) in &contract.payout_history {
+                // No payout exceeds what the members have contributed
+                assert!(*amount <= contributed);
+                // Each member is paid at most once per rotation
+                assert_eq!(contract.payout_history.iter().filter(|(paid, _)| paid == member).count(), 1);
+            }
+        }
+
+        proptest! {
+            // This test runs random sequences of contributions, requests, approvals and cycle advances.
+            // It verifies that the cycle invariants hold after every step, whichever operations fail.
+            #[test]
+            fn cycle_invariants_hold(max_contributors in 1u128..=4, ops in proptest::collection::vec(op(), 1..60)) {
+                ink::env::test::run_test::<crate::RaiserEnvironment, _>(|_| {
+                    // The pool holds only the existential deposit, so every payout is paid from contributions
+                    let pool = AccountId::from([0x42; 32]);
+                    let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+                    ink::env::test::set_callee::<crate::RaiserEnvironment>(pool);
+                    ink::env::test::set_account_balance::<crate::RaiserEnvironment>(pool, minimum_balance);
+                    let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+                    let members = [accounts.alice, accounts.bob, accounts.charlie, accounts.django];
+                    for member in members {
+                        ink::env::test::set_account_balance::<crate::RaiserEnvironment>(member, 1_000 * UNIT);
+                    }
+
+                    ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+                    let mut contract = Raiser::new();
+                    assert_eq!(contract.set_max_contributors(max_contributors), Ok(()));
+                    let mut contributed: Balance = 0;
+                    let mut lifetime_contributed: Balance = 0;
+                    let mut cycle = contract.contribution_cycle;
+
+                    for op in &ops {
+                        match *op {
+                            Op::Contribute(member) => {
+                                ink::env::test::set_caller::<crate::RaiserEnvironment>(members[member]);
+                                ink::env::test::transfer_in::<crate::RaiserEnvironment>(100 * UNIT);
+                                if contract.contribute().is_ok() {
+                                    contributed += 100 * UNIT;
+                                    lifetime_contributed += 100 * UNIT;
+                                } else {
+                                    // A failed call reverts the transfer
+                                    ink::env::test::set_account_balance::<crate::RaiserEnvironment>(pool, ink::env::balance::<crate::RaiserEnvironment>() - 100 * UNIT);
+                                    let balance = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(members[member]).unwrap();
+                                    ink::env::test::set_account_balance::<crate::RaiserEnvironment>(members[member], balance + 100 * UNIT);
+                                }
+                            }
+                            Op::ContributeRound(member) => {
+                                ink::env::test::set_caller::<crate::RaiserEnvironment>(members[member]);
+                                ink::env::test::transfer_in::<crate::RaiserEnvironment>(50 * UNIT);
+                                if contract.contribute_round().is_ok() {
+                                    contributed += 50 * UNIT;
+                                    lifetime_contributed += 50 * UNIT;
+                                } else {
+                                    // A failed call reverts the transfer
+                                    ink::env::test::set_account_balance::<crate::RaiserEnvironment>(pool, ink::env::balance::<crate::RaiserEnvironment>() - 50 * UNIT);
+                                    let balance = ink::env::test::get_account_balance::<crate::RaiserEnvironment>(members[member]).unwrap();
+                                    ink::env::test::set_account_balance::<crate::RaiserEnvironment>(members[member], balance + 50 * UNIT);
+                                }
+                            }
+                            Op::Request(member) => {
+                                ink::env::test::set_caller::<crate::RaiserEnvironment>(members[member]);
+                                let _ = contract.request_token();
+                            }
+                            Op::Approve => {
+                                ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+                                let pending = contract.requests.first().map_or(0, |(_, _, request_id)| *request_id);
+                                let _ = contract.approve_request(pending);
+                            }
+                            Op::Advance => {
+                                let _ = contract.next_contribution_cycle();
+                            }
+                        }
+                        if contract.contribution_cycle != cycle {
+                            // The ledger starts over with every cycle
+                            cycle = contract.contribution_cycle;
+                            contributed = 0;
+                        }
+                        check_invariants(&contract, contributed, lifetime_contributed);
+                    }
+                    Ok(())
+                })
+                .unwrap();
+            }
+        }
+    }
+
+    /// Gas and storage deposit benchmarks, run against a node with `cargo test --features e2e-tests`.
+    ///
+    /// The benchmark fills a pool with 10 members and a pool with `MAX_POOL_SIZE` members, and measures `contribute` for the last member,
+    /// `get_contributors` and `approve_request` with a dry run. It compares the full pool with the small one instead of fixed numbers, which
+    /// depend on the node: the storage deposit of `contribute` must not grow with the pool, and no gas cost may grow faster than the
+    /// number of members, so storage layout regressions show up in CI.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use crate::testing::{ScenarioBuilder, Step};
+        use ink_e2e::{ChainBackend, ContractsBackend, E2EBackend};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// The contribution every member pays to join the benchmark pool: one token.
+        const CONTRIBUTION: Balance = 100 * crate::RaiserEnvironment::CENT;
+
+        /// The `ref_time` and storage deposit measured for each benchmarked message.
+        #[derive(Debug)]
+        struct Costs {
+            contribute_gas: u64,
+            contribute_deposit: Balance,
+            get_contributors_gas: u64,
+            approve_request_gas: u64,
+        }
+
+        /// Fills a new pool with `members` accounts and measures the benchmarked messages.
+        ///
+        /// Alice deploys the pool and holds every role, so Alice approves the payout of the first member.
+        async fn measure<Client>(client: &mut Client, members: u32) -> E2EResult<Costs>
+        where
+            Client: E2EBackend<crate::RaiserEnvironment> + ChainBackend<Balance = Balance>,
+            <Client as ContractsBackend<crate::RaiserEnvironment>>::Error: std::error::Error + 'static,
+        {
+            let mut constructor = RaiserRef::new();
+            let contract = client
+                .instantiate("raiser", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<Raiser>();
+
+            let set_max = call_builder.set_max_contributors(members as u128);
+            client.call(&ink_e2e::alice(), &set_max).submit().await.expect("set_max_contributors failed");
+
+            let mut accounts = Vec::new();
+            for _ in 0..members {
+                accounts.push(client.create_and_fund_account(&ink_e2e::alice(), 1_000 * CONTRIBUTION).await);
+            }
+
+            // Every member but the last one joins without being measured
+            let (last, others) = accounts.split_last().expect("at least one member");
+            for member in others {
+                let contribute = call_builder.contribute();
+                client.call(member, &contribute).value(CONTRIBUTION).submit().await.expect("contribute failed");
+            }
+
+            let contribute = call_builder.contribute();
+            let contribute_run = client.call(last, &contribute).value(CONTRIBUTION).dry_run().await?;
+            client.call(last, &contribute).value(CONTRIBUTION).submit().await.expect("contribute failed");
+
+            let get_contributors = call_builder.get_contributors();
+            let get_contributors_run = client.call(&ink_e2e::alice(), &get_contributors).dry_run().await?;
+
+            let request_token = call_builder.request_token();
+            client.call(&accounts[0], &request_token).submit().await.expect("request_token failed");
+
+            let approve_request = call_builder.approve_request(1);
+            let approve_request_run = client.call(&ink_e2e::alice(), &approve_request).dry_run().await?;
+            client.call(&ink_e2e::alice(), &approve_request).submit().await.expect("approve_request failed");
+
+            Ok(Costs {
+                contribute_gas: contribute_run.exec_result.gas_consumed.ref_time(),
+                contribute_deposit: contribute_run.exec_result.storage_deposit.charge_or_zero(),
+                get_contributors_gas: get_contributors_run.exec_result.gas_consumed.ref_time(),
+                approve_request_gas: approve_request_run.exec_result.gas_consumed.ref_time(),
+            })
+        }
+
+        /// Replays the steps of `scenario` against a pool, with `accounts[i]` acting for member `i` and Alice as the owner.
+        async fn replay<Client>(
+            client: &mut Client,
+            call_builder: &mut <Raiser as ink::codegen::ContractCallBuilder>::Type,
+            accounts: &[ink_e2e::Keypair],
+            scenario: &ScenarioBuilder,
+        ) -> E2EResult<()>
+        where
+            Client: E2EBackend<crate::RaiserEnvironment> + ChainBackend<Balance = Balance>,
+            <Client as ContractsBackend<crate::RaiserEnvironment>>::Error: std::error::Error + 'static,
+        {
+            for step in scenario.steps() {
+                match *step {
+                    Step::Join { member, amount } => {
+                        let contribute = call_builder.contribute();
+                        client.call(&accounts[member], &contribute).value(amount).submit().await.expect("contribute failed");
+                    }
+                    Step::PayInstallment { member, amount } => {
+                        let contribute_round = call_builder.contribute_round();
+                        client.call(&accounts[member], &contribute_round).value(amount).submit().await.expect("contribute_round failed");
+                    }
+                    Step::Enroll { member, amount } => {
+                        let enroll_next_cycle = call_builder.enroll_next_cycle();
+                        client.call(&accounts[member], &enroll_next_cycle).value(amount).submit().await.expect("enroll_next_cycle failed");
+                    }
+                    Step::Payout { member } => {
+                        let request_token = call_builder.request_token();
+                        client.call(&accounts[member], &request_token).submit().await.expect("request_token failed");
+                        let get_pending_requests = call_builder.get_pending_requests(0, 1);
+                        let (request_id, ..) = client.call(&ink_e2e::alice(), &get_pending_requests).dry_run().await?.return_value()[0];
+                        let approve_request = call_builder.approve_request(request_id);
+                        client.call(&ink_e2e::alice(), &approve_request).submit().await.expect("approve_request failed");
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        // This test replays a `ScenarioBuilder` scenario on a node.
+        // Three members run a full cycle and enroll in the next one, as in the off-chain `scenario_builder_works` test.
+        #[ink_e2e::test(environment = crate::RaiserEnvironment)]
+        async fn scenario_replay_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let scenario = ScenarioBuilder::new(3, CONTRIBUTION).rounds(4, CONTRIBUTION);
+            let mut constructor = RaiserRef::new();
+            let contract = client
+                .instantiate("raiser", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<Raiser>();
+
+            let set_max = call_builder.set_max_contributors(scenario.member_count() as u128);
+            client.call(&ink_e2e::alice(), &set_max).submit().await.expect("set_max_contributors failed");
+            let mut accounts = Vec::new();
+            for _ in 0..scenario.member_count() {
+                accounts.push(client.create_and_fund_account(&ink_e2e::alice(), 1_000 * CONTRIBUTION).await);
+            }
+
+            replay(&mut client, &mut call_builder, &accounts, &scenario).await?;
+            let get_completed_payouts = call_builder.get_completed_payouts();
+            assert_eq!(client.call(&ink_e2e::alice(), &get_completed_payouts).dry_run().await?.return_value(), 1);
+            Ok(())
+        }
+
+        // This test checks the `code_info` function.
+        // The code hash needs uploaded code, so it is only available on a node.
+        #[ink_e2e::test(environment = crate::RaiserEnvironment)]
+        async fn code_info_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut constructor = RaiserRef::new();
+            let contract = client
+                .instantiate("raiser", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let call_builder = contract.call_builder::<Raiser>();
+
+            let code_info = call_builder.code_info();
+            let (code_hash, storage_version, crate_version) = client.call(&ink_e2e::alice(), &code_info).dry_run().await?.return_value();
+            assert_ne!(code_hash, Hash::default());
+            assert_eq!(storage_version, STORAGE_VERSION);
+            assert_eq!(crate_version, CRATE_VERSION.as_bytes());
+            Ok(())
+        }
+
+        // This test checks how the costs of a pool grow with its members.
+        // It verifies that a full pool charges the same storage deposit for a new member as a pool of 10,
+        // and that no gas cost grows faster than the number of members.
+        #[ink_e2e::test(environment = crate::RaiserEnvironment)]
+        async fn costs_scale_with_members<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let small = measure(&mut client, 10).await?;
+            let full = measure(&mut client, MAX_POOL_SIZE as u32).await?;
+            let growth = MAX_POOL_SIZE as u64 / 10 + 1;
+
+            assert!(full.contribute_deposit <= small.contribute_deposit, "contribute storage deposit regressed: {:?} {:?}", small, full);
+            assert!(full.contribute_gas <= small.contribute_gas * growth, "contribute gas regressed: {:?} {:?}", small, full);
+            assert!(full.get_contributors_gas <= small.get_contributors_gas * growth, "get_contributors gas regressed: {:?} {:?}", small, full);
+            assert!(full.approve_request_gas <= small.approve_request_gas * growth, "approve_request gas regressed: {:?} {:?}", small, full);
+            Ok(())
+        }
+    }
+   
+}
+
+pub use self::raiser::{ConfigPatch, Error, Raiser, RaiserRef};
+
+/// Events emitted by the `Raiser` contract.
+///
+/// The events are defined outside of the contract module with `#[ink::event]`, so each one gets an ink! 5
+/// signature topic derived from its name and fields. Light clients can subscribe to a single event type of a
+/// specific pool by filtering on the pool's contract address and that signature topic.
+///
+/// Every event except `Transfer` and `Approval`, which keep the PSP22 layout, ends with the same three fields, so an
+/// indexer can be written once against any deployment:
+/// - `pool_id`: The address of the pool contract.
+/// - `cycle`: The contribution cycle the event belongs to.
+/// - `round`: The payout round the event belongs to, indexed as the last topic.
+///
+/// The default environment allows four topics including the signature topic. Each event indexes up to two
+/// fields before `round`, so subscriptions can filter on a member across every event type:
+/// - The accounts the event is about, such as `member`, `requester` or `referrer`, are indexed first.
+/// - `cycle` is indexed if a topic is left.
+/// - `pool_id` is a plain field, because the pool is already known from the emitting contract address.
+pub mod events {
+    use crate::raiser::{Config, ConfigPatch, DustPolicy, PoolStatus, RemoteBeneficiary, Role, Ruling};
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    type AccountId = <crate::RaiserEnvironment as ink::env::Environment>::AccountId;
+    type Balance = <crate::RaiserEnvironment as ink::env::Environment>::Balance;
+    type Hash = <crate::RaiserEnvironment as ink::env::Environment>::Hash;
+    type Timestamp = <crate::RaiserEnvironment as ink::env::Environment>::Timestamp;
+
+    /// Event emitted when a token transfer occurs.
+    #[ink::event]
+    pub struct Transfer {
+        #[ink(topic)]
+        pub from: Option<AccountId>,
+        #[ink(topic)]
+        pub to: Option<AccountId>,
+        pub value: Balance,
+    }
+
+    /// Event emitted when an approval occurs that `spender` is allowed to withdraw
+    #[ink::event]
+    pub struct Approval {
+        #[ink(topic)]
+        pub owner: AccountId,
+        #[ink(topic)]
+        pub spender: AccountId,
+        pub value: Balance,
+    }
+
+    /// Event emitted when the owner removes a contributor from the pool and refunds them.
+    #[ink::event]
+    pub struct MemberRemoved {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub refund: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a sponsor adds matching funds to the pool.
+    #[ink::event]
+    pub struct Sponsored {
+        #[ink(topic)]
+        pub sponsor: AccountId,
+        pub amount: Balance,
+        pub target_round: Option<u128>,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a contributor requests a payout.
+    #[ink::event]
+    pub struct RequestCreated {
+        #[ink(topic)]
+        pub request_id: u128,
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub amount: Balance,
+        pub memo_hash: Option<Hash>,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a request is dropped because it was not approved within the approval timeout.
+    #[ink::event]
+    pub struct RequestExpired {
+        pub request_id: u128,
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a payout request is approved and paid.
+    #[ink::event]
+    pub struct RequestApproved {
+        #[ink(topic)]
+        pub request_id: u128,
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member authorizes recurring installments.
+    #[ink::event]
+    pub struct RecurringAuthorized {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub max_rounds: u128,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a keeper pulls a member's installment for a round.
+    #[ink::event]
+    pub struct ContributionPulled {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the owner updates the pool settings.
+    #[ink::event]
+    pub struct ConfigUpdated {
+        pub old: Config,
+        pub new: Config,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a settings change is queued until the current cycle ends.
+    #[ink::event]
+    pub struct ConfigQueued {
+        pub patch: ConfigPatch,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a queued settings change is dropped at the end of the cycle because it is no longer valid.
+    #[ink::event]
+    pub struct ConfigDiscarded {
+        pub patch: ConfigPatch,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the next eligible requester defers their turn to the back of the queue.
+    #[ink::event]
+    pub struct TurnDeferred {
+        #[ink(topic)]
+        pub member: AccountId,
+        #[ink(topic)]
+        pub next_requester: Option<AccountId>,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an unresponsive requester is skipped and moved to the back of the queue.
+    #[ink::event]
+    pub struct MemberSkipped {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub missed_turns: u32,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an admin grants a role to an account.
+    #[ink::event]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        pub sender: AccountId,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an admin revokes a role from an account.
+    #[ink::event]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        pub role: Role,
+        #[ink(topic)]
+        pub account: AccountId,
+        pub sender: AccountId,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an admin allocates untracked funds to the payouts.
+    #[ink::event]
+    pub struct DonationsAllocated {
+        pub amount: Balance,
+        pub target_round: Option<u128>,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted by `tick` for each member who has not paid their installment when a round opens.
+    #[ink::event]
+    pub struct ContributionDue {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub deadline: Timestamp,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted by `tick` for each member who has not paid their installment once the grace period has lapsed.
+    #[ink::event]
+    pub struct ContributionOverdue {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub deadline: Timestamp,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an approved payout is held in escrow until the requester claims it.
+    #[ink::event]
+    pub struct PayoutEscrowed {
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub amount: Balance,
+        pub expires_at: Timestamp,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an approved payout is credited to the recipient's pending withdrawal.
+    #[ink::event]
+    pub struct PayoutQueued {
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an unclaimed payout is returned to the pool.
+    #[ink::event]
+    pub struct EscrowExpired {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an admin prunes the history of past contribution cycles.
+    #[ink::event]
+    pub struct HistoryPruned {
+        pub cycles_removed: u128,
+        pub entries_removed: u32,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a referrer earns the bonus for a member who completed a full cycle.
+    #[ink::event]
+    pub struct ReferralRewarded {
+        #[ink(topic)]
+        pub referrer: AccountId,
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an admin starts winding down the pool.
+    #[ink::event]
+    pub struct WindDownStarted {
+        pub refunds_at: Timestamp,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the members of a wound down pool are refunded.
+    #[ink::event]
+    pub struct MembersRefunded {
+        pub members: u32,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an approved payout is split into installments at the requester's choice.
+    #[ink::event]
+    pub struct PayoutSplit {
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub amount: Balance,
+        pub installments: u128,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the insurance reserve covers the installment of a member who defaulted.
+    #[ink::event]
+    pub struct DefaultCovered {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an installment paid in an alternative asset is swapped into the native token.
+    #[ink::event]
+    pub struct AssetConverted {
+        #[ink(topic)]
+        pub member: AccountId,
+        #[ink(topic)]
+        pub asset: AccountId,
+        pub amount_in: Balance,
+        pub amount_out: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the lifecycle status of the pool changes.
+    #[ink::event]
+    pub struct StatusChanged {
+        pub previous: PoolStatus,
+        pub status: PoolStatus,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a rounding remainder is moved to the dust balance or the fee reserve.
+    #[ink::event]
+    pub struct DustCollected {
+        pub amount: Balance,
+        pub policy: DustPolicy,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member shares their payout slot with a team.
+    #[ink::event]
+    pub struct TeamCreated {
+        #[ink(topic)]
+        pub beneficiary: AccountId,
+        pub members: Vec<AccountId>,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an account enrolls in the next cycle.
+    #[ink::event]
+    pub struct NextCycleEnrolled {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an account enrolled in the next cycle fails the admission gates when the cycle starts, and its installment is returned.
+    #[ink::event]
+    pub struct EnrollmentRefunded {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an account joins the pool without paying an installment.
+    #[ink::event]
+    pub struct MemberJoined {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub weight: u8,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member proposes to swap payout positions with another member.
+    #[ink::event]
+    pub struct SwapProposed {
+        pub swap_id: u128,
+        #[ink(topic)]
+        pub a: AccountId,
+        #[ink(topic)]
+        pub b: AccountId,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when two members swap their payout positions.
+    #[ink::event]
+    pub struct PositionsSwapped {
+        pub swap_id: u128,
+        #[ink(topic)]
+        pub a: AccountId,
+        #[ink(topic)]
+        pub b: AccountId,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a cycle ends, with the statistics analytics pipelines chart pool health from.
+    #[ink::event]
+    pub struct CycleStats {
+        pub total_pot: Balance,
+        pub members: u128,
+        pub avg_contribution: Balance,
+        pub duration_ms: Timestamp,
+        pub defaults: u128,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a new contribution cycle starts.
+    #[ink::event]
+    pub struct CycleAdvanced {
+        pub members: u128,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a successor pool is deployed with the current members.
+    #[ink::event]
+    pub struct PoolCloned {
+        #[ink(topic)]
+        pub successor: AccountId,
+        pub members: u32,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the metadata URI of the pool changes.
+    #[ink::event]
+    pub struct MetadataUpdated {
+        pub uri: String,
+        #[ink(topic)]
+        pub hash: Hash,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the permissions of an automation operator change.
+    #[ink::event]
+    pub struct AutomationSet {
+        #[ink(topic)]
+        pub operator: AccountId,
+        pub permissions: u8,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member pays the missing installment of another member.
+    #[ink::event]
+    pub struct MemberCovered {
+        #[ink(topic)]
+        pub member: AccountId,
+        #[ink(topic)]
+        pub coverer: AccountId,
+        pub amount: Balance,
+        pub debt: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when part of a covered installment is recovered from the payout of the covered member.
+    #[ink::event]
+    pub struct CoverRepaid {
+        #[ink(topic)]
+        pub member: AccountId,
+        #[ink(topic)]
+        pub creditor: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member proposes to replace the owner.
+    #[ink::event]
+    pub struct ImpeachmentProposed {
+        pub id: u128,
+        #[ink(topic)]
+        pub proposer: AccountId,
+        #[ink(topic)]
+        pub new_owner: AccountId,
+        pub ends_at: Timestamp,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the members replace the owner.
+    #[ink::event]
+    pub struct OwnerImpeached {
+        pub id: u128,
+        #[ink(topic)]
+        pub previous_owner: AccountId,
+        #[ink(topic)]
+        pub new_owner: AccountId,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the installment of a member is paid from their payout lock-up.
+    #[ink::event]
+    pub struct LockupApplied {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member changes their display name.
+    #[ink::event]
+    pub struct AliasSet {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub name: Vec<u8>,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an admin distributes the untracked funds among the members.
+    #[ink::event]
+    pub struct SurplusDistributed {
+        pub amount: Balance,
+        pub members: u32,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member claims their share of the distributed surplus.
+    #[ink::event]
+    pub struct SurplusClaimed {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the transfer of an approved payout fails and the payout is queued for a retry.
+    #[ink::event]
+    pub struct PayoutFailed {
+        #[ink(topic)]
+        pub requester: AccountId,
+        pub request_id: u128,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a failed payout is sent to another account with the consent of the owner and the recipient.
+    #[ink::event]
+    pub struct PayoutRedirected {
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+        pub request_id: u128,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an account creates a pool with `create_pool`.
+    #[ink::event]
+    pub struct PoolCreated {
+        #[ink(topic)]
+        pub creator: AccountId,
+        #[ink(topic)]
+        pub pool: AccountId,
+        pub fee: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member disputes an escrowed payout and the arbitrator opens a case.
+    #[ink::event]
+    pub struct DisputeRaised {
+        #[ink(topic)]
+        pub claimant: AccountId,
+        #[ink(topic)]
+        pub recipient: AccountId,
+        pub dispute_id: u128,
+        pub amount: Balance,
+        pub fee: Balance,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when the ruling of the arbitrator on a disputed payout is enforced.
+    #[ink::event]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        pub recipient: AccountId,
+        pub dispute_id: u128,
+        pub ruling: Ruling,
+        pub returned: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member proposes to extend the contribution deadline of the current round.
+    #[ink::event]
+    pub struct ExtensionProposed {
+        #[ink(topic)]
+        pub proposer: AccountId,
+        pub id: u128,
+        pub ms: Timestamp,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a majority of the members extends the contribution deadline of the current round.
+    #[ink::event]
+    pub struct DeadlineExtended {
+        pub id: u128,
+        pub ms: Timestamp,
+        pub deadline: Timestamp,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member registers or removes the remote-chain beneficiary of their payouts.
+    #[ink::event]
+    pub struct RemoteBeneficiarySet {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub beneficiary: Option<RemoteBeneficiary>,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a payout is routed to a remote beneficiary through the XCM gateway.
+    #[ink::event]
+    pub struct PayoutRouted {
+        #[ink(topic)]
+        pub recipient: AccountId,
+        pub amount: Balance,
+        pub para_id: u32,
+        pub account: [u8; 32],
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when an open impeachment proposal is cancelled because the contribution cycle ended.
+    #[ink::event]
+    pub struct ImpeachmentCancelled {
+        pub id: u128,
+        #[ink(topic)]
+        pub new_owner: AccountId,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member reclaims their net contribution from a stuck pool.
+    #[ink::event]
+    pub struct FundsRescued {
+        #[ink(topic)]
+        pub member: AccountId,
+        pub amount: Balance,
+        pub pool_id: AccountId,
+        #[ink(topic)]
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+
+    /// Event emitted when a member registers a session key.
+    #[ink::event]
+    pub struct SessionKeyRegistered {
+        #[ink(topic)]
+        pub member: AccountId,
+        #[ink(topic)]
+        pub key: AccountId,
+        pub expires_at: Timestamp,
+        pub pool_id: AccountId,
+        pub cycle: u128,
+        #[ink(topic)]
+        pub round: u128,
+    }
+}
+
+/// The public interface of a `Raiser` pool for cross-contract integrations, and the interfaces pools call on other contracts.
+///
+/// Aggregators and insurance contracts can call any pool generically with `contract_ref!(RaiserPool)`.
+/// The trait messages have their own selectors, so the messages of the contract keep theirs.
+/// Arbitration contracts implement `Arbitrator` to receive the disputes of a pool, and XCM gateways implement `XcmGateway`
+/// to route payouts to other parachains.
+pub mod traits {
+    use crate::raiser::{Error, PoolStats};
+
+    type AccountId = <crate::RaiserEnvironment as ink::env::Environment>::AccountId;
+    type Balance = <crate::RaiserEnvironment as ink::env::Environment>::Balance;
+
+    /// The messages a pool exposes to other contracts.
+    #[ink::trait_definition]
+    pub trait RaiserPool {
+        /// Joins the pool with the transferred value.
+        #[ink(message, payable)]
+        fn contribute(&mut self) -> Result<(), Error>;
+
+        /// Requests the payout of the caller, who must be the next eligible requester.
+        #[ink(message)]
+        fn request_token(&mut self) -> Result<(), Error>;
+
+        /// Approves the pending payout request `request_id`. The caller must hold the `Approver` role.
+        #[ink(message)]
+        fn approve_request(&mut self, request_id: u128) -> Result<(), Error>;
+
+        /// Checks if the pool is in the payment phase.
+        #[ink(message)]
+        fn is_payment_phase(&self) -> bool;
+
+        /// Returns the current payout round.
+        #[ink(message)]
+        fn get_current_round(&self) -> u128;
+
+        /// Returns the next eligible requester, if any.
+        #[ink(message)]
+        fn get_next_requester(&self) -> Option<AccountId>;
+
+        /// Returns the total amount contributed in the current cycle.
+        #[ink(message)]
+        fn get_total_supply(&self) -> Balance;
+
+        /// Returns the number of members.
+        #[ink(message)]
+        fn total_contributors(&self) -> u128;
+
+        /// Returns the amount `owner` has contributed.
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance;
+
+        /// Claims the escrowed payout of the caller.
+        #[ink(message)]
+        fn claim(&mut self) -> Result<(), Error>;
+    }
+
+    /// The messages a pool exposes to the pools it deployed, which report their members and statistics to it.
+    #[ink::trait_definition]
+    pub trait PoolRegistry {
+        /// Records the statistics the calling pool reports about itself.
+        #[ink(message)]
+        fn report_pool_stats(&mut self, stats: PoolStats) -> Result<(), Error>;
+
+        /// Records that `member` joined or left the calling pool.
+        #[ink(message)]
+        fn report_membership(&mut self, member: AccountId, joined: bool) -> Result<(), Error>;
+    }
+
+    /// The messages an external arbitration contract exposes to pools.
+    #[ink::trait_definition]
+    pub trait Arbitrator {
+        /// Opens a case on the escrowed payout of `recipient`, disputed by `claimant`, and collects the transferred fee.
+        ///
+        /// Returns whether the case was accepted. The arbitrator rules by calling `resolve_dispute` on the pool.
+        #[ink(message, payable)]
+        fn open_case(&mut self, dispute_id: u128, claimant: AccountId, recipient: AccountId, amount: Balance) -> bool;
+    }
+
+    /// The messages an XCM gateway contract exposes to pools.
+    #[ink::trait_definition]
+    pub trait XcmGateway {
+        /// Sends the transferred value to `account` on parachain `para_id`.
+        ///
+        /// Returns whether the transfer was dispatched.
+        #[ink(message, payable)]
+        fn transfer_to_parachain(&mut self, para_id: u32, account: [u8; 32]) -> bool;
+    }
+}
+
+/// Deterministic fixtures for tests, enabled with the `testing` feature.
+///
+/// `ScenarioBuilder` describes a pool state as a list of steps: members joining, paying installments, enrolling in the next cycle
+/// and receiving their payouts. Unit tests apply the steps to an off-chain pool with `build`, and end-to-end tests replay the same
+/// `steps` against a node, so a multi-member, multi-cycle flow is set up the same way in a few lines.
+///
+/// Scenarios use the join order and 1x membership tiers, so member `i` of a cycle receives the `i`-th payout.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing {
+    use crate::raiser::{Config, Raiser};
+    use ink::prelude::vec::Vec;
+
+    type AccountId = <crate::RaiserEnvironment as ink::env::Environment>::AccountId;
+    type Balance = <crate::RaiserEnvironment as ink::env::Environment>::Balance;
+
+    /// `Step` is a step of a scenario. Members are referred to by their index, which `member` maps to an account off-chain.
+    ///
+    /// Variants:
+    /// - `Join`: The member joins the pool with `contribute`, paying `amount`.
+    /// - `PayInstallment`: The member pays `amount` for the current round with `contribute_round`.
+    /// - `Enroll`: The member enrolls in the next cycle with `enroll_next_cycle`, paying `amount`.
+    /// - `Payout`: The member, the next requester, requests their payout and the owner approves it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Step {
+        Join { member: usize, amount: Balance },
+        PayInstallment { member: usize, amount: Balance },
+        Enroll { member: usize, amount: Balance },
+        Payout { member: usize },
+    }
+
+    /// Returns the account of member `index` in scenarios built off-chain.
+    ///
+    /// The accounts differ from the default test accounts, so the owner, Alice, is never a member by accident.
+    pub fn member(index: usize) -> AccountId {
+        let mut bytes = [0xAA; 32];
+        bytes[..8].copy_from_slice(&(index as u64).to_le_bytes());
+        AccountId::from(bytes)
+    }
+
+    /// `ScenarioBuilder` builds a pool state step by step.
+    #[derive(Debug, Clone)]
+    pub struct ScenarioBuilder {
+        config: Config,
+        members: usize,
+        payouts: usize,
+        unpaid: bool,
+        enrolled: bool,
+        steps: Vec<Step>,
+    }
+
+    impl ScenarioBuilder {
+        /// Starts a scenario in which `members` members join a pool of that size, each paying `amount`, so the pool is full.
+        pub fn new(members: usize, amount: Balance) -> Self {
+            Self {
+                config: Config {
+                    max_contributors: members as u128,
+                    ..Default::default()
+                },
+                members,
+                payouts: 0,
+                unpaid: false,
+                enrolled: false,
+                steps: (0..members).map(|member| Step::Join { member, amount }).collect(),
+            }
+        }
+
+        /// Replaces the pool settings. `max_contributors` is kept at the number of members.
+        pub fn config(mut self, config: Config) -> Self {
+            self.config = Config {
+                max_contributors: self.members as u128,
+                ..config
+            };
+            self
+        }
+
+        /// Has every member pay `amount` for the current round.
+        pub fn installments(mut self, amount: Balance) -> Self {
+            for member in 0..self.members {
+                self.steps.push(Step::PayInstallment { member, amount });
+            }
+            self.unpaid = false;
+            self
+        }
+
+        /// Has every member enroll in the next cycle, paying `amount` as their first installment.
+        pub fn enroll(mut self, amount: Balance) -> Self {
+            for member in 0..self.members {
+                self.steps.push(Step::Enroll { member, amount });
+            }
+            self.enrolled = true;
+            self
+        }
+
+        /// Pays out the current round to the next member in the queue.
+        pub fn payout(mut self) -> Self {
+            self.steps.push(Step::Payout { member: self.payouts % self.members });
+            self.payouts += 1;
+            // The installments of the first round of a cycle are paid when the members join or enroll
+            let cycle_ended = self.payouts.is_multiple_of(self.members);
+            self.unpaid = !(cycle_ended && self.enrolled);
+            if cycle_ended {
+                self.enrolled = false;
+            }
+            self
+        }
+
+        /// Runs `rounds` complete rounds, each paying `amount` per member: the members pay the installment of the round
+        /// if it is not paid yet, and the round is paid out. Before the last payout of a cycle, every member enrolls in the
+        /// next cycle if more rounds follow.
+        pub fn rounds(mut self, rounds: usize, amount: Balance) -> Self {
+            for round in 0..rounds {
+                if self.unpaid {
+                    self = self.installments(amount);
+                }
+                if (self.payouts + 1).is_multiple_of(self.members) && round + 1 < rounds {
+                    self = self.enroll(amount);
+                }
+                self = self.payout();
+            }
+            self
+        }
+
+        /// Returns the number of members.
+        pub fn member_count(&self) -> usize {
+            self.members
+        }
+
+        /// Returns the pool settings.
+        pub fn pool_config(&self) -> Config {
+            self.config.clone()
+        }
+
+        /// Returns the steps of the scenario, in order.
+        pub fn steps(&self) -> &[Step] {
+            &self.steps
+        }
+
+        /// Deploys a pool off-chain, with Alice as the owner, and applies every step.
+        ///
+        /// The pool holds only the existential deposit when it is deployed, and each member holds exactly what they pay
+        /// during the scenario, so payouts are paid from the contributions alone.
+        ///
+        /// Panics with the failing step if a message returns an error, so a broken fixture points at its cause.
+        pub fn build(&self) -> Raiser {
+            let accounts = ink::env::test::default_accounts::<crate::RaiserEnvironment>();
+            let minimum_balance = ink::env::minimum_balance::<crate::RaiserEnvironment>();
+            ink::env::test::set_callee::<crate::RaiserEnvironment>(AccountId::from([0x42; 32]));
+            ink::env::test::set_account_balance::<crate::RaiserEnvironment>(AccountId::from([0x42; 32]), minimum_balance);
+            for index in 0..self.members {
+                let paid: Balance = self
+                    .steps
+                    .iter()
+                    .map(|step| match *step {
+                        Step::Join { member, amount } | Step::PayInstallment { member, amount } | Step::Enroll { member, amount }
+                            if member == index =>
+                        {
+                            amount
+                        }
+                        _ => 0,
+                    })
+                    .sum();
+                ink::env::test::set_account_balance::<crate::RaiserEnvironment>(member(index), minimum_balance + paid);
+            }
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            let mut contract = Raiser::new_with_config(self.config.clone()).expect("invalid scenario config");
+
+            for step in &self.steps {
+                let result = match *step {
+                    Step::Join { member: index, amount } => {
+                        ink::env::test::set_caller::<crate::RaiserEnvironment>(member(index));
+                        ink::env::test::transfer_in::<crate::RaiserEnvironment>(amount);
+                        contract.contribute()
+                    }
+                    Step::PayInstallment { member: index, amount } => {
+                        ink::env::test::set_caller::<crate::RaiserEnvironment>(member(index));
+                        ink::env::test::transfer_in::<crate::RaiserEnvironment>(amount);
+                        contract.contribute_round()
+                    }
+                    Step::Enroll { member: index, amount } => {
+                        ink::env::test::set_caller::<crate::RaiserEnvironment>(member(index));
+                        ink::env::test::transfer_in::<crate::RaiserEnvironment>(amount);
+                        contract.enroll_next_cycle()
+                    }
+                    Step::Payout { member: index } => {
+                        assert_eq!(contract.get_next_requester(), Some(member(index)), "scenario step {:?}: unexpected next requester", step);
+                        ink::env::test::set_caller::<crate::RaiserEnvironment>(member(index));
+                        ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(0);
+                        contract.request_token().and_then(|()| {
+                            let (request_id, ..) = contract.get_pending_requests(0, 1)[0];
+                            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+                            contract.approve_request(request_id)
+                        })
+                    }
+                };
+                assert_eq!(result, Ok(()), "scenario step {:?} failed", step);
+            }
+
+            ink::env::test::set_caller::<crate::RaiserEnvironment>(accounts.alice);
+            ink::env::test::set_value_transferred::<crate::RaiserEnvironment>(0);
+            contract
+        }
+    }
+}
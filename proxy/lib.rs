@@ -0,0 +1,122 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// A contract that holds a membership in a `Raiser` pool, the way a DAO treasury or a multisig would.
+///
+/// It is used by the end-to-end tests to check that a pool works with contract callers:
+/// - `contribute`: Joins the pool with the transferred value.
+/// - `request_token`: Requests the payout of the proxy.
+/// - `claim`: Claims the escrowed payout of the proxy, which the pool sends to this contract.
+
+#[ink::contract(env = raiser::RaiserEnvironment)]
+mod raiser_proxy {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use raiser::traits::RaiserPool;
+    use raiser::Error;
+
+    #[ink(storage)]
+    pub struct RaiserProxy {
+        pool: AccountId,
+    }
+
+    impl RaiserProxy {
+        /// Creates a proxy for the pool at `pool`.
+        #[ink(constructor)]
+        pub fn new(pool: AccountId) -> Self {
+            Self { pool }
+        }
+
+        /// Joins the pool with the transferred value.
+        #[ink(message, payable)]
+        pub fn contribute(&mut self) -> Result<(), Error> {
+            build_call::<Environment>()
+                .call(self.pool)
+                .transferred_value(self.env().transferred_value())
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("RaiserPool::contribute"))))
+                .returns::<Result<(), Error>>()
+                .invoke()
+        }
+
+        /// Requests the payout of the proxy.
+        #[ink(message)]
+        pub fn request_token(&mut self) -> Result<(), Error> {
+            let mut pool: ink::contract_ref!(RaiserPool) = self.pool.into();
+            pool.request_token()
+        }
+
+        /// Claims the escrowed payout of the proxy.
+        #[ink(message)]
+        pub fn claim(&mut self) -> Result<(), Error> {
+            let mut pool: ink::contract_ref!(RaiserPool) = self.pool.into();
+            pool.claim()
+        }
+
+        /// Returns the pool this proxy is a member of.
+        #[ink(message)]
+        pub fn pool(&self) -> AccountId {
+            self.pool
+        }
+    }
+
+    /// End-to-end tests of a pool with a contract member, run against a node with `cargo test --features e2e-tests`.
+    ///
+    /// They live in this crate so the pool and the proxy are compiled against the same `raiser::RaiserEnvironment`.
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::{ChainBackend, ContractsBackend};
+        use raiser::{ConfigPatch, Raiser, RaiserRef};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// The contribution of the proxy: one token.
+        const CONTRIBUTION: Balance = 100 * raiser::RaiserEnvironment::CENT;
+
+        // This test checks that a contract can be a member, like a DAO treasury.
+        // A proxy contract joins the pool, requests its payout and pulls it with `claim`, so the pool never pushes funds to it.
+        #[ink_e2e::test(environment = raiser::RaiserEnvironment)]
+        async fn contract_member_works<Client: E2EBackend<raiser::RaiserEnvironment>>(mut client: Client) -> E2EResult<()> {
+            let mut constructor = RaiserRef::new();
+            let pool = client
+                .instantiate("raiser", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut pool_builder = pool.call_builder::<Raiser>();
+
+            let patch = ConfigPatch {
+                max_contributors: Some(1),
+                claim_window: Some(60_000),
+                ..Default::default()
+            };
+            let update_config = pool_builder.update_config(patch);
+            client.call(&ink_e2e::alice(), &update_config).submit().await.expect("update_config failed");
+
+            let mut proxy_constructor = RaiserProxyRef::new(pool.account_id);
+            let proxy = client
+                .instantiate("raiser_proxy", &ink_e2e::bob(), &mut proxy_constructor)
+                .submit()
+                .await
+                .expect("instantiate proxy failed");
+            let mut proxy_builder = proxy.call_builder::<RaiserProxy>();
+
+            let contribute = proxy_builder.contribute();
+            client.call(&ink_e2e::bob(), &contribute).value(CONTRIBUTION).submit().await.expect("contribute failed");
+            let balance_of = pool_builder.balance_of(proxy.account_id);
+            assert_eq!(client.call(&ink_e2e::alice(), &balance_of).dry_run().await?.return_value(), CONTRIBUTION);
+
+            let request_token = proxy_builder.request_token();
+            client.call(&ink_e2e::bob(), &request_token).submit().await.expect("request_token failed");
+            let approve_request = pool_builder.approve_request(1);
+            client.call(&ink_e2e::alice(), &approve_request).submit().await.expect("approve_request failed");
+
+            let before = client.free_balance(proxy.account_id).await?;
+            let claim = proxy_builder.claim();
+            client.call(&ink_e2e::bob(), &claim).submit().await.expect("claim failed");
+            let after = client.free_balance(proxy.account_id).await?;
+            assert_eq!(after - before, CONTRIBUTION);
+            Ok(())
+        }
+    }
+}
+
+pub use self::raiser_proxy::{RaiserProxy, RaiserProxyRef};